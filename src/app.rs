@@ -13,13 +13,19 @@ use ratatui_garnish::GarnishableWidget;
 use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::config::Config;
+use crate::config::{Config, LaunchMode};
 use crate::event::{self, AppEvent};
+use crate::models::agent::AgentBackend;
 use crate::screens::{
-    AgentDetailScreen, BrowserScreen, DashboardScreen, Screen, ScreenAction, SpawnWizard,
+    AgentDetailScreen, BrowserScreen, ChangesScreen, CleanupScreen, DashboardScreen, HistoryScreen, Screen, ScreenAction,
+    SpawnWizard, ToolStatsScreen, TranscriptScreen, UsageScreen,
 };
-use crate::services::{AgentManager, SessionStore, Theme};
+use crate::services::cleanup::Protection;
+use crate::services::clipboard::CopyMethod;
+use crate::services::terminal_launcher;
+use crate::services::{AgentManager, Clipboard, SessionStore, SessionWatcher, TerminalCommand, Theme};
 
 /// Result of running the application.
 #[derive(Debug)]
@@ -27,7 +33,11 @@ pub enum AppResult {
     /// User quit normally.
     Exit,
     /// User selected a session to launch.
-    LaunchSession { session_id: String, project_path: String },
+    LaunchSession {
+        session_id: String,
+        project_path: String,
+        mode: LaunchMode,
+    },
     /// Start a new session in the given project.
     NewSession { project_path: String },
     /// Open lazygit in the project directory.
@@ -36,8 +46,12 @@ pub enum AppResult {
     OpenGithub { project_path: String },
     /// Open terminal in the project directory.
     OpenTerminal { project_path: String },
-    /// Open editor in the project directory.
-    OpenEditor { project_path: String },
+    /// Open editor in the project directory, optionally at a file and line.
+    OpenEditor {
+        project_path: String,
+        file: Option<String>,
+        line: Option<usize>,
+    },
 }
 
 /// Which tab/screen is currently active.
@@ -49,18 +63,33 @@ pub enum AppScreen {
     AgentDetail,
     /// Session browser (the original total-recall).
     Sessions,
+    /// Full transcript of one session, opened from the browser.
+    Transcript,
+    /// Token usage and cost rollups, opened from the browser.
+    Usage,
+    /// Archive and restore old sessions, opened from the browser.
+    Cleanup,
+    /// Files touched by one session, opened from the browser.
+    Changes,
+    /// Tool call analytics, opened from the browser.
+    ToolStats,
+    /// Activity heatmap and timelines, opened from the browser.
+    History,
 }
 
+/// How long a status message stays visible outside the dashboard.
+const STATUS_TOAST_DURATION: Duration = Duration::from_secs(3);
+
 /// Application state.
 pub struct App {
     current_screen: AppScreen,
     should_quit: bool,
-    launch_session: Option<(String, String)>,
+    launch_session: Option<(String, String, Option<LaunchMode>)>,
     new_session: Option<String>,
     open_lazygit: Option<String>,
     open_github: Option<String>,
     open_terminal: Option<String>,
-    open_editor: Option<String>,
+    open_editor: Option<(String, Option<String>, Option<usize>)>,
 
     // Theme
     theme: Arc<Theme>,
@@ -73,6 +102,12 @@ pub struct App {
     dashboard_screen: DashboardScreen,
     agent_detail_screen: AgentDetailScreen,
     browser_screen: BrowserScreen,
+    transcript_screen: TranscriptScreen,
+    usage_screen: UsageScreen,
+    cleanup_screen: CleanupScreen,
+    changes_screen: ChangesScreen,
+    tool_stats_screen: ToolStatsScreen,
+    history_screen: HistoryScreen,
     spawn_wizard: SpawnWizard,
 
     // Agent detail: which agent index we're viewing
@@ -80,9 +115,11 @@ pub struct App {
 
     // Status bar info
     status_message: String,
+    /// When the status message was last set by a screen action, so other
+    /// screens can show it briefly
+    status_shown_at: Option<Instant>,
 
-    // Tick counter for periodic agent polling
-    tick_count: u64,
+    clipboard: Clipboard,
 }
 
 impl App {
@@ -94,15 +131,18 @@ impl App {
         // Initialize services
         let session_store = Arc::new(SessionStore::new(config.clone())?);
 
-        // We'll create a temporary sender for AgentManager construction.
-        // The real event loop sender replaces this later.
-        let (event_tx, _) = tokio::sync::mpsc::unbounded_channel();
-        let agent_manager = AgentManager::new(config.clone(), event_tx)?;
+        let agent_manager = AgentManager::new(config.clone())?;
 
         // Initialize screens
         let browser_screen = BrowserScreen::new(session_store.clone(), config.clone(), theme.clone());
         let dashboard_screen = DashboardScreen::new(theme.clone());
         let agent_detail_screen = AgentDetailScreen::new(theme.clone());
+        let transcript_screen = TranscriptScreen::new(theme.clone(), config.clone());
+        let usage_screen = UsageScreen::new(theme.clone(), config.clone());
+        let cleanup_screen = CleanupScreen::new(theme.clone(), config.clone());
+        let changes_screen = ChangesScreen::new(theme.clone(), config.clone());
+        let tool_stats_screen = ToolStatsScreen::new(theme.clone());
+        let history_screen = HistoryScreen::new(theme.clone());
         let spawn_wizard = SpawnWizard::new(theme.clone());
 
         Ok(Self {
@@ -120,10 +160,17 @@ impl App {
             dashboard_screen,
             agent_detail_screen,
             browser_screen,
+            transcript_screen,
+            usage_screen,
+            cleanup_screen,
+            changes_screen,
+            tool_stats_screen,
+            history_screen,
             spawn_wizard,
             focused_agent_index: None,
             status_message: "Loading...".to_string(),
-            tick_count: 0,
+            status_shown_at: None,
+            clipboard: Clipboard::new(),
         })
     }
 
@@ -167,8 +214,8 @@ impl App {
             );
         }
 
-        // Reconcile persisted agents with actual tmux state
-        self.agent_manager.poll_agents();
+        // Refresh the full-text search index in the background
+        self.browser_screen.refresh_search_index();
     }
 
     /// Main event loop using channel-based events.
@@ -178,10 +225,20 @@ impl App {
     ) -> Result<AppResult> {
         let (event_tx, mut event_rx) = event::spawn_event_tasks();
 
-        // Give the agent manager the real event sender
-        // (We can't swap it in after construction easily, but the tick-based polling
-        // in handle_tick() handles agent monitoring directly)
-        let _ = event_tx; // keep alive to prevent input thread from stopping
+        // Poll agents in the background; its first poll reconciles persisted
+        // agents with the actual tmux state
+        self.agent_manager.start_monitor(event_tx.clone());
+
+        // Watch the projects directory so new sessions/messages show up live.
+        // The watcher stops when dropped at the end of the loop.
+        let _session_watcher =
+            match SessionWatcher::start(&self.config.claude_projects_dir(), event_tx.clone()) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    tracing::warn!("Live session updates disabled: {}", e);
+                    None
+                }
+            };
 
         loop {
             // Draw UI
@@ -206,12 +263,25 @@ impl App {
                             | (KeyModifiers::CONTROL, KeyCode::Char('q')) => {
                                 self.should_quit = true;
                             }
+                            // Text input (e.g. search) swallows Tab/Esc and the rest
+                            _ if self.screen_captures_input() => {
+                                let action = self.handle_screen_key(key).await;
+                                self.process_action(action);
+                            }
                             // Tab toggles between Dashboard and Sessions
                             (KeyModifiers::NONE, KeyCode::Tab) => {
                                 self.current_screen = match self.current_screen {
                                     AppScreen::Dashboard => AppScreen::Sessions,
                                     AppScreen::Sessions => AppScreen::Dashboard,
                                     AppScreen::AgentDetail => AppScreen::Dashboard,
+                                    AppScreen::Transcript
+                                    | AppScreen::Usage
+                                    | AppScreen::Cleanup
+                                    | AppScreen::Changes
+                                    | AppScreen::ToolStats
+                                    | AppScreen::History => {
+                                        AppScreen::Sessions
+                                    }
                                 };
                             }
                             // Esc from AgentDetail goes back to Dashboard
@@ -219,8 +289,10 @@ impl App {
                                 self.current_screen = AppScreen::Dashboard;
                                 self.focused_agent_index = None;
                             }
-                            // Esc quits from sessions screen
-                            (KeyModifiers::NONE, KeyCode::Esc) if self.current_screen == AppScreen::Sessions => {
+                            // Esc quits from sessions screen (after clearing any filter)
+                            (KeyModifiers::NONE, KeyCode::Esc)
+                                if self.current_screen == AppScreen::Sessions && !self.browser_screen.has_filter() =>
+                            {
                                 self.should_quit = true;
                             }
                             _ => {
@@ -233,9 +305,14 @@ impl App {
                     AppEvent::Tick => {
                         self.handle_tick();
                     }
-                    AppEvent::AgentUpdate { agent_id } | AppEvent::AgentExited { agent_id } => {
-                        // Agent events update status message
-                        if let Some(agent) = self.agent_manager.get_by_id_mut(&agent_id) {
+                    AppEvent::SessionChanged { path, change } => {
+                        tracing::debug!("Session file {:?}: {:?}", path, change);
+                        self.browser_screen.apply_session_change(&path, change);
+                        self.transcript_screen.apply_session_change(&path, change);
+                    }
+                    AppEvent::AgentUpdate { agent_id, diff } | AppEvent::AgentExited { agent_id, diff } => {
+                        // Status changes update the status message
+                        if let Some(agent) = self.agent_manager.apply_diff(&agent_id, &diff) {
                             self.status_message = format!(
                                 "Agent {} — {}",
                                 agent.name,
@@ -250,16 +327,38 @@ impl App {
                 break;
             }
         }
+        self.agent_manager.stop_monitor();
 
         Ok(self.build_result())
     }
 
+    /// Whether the current screen is capturing text input.
+    fn screen_captures_input(&self) -> bool {
+        match self.current_screen {
+            AppScreen::Sessions => self.browser_screen.is_capturing_input(),
+            AppScreen::Transcript => self.transcript_screen.is_capturing_input(),
+            AppScreen::Dashboard
+            | AppScreen::AgentDetail
+            | AppScreen::Usage
+            | AppScreen::Cleanup
+            | AppScreen::Changes
+            | AppScreen::ToolStats
+            | AppScreen::History => false,
+        }
+    }
+
     /// Handle key events for the current screen.
     async fn handle_screen_key(&mut self, key: crossterm::event::KeyEvent) -> ScreenAction {
         match self.current_screen {
             AppScreen::Dashboard => self.handle_dashboard_key(key),
             AppScreen::AgentDetail => self.handle_detail_key(key),
             AppScreen::Sessions => self.browser_screen.handle_key(key).await,
+            AppScreen::Transcript => self.transcript_screen.handle_key(key).await,
+            AppScreen::Usage => self.usage_screen.handle_key(key).await,
+            AppScreen::Cleanup => self.cleanup_screen.handle_key(key).await,
+            AppScreen::Changes => self.changes_screen.handle_key(key).await,
+            AppScreen::ToolStats => self.tool_stats_screen.handle_key(key).await,
+            AppScreen::History => self.history_screen.handle_key(key).await,
         }
     }
 
@@ -308,8 +407,8 @@ impl App {
                 }
             }
             KeyCode::F(5) => {
-                self.agent_manager.poll_agents();
-                ScreenAction::StatusMessage("Refreshed".to_string())
+                self.agent_manager.refresh();
+                ScreenAction::StatusMessage("Refreshing agents…".to_string())
             }
             KeyCode::Esc => {
                 self.should_quit = true;
@@ -364,9 +463,64 @@ impl App {
             ScreenAction::None => {}
             ScreenAction::StatusMessage(msg) => {
                 self.status_message = msg;
+                self.status_shown_at = Some(Instant::now());
+            }
+            ScreenAction::CopyToClipboard { text, what } => {
+                self.status_message = match self.clipboard.copy(&text) {
+                    Ok(CopyMethod::System) => format!("Copied {}", what),
+                    Ok(CopyMethod::Osc52) => format!("Copied {} (via terminal)", what),
+                    Err(e) => format!("Failed to copy {}: {}", what, e),
+                };
+                self.status_shown_at = Some(Instant::now());
+            }
+            ScreenAction::OpenTranscript { session } => {
+                let name = session.display_name();
+                match self.transcript_screen.open(*session) {
+                    Ok(()) => self.current_screen = AppScreen::Transcript,
+                    Err(e) => {
+                        self.status_message = format!("Failed to open {}: {}", name, e);
+                        self.status_shown_at = Some(Instant::now());
+                    }
+                }
+            }
+            ScreenAction::OpenChanges { session } => {
+                let name = session.display_name();
+                match self.changes_screen.open(*session) {
+                    Ok(()) => self.current_screen = AppScreen::Changes,
+                    Err(e) => {
+                        self.status_message = format!("Failed to open {}: {}", name, e);
+                        self.status_shown_at = Some(Instant::now());
+                    }
+                }
+            }
+            ScreenAction::OpenToolStats { session_id, project_path } => {
+                self.tool_stats_screen
+                    .open(self.browser_screen.all_sessions(), session_id, project_path);
+                self.current_screen = AppScreen::ToolStats;
+            }
+            ScreenAction::OpenHistory => {
+                self.history_screen.open(self.browser_screen.all_sessions());
+                self.current_screen = AppScreen::History;
+            }
+            ScreenAction::OpenUsage => {
+                self.usage_screen.open(self.browser_screen.session_store());
+                self.current_screen = AppScreen::Usage;
+            }
+            ScreenAction::OpenCleanup => {
+                let protection = Protection::from_agents(self.agent_manager.agents());
+                self.cleanup_screen.open(self.browser_screen.session_store(), protection);
+                self.current_screen = AppScreen::Cleanup;
             }
-            ScreenAction::LaunchSession { session_id, project_path } => {
-                self.launch_session = Some((session_id, project_path));
+            ScreenAction::RunCleanup => {
+                // Agents may have started since the screen opened
+                let protection = Protection::from_agents(self.agent_manager.agents());
+                self.status_message = self.cleanup_screen.archive(protection);
+            }
+            ScreenAction::BackToSessions => {
+                self.current_screen = AppScreen::Sessions;
+            }
+            ScreenAction::LaunchSession { session_id, project_path, mode } => {
+                self.launch_session = Some((session_id, project_path, mode));
                 self.should_quit = true;
             }
             ScreenAction::NewSession { project_path } => {
@@ -385,14 +539,14 @@ impl App {
                 self.open_terminal = Some(project_path);
                 self.should_quit = true;
             }
-            ScreenAction::OpenEditor { project_path } => {
-                self.open_editor = Some(project_path);
+            ScreenAction::OpenEditor { project_path, file, line } => {
+                self.open_editor = Some((project_path, file, line));
                 self.should_quit = true;
             }
             ScreenAction::OpenSpawnWizard => {
                 let projects = self.browser_screen.projects().to_vec();
                 let registry = self.agent_manager.registry();
-                self.spawn_wizard.open(projects, registry);
+                self.spawn_wizard.open(projects, registry, self.config.agents.backend);
             }
             ScreenAction::KillAgent { index } => {
                 tracing::debug!("Processing KillAgent at index {}", index);
@@ -451,16 +605,18 @@ impl App {
             }
             ScreenAction::AttachAgent { index } => {
                 if let Some(agent) = self.agent_manager.get(index) {
-                    if agent.status.is_alive() {
-                        let tmux_session = agent.tmux_session.clone();
-                        let _ = std::process::Command::new("ghostty")
-                            .arg("-e")
-                            .arg("tmux")
-                            .arg("attach-session")
-                            .arg("-t")
-                            .arg(&tmux_session)
-                            .spawn();
-                        self.status_message = format!("Attached to {}", agent.name);
+                    if agent.backend == AgentBackend::Headless {
+                        // No terminal to attach to; Enter shows its output
+                        let log = agent.log_path.as_deref().unwrap_or_else(|| std::path::Path::new("?"));
+                        self.status_message = format!("{} runs headless, logging to {}", agent.name, log.display());
+                    } else if agent.status.is_alive() {
+                        let launcher = terminal_launcher::from_config(&self.config.launcher);
+                        let window = TerminalCommand::new(["tmux", "attach-session", "-t", &agent.tmux_session])
+                            .class(self.config.launcher.class.as_deref());
+                        self.status_message = match launcher.command(&window).spawn() {
+                            Ok(_) => format!("Attached to {}", agent.name),
+                            Err(e) => format!("Failed to open {}: {}", launcher.name(), e),
+                        };
                     } else {
                         self.status_message = format!("Agent {} is not alive", agent.name);
                     }
@@ -481,6 +637,7 @@ impl App {
             request.agent_type,
             request.task_prompt,
             request.use_worktree,
+            request.backend,
         ) {
             Ok(index) => {
                 if let Some(agent) = self.agent_manager.get(index) {
@@ -495,33 +652,23 @@ impl App {
         }
     }
 
-    /// Periodic tick handler — ticks dashboard every tick, polls agents every 6th.
+    /// Periodic tick handler — drives dashboard animations.
+    ///
+    /// Agent polling happens in the monitor task, which sends `AgentUpdate`
+    /// and `AgentExited` events.
     fn handle_tick(&mut self) {
-        self.tick_count += 1;
-
-        // Tick dashboard animations every tick
         self.dashboard_screen.tick();
-
-        // Poll agents every 6 ticks (3 seconds at 500ms tick rate)
-        if self.tick_count % 6 == 0 {
-            self.agent_manager.poll_agents();
-
-            // Update status
-            let active = self.agent_manager.active_count();
-            let total = self.agent_manager.agents().len();
-            if total > 0 {
-                self.status_message = format!(
-                    "{} agents ({} active)",
-                    total, active
-                );
-            }
-        }
+        self.tool_stats_screen.tick();
     }
 
     /// Build the final AppResult from state flags.
     fn build_result(&mut self) -> AppResult {
-        if let Some((session_id, project_path)) = self.launch_session.take() {
-            AppResult::LaunchSession { session_id, project_path }
+        if let Some((session_id, project_path, mode)) = self.launch_session.take() {
+            AppResult::LaunchSession {
+                session_id,
+                project_path,
+                mode: mode.unwrap_or(self.config.launcher.mode),
+            }
         } else if let Some(project_path) = self.new_session.take() {
             AppResult::NewSession { project_path }
         } else if let Some(project_path) = self.open_lazygit.take() {
@@ -530,8 +677,8 @@ impl App {
             AppResult::OpenGithub { project_path }
         } else if let Some(project_path) = self.open_terminal.take() {
             AppResult::OpenTerminal { project_path }
-        } else if let Some(project_path) = self.open_editor.take() {
-            AppResult::OpenEditor { project_path }
+        } else if let Some((project_path, file, line)) = self.open_editor.take() {
+            AppResult::OpenEditor { project_path, file, line }
         } else {
             AppResult::Exit
         }
@@ -559,6 +706,31 @@ impl App {
             AppScreen::Sessions => {
                 self.browser_screen.draw(f, area);
             }
+            AppScreen::Transcript => {
+                self.transcript_screen.draw(f, area);
+            }
+            AppScreen::Usage => {
+                self.usage_screen.draw(f, area);
+            }
+            AppScreen::Cleanup => {
+                self.cleanup_screen.draw(f, area);
+            }
+            AppScreen::Changes => {
+                self.changes_screen.draw(f, area);
+            }
+            AppScreen::ToolStats => {
+                self.tool_stats_screen.draw(f, area);
+            }
+            AppScreen::History => {
+                self.history_screen.draw(f, area);
+            }
+        }
+
+        // Only the dashboard has a status bar; elsewhere show new messages briefly
+        if self.current_screen != AppScreen::Dashboard
+            && self.status_shown_at.is_some_and(|at| at.elapsed() < STATUS_TOAST_DURATION)
+        {
+            self.draw_status_toast(f, area);
         }
 
         // Spawn wizard overlay (drawn on top)
@@ -566,4 +738,27 @@ impl App {
             self.spawn_wizard.draw(f, f.area());
         }
     }
+
+    /// Draw the status message over the bottom-right corner.
+    fn draw_status_toast(&self, f: &mut ratatui::Frame, area: ratatui::layout::Rect) {
+        use ratatui::style::Style;
+        use ratatui::widgets::{Clear, Paragraph};
+
+        let text = format!(" {} ", self.status_message);
+        let width = (text.chars().count() as u16).min(area.width);
+        if width == 0 || area.height == 0 {
+            return;
+        }
+        let toast_area = ratatui::layout::Rect {
+            x: area.x + area.width - width,
+            y: area.y + area.height - 1,
+            width,
+            height: 1,
+        };
+        f.render_widget(Clear, toast_area);
+        f.render_widget(
+            Paragraph::new(text).style(Style::default().fg(self.theme.background).bg(self.theme.color3)),
+            toast_area,
+        );
+    }
 }
@@ -0,0 +1,912 @@
+//! Non-interactive subcommands for scripting.
+//!
+//! Every command prints a plain-text table by default, or JSON with `--json`.
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::app::AppResult;
+use crate::config::{Config, LaunchMode};
+use crate::models::message::tool_result_text;
+use crate::models::agent::AgentBackend;
+use crate::models::{ContentBlock, MessageContent, Project, Session};
+use crate::services::cleanup::{self, CleanupFilter, Protection};
+use crate::services::exporter::{self, ExportFormat, ExportOptions};
+use crate::services::tool_stats::{format_latency, ToolCounter, ToolStats};
+use crate::services::{AgentManager, MetadataStore, SessionStore};
+use crate::utils::{local_time, truncate};
+
+/// Default number of rows for list/search commands.
+const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List projects, most recently active first
+    ListProjects,
+    /// List sessions, most recent first
+    ListSessions {
+        /// Only sessions of this project (name, path or encoded directory)
+        #[arg(short, long)]
+        project: Option<String>,
+        /// Maximum number of sessions to print (0 = all)
+        #[arg(short = 'n', long, default_value_t = DEFAULT_LIMIT)]
+        limit: usize,
+    },
+    /// Print a session's summary and conversation
+    Show {
+        /// Session ID, unique ID prefix or slug
+        session: String,
+    },
+    /// Resume a session with claude (in this terminal by default)
+    Resume {
+        /// Session ID, unique ID prefix or slug
+        session: String,
+        /// Where to run claude
+        #[arg(short, long, value_enum, default_value_t = LaunchMode::Exec)]
+        mode: LaunchMode,
+    },
+    /// Export a session transcript as Markdown, HTML or JSON
+    Export {
+        /// Session ID, unique ID prefix or slug
+        session: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Include thinking blocks
+        #[arg(long)]
+        thinking: bool,
+        /// Leave out tool calls and results
+        #[arg(long)]
+        no_tools: bool,
+        /// Truncate tool results longer than this many characters (0 = no limit)
+        #[arg(long, default_value_t = 2_000)]
+        max_result_chars: usize,
+        /// Keep absolute home directory paths instead of replacing them with ~
+        #[arg(long)]
+        no_redact: bool,
+    },
+    /// Point a moved project's sessions at its new directory
+    Relink {
+        /// Project (name, old path or encoded directory)
+        project: String,
+        /// Where the project directory lives now
+        new_path: PathBuf,
+    },
+    /// Archive old, large or empty sessions (a dry run unless --apply)
+    Cleanup {
+        /// Sessions last active more than this many days ago
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<i64>,
+        /// Sessions taking at least this much space, e.g. 500K or 20M
+        #[arg(long, value_name = "SIZE", value_parser = cleanup::parse_size)]
+        larger_than: Option<u64>,
+        /// Sessions with at most this many messages
+        #[arg(long, value_name = "N")]
+        max_messages: Option<usize>,
+        /// Only sessions that never got past the first prompt
+        #[arg(long)]
+        empty: bool,
+        /// Only sessions of this project (name, path or encoded directory)
+        #[arg(short, long)]
+        project: Option<String>,
+        /// Move the sessions into an archive instead of only listing them
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Restore an archive made by `cleanup`, or list archives if none is given
+    Restore {
+        /// Archive file name or path
+        archive: Option<String>,
+    },
+    /// Tool call counts, failure rates, latency and the most-run Bash commands
+    Tools {
+        /// Only this session (ID, unique ID prefix or slug)
+        session: Option<String>,
+        /// Only sessions of this project (name, path or encoded directory)
+        #[arg(short, long, conflicts_with = "session")]
+        project: Option<String>,
+        /// Maximum number of rows per table
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Full-text search across all session transcripts
+    Search {
+        query: String,
+        /// Maximum number of sessions to print
+        #[arg(short = 'n', long, default_value_t = DEFAULT_LIMIT)]
+        limit: usize,
+    },
+    /// Manage spawned agents
+    #[command(subcommand)]
+    Agents(AgentsCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AgentsCommand {
+    /// List tracked agents and their status
+    List,
+    /// Spawn an agent, headless or in a detached tmux session
+    Spawn {
+        /// Project directory to work in
+        #[arg(short, long)]
+        project: PathBuf,
+        /// Agent type from the registry
+        #[arg(short = 't', long = "type", default_value = "general-purpose")]
+        agent_type: String,
+        /// Run in a fresh jj worktree
+        #[arg(short, long)]
+        worktree: bool,
+        /// How to run claude (default from config: headless)
+        #[arg(short, long, value_enum)]
+        backend: Option<AgentBackend>,
+        /// Task prompt for claude
+        prompt: String,
+    },
+    /// Kill an agent's tmux session or process
+    Kill {
+        /// Agent name, ID, ID prefix or list index
+        agent: String,
+    },
+}
+
+/// Run a subcommand to completion.
+pub async fn run(command: Command, config: Config, json: bool) -> Result<()> {
+    let config = Arc::new(config);
+
+    match command {
+        Command::ListProjects => {
+            let store = load_store(&config)?;
+            list_projects(store.projects(), &config, json)
+        }
+        Command::ListSessions { project, limit } => {
+            let store = load_store(&config)?;
+            let mut sessions: Vec<&Session> = match &project {
+                Some(query) => {
+                    let project = find_project(store.projects(), query)?;
+                    store
+                        .sessions_for_project(&project.encoded_path)
+                        .map(|s| s.iter().collect())
+                        .unwrap_or_default()
+                }
+                None => store.all_sessions().collect(),
+            };
+            sessions.sort_by_key(|s| std::cmp::Reverse(s.last_message));
+            if limit > 0 {
+                sessions.truncate(limit);
+            }
+            list_sessions(&sessions, &config, json)
+        }
+        Command::Show { session } => {
+            let store = load_store(&config)?;
+            let session = find_session(store.all_sessions(), &session)?;
+            show_session(session, &config, json)
+        }
+        Command::Resume { session, mode } => {
+            let store = load_store(&config)?;
+            let session = find_session(store.all_sessions(), &session)?;
+            crate::launch(
+                AppResult::LaunchSession {
+                    session_id: session.id.clone(),
+                    project_path: session.project_path.clone(),
+                    mode,
+                },
+                &config,
+            );
+            Ok(())
+        }
+        Command::Export {
+            session,
+            format,
+            output,
+            thinking,
+            no_tools,
+            max_result_chars,
+            no_redact,
+        } => {
+            let store = load_store(&config)?;
+            let session = find_session(store.all_sessions(), &session)?;
+            let entries = SessionStore::load_entries(&session.file_path)?;
+            let options = ExportOptions {
+                include_thinking: thinking,
+                include_tools: !no_tools,
+                max_tool_result_chars: (max_result_chars > 0).then_some(max_result_chars),
+                redact_home: !no_redact,
+            };
+            let rendered = exporter::export(session, &entries, format, &options);
+            match output {
+                Some(path) => std::fs::write(&path, rendered)
+                    .with_context(|| format!("Failed to write {:?}", path))?,
+                None => print!("{}", rendered),
+            }
+            Ok(())
+        }
+        Command::Relink { project, new_path } => {
+            let mut store = load_store(&config)?;
+            let encoded_path = find_project(store.projects(), &project)?.encoded_path.clone();
+            let new_encoded = store.relink_project(&encoded_path, &new_path)?;
+            let project = store
+                .projects()
+                .iter()
+                .find(|p| p.encoded_path == new_encoded)
+                .context("Relinked project disappeared")?;
+
+            if json {
+                return print_json(&json!({
+                    "encoded_path": project.encoded_path,
+                    "path": project.decoded_path,
+                    "session_count": project.session_count,
+                }));
+            }
+            println!(
+                "Relinked {} session(s) to {}",
+                project.session_count, project.decoded_path
+            );
+            Ok(())
+        }
+        Command::Cleanup {
+            older_than,
+            larger_than,
+            max_messages,
+            empty,
+            project,
+            apply,
+        } => {
+            let filter = CleanupFilter {
+                older_than_days: older_than,
+                min_bytes: larger_than,
+                max_messages,
+                empty_only: empty,
+            };
+            if filter.is_unbounded() {
+                bail!("Pick sessions with --older-than, --larger-than, --max-messages or --empty");
+            }
+
+            let store = load_store(&config)?;
+            let mut sessions: Vec<&Session> = match &project {
+                Some(query) => {
+                    let project = find_project(store.projects(), query)?;
+                    store
+                        .sessions_for_project(&project.encoded_path)
+                        .map(|s| s.iter().collect())
+                        .unwrap_or_default()
+                }
+                None => store.all_sessions().collect(),
+            };
+            sessions.sort_by_key(|s| s.last_message);
+            let candidates = cleanup::candidates(&store, sessions);
+
+            let mut manager = AgentManager::new(config.clone())?;
+            manager.poll_agents();
+            let protection = Protection::from_agents(manager.agents());
+            let plan = cleanup::plan(&candidates, &filter, &protection);
+
+            let archive = if apply && !plan.selected.is_empty() {
+                let projects_dir = config.claude_projects_dir();
+                Some(cleanup::archive(&plan.selected, &protection, &projects_dir, &cleanup::archive_dir())?)
+            } else {
+                None
+            };
+
+            if json {
+                let ids = |list: &[&cleanup::Candidate]| -> Vec<String> {
+                    list.iter().map(|c| c.session.id.clone()).collect()
+                };
+                return print_json(&json!({
+                    "dry_run": !apply,
+                    "sessions": ids(&plan.selected),
+                    "bytes": plan.total_bytes(),
+                    "protected": ids(&plan.protected),
+                    "archive": archive,
+                }));
+            }
+
+            let rows = plan
+                .selected
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.session.id.clone(),
+                        local_time(c.session.last_message),
+                        c.session.message_count.to_string(),
+                        cleanup::format_size(c.bytes),
+                        c.session.project_path.clone(),
+                        truncate(&one_line(&c.session.preview_text), 50),
+                    ]
+                })
+                .collect();
+            print_table(&["ID", "LAST MESSAGE", "MSGS", "SIZE", "PROJECT", "PREVIEW"], rows);
+            for candidate in &plan.protected {
+                println!("Skipping {} (in use by a live agent)", candidate.session.id);
+            }
+            match archive {
+                Some(path) => println!(
+                    "Archived {} session(s), freed {}, to {}",
+                    plan.selected.len(),
+                    cleanup::format_size(plan.total_bytes()),
+                    path.display()
+                ),
+                None => println!(
+                    "{} session(s), {} reclaimable{}",
+                    plan.selected.len(),
+                    cleanup::format_size(plan.total_bytes()),
+                    if apply { "" } else { " (dry run, pass --apply to archive)" }
+                ),
+            }
+            Ok(())
+        }
+        Command::Restore { archive } => {
+            let Some(name) = archive else {
+                let archives = cleanup::list_archives()?;
+                if json {
+                    let archives: Vec<_> = archives
+                        .iter()
+                        .map(|a| json!({ "path": a.path, "bytes": a.bytes, "created": a.created }))
+                        .collect();
+                    return print_json(&archives);
+                }
+                let rows = archives
+                    .iter()
+                    .map(|a| {
+                        vec![
+                            a.path.display().to_string(),
+                            a.created.format("%Y-%m-%d %H:%M").to_string(),
+                            cleanup::format_size(a.bytes),
+                        ]
+                    })
+                    .collect();
+                print_table(&["ARCHIVE", "CREATED", "SIZE"], rows);
+                return Ok(());
+            };
+
+            let path = cleanup::find_archive(&name)?;
+            let report = cleanup::restore(&path, &config.claude_projects_dir())?;
+            if json {
+                return print_json(&json!({
+                    "restored": report.restored,
+                    "skipped": report.skipped,
+                }));
+            }
+            println!("Restored {} file(s) from {}", report.restored.len(), path.display());
+            for skipped in &report.skipped {
+                println!("Skipped {} (already exists)", skipped.display());
+            }
+            if !report.skipped.is_empty() {
+                println!("Kept the archive because some files were skipped");
+            }
+            Ok(())
+        }
+        Command::Tools { session, project, limit } => {
+            let store = load_store(&config)?;
+            let sessions: Vec<&Session> = match (&session, &project) {
+                (Some(query), _) => vec![find_session(store.all_sessions(), query)?],
+                (None, Some(query)) => {
+                    let project = find_project(store.projects(), query)?;
+                    store
+                        .sessions_for_project(&project.encoded_path)
+                        .map(|s| s.iter().collect())
+                        .unwrap_or_default()
+                }
+                (None, None) => store.all_sessions().collect(),
+            };
+            let stats = ToolStats::for_sessions(sessions);
+            print_tool_stats(&stats, limit, json)
+        }
+        Command::Search { query, limit } => {
+            let store = load_store(&config)?;
+            let db = MetadataStore::open_default()?;
+            db.sync_search_index(store.all_sessions())?;
+            let hits = db.search(&query, limit)?;
+
+            if json {
+                let hits: Vec<_> = hits
+                    .iter()
+                    .map(|hit| {
+                        json!({
+                            "session_id": hit.session_id,
+                            "project_path": hit.project_path,
+                            "file_path": hit.file_path,
+                            "kind": hit.kind,
+                            "snippet": plain_snippet(&hit.snippet_segments()),
+                            "match_count": hit.match_count,
+                        })
+                    })
+                    .collect();
+                return print_json(&hits);
+            }
+
+            let rows = hits
+                .iter()
+                .map(|hit| {
+                    vec![
+                        hit.session_id.clone(),
+                        hit.match_count.to_string(),
+                        hit.kind.clone(),
+                        hit.project_path.clone(),
+                        plain_snippet(&hit.snippet_segments()),
+                    ]
+                })
+                .collect();
+            print_table(&["SESSION", "HITS", "KIND", "PROJECT", "SNIPPET"], rows);
+            Ok(())
+        }
+        Command::Agents(command) => run_agents(command, config, json),
+    }
+}
+
+fn run_agents(command: AgentsCommand, config: Arc<Config>, json: bool) -> Result<()> {
+    let mut manager = AgentManager::new(config.clone())?;
+
+    match command {
+        AgentsCommand::List => {
+            manager.poll_agents();
+            if json {
+                return print_json(&manager.agents());
+            }
+            let rows = manager
+                .agents()
+                .iter()
+                .enumerate()
+                .map(|(i, agent)| {
+                    vec![
+                        i.to_string(),
+                        agent.name.clone(),
+                        agent.status.label().to_string(),
+                        agent.agent_type.clone(),
+                        agent.project_path.display().to_string(),
+                        local_time(agent.spawned_at),
+                        truncate(&one_line(&agent.task_prompt), 60),
+                    ]
+                })
+                .collect();
+            print_table(
+                &["#", "NAME", "STATUS", "TYPE", "PROJECT", "SPAWNED", "TASK"],
+                rows,
+            );
+            Ok(())
+        }
+        AgentsCommand::Spawn {
+            project,
+            agent_type,
+            worktree,
+            backend,
+            prompt,
+        } => {
+            let project = project
+                .canonicalize()
+                .with_context(|| format!("Project directory not found: {}", project.display()))?;
+            let backend = backend.unwrap_or(config.agents.backend);
+            let index = manager.spawn(project, agent_type, prompt, worktree, backend)?;
+            let agent = &manager.agents()[index];
+            if json {
+                print_json(agent)
+            } else {
+                match &agent.log_path {
+                    Some(log) => println!("Spawned {} (log {})", agent.name, log.display()),
+                    None => println!("Spawned {} (tmux session {})", agent.name, agent.tmux_session),
+                }
+                Ok(())
+            }
+        }
+        AgentsCommand::Kill { agent } => {
+            let index = find_agent(&manager, &agent)?;
+            manager.kill(index)?;
+            let agent = &manager.agents()[index];
+            if json {
+                print_json(agent)
+            } else {
+                println!("Killed {}", agent.name);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn load_store(config: &Arc<Config>) -> Result<SessionStore> {
+    let mut store = SessionStore::new(config.clone())?;
+    store.scan()?;
+    Ok(store)
+}
+
+/// Resolve a project by display name, decoded path or encoded directory name.
+fn find_project<'a>(projects: &'a [Project], query: &str) -> Result<&'a Project> {
+    let query = query.trim_end_matches('/');
+    if let Some(project) = projects
+        .iter()
+        .find(|p| p.decoded_path == query || p.encoded_path == query)
+    {
+        return Ok(project);
+    }
+
+    let by_name: Vec<&Project> = projects.iter().filter(|p| p.display_name == query).collect();
+    match by_name.as_slice() {
+        [project] => Ok(project),
+        [] => bail!("No project matching '{}'", query),
+        _ => bail!(
+            "'{}' is ambiguous, use the full path: {}",
+            query,
+            by_name
+                .iter()
+                .map(|p| p.decoded_path.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Resolve a session by full ID, slug or unique ID prefix.
+fn find_session<'a>(sessions: impl Iterator<Item = &'a Session>, query: &str) -> Result<&'a Session> {
+    let mut matches: Vec<&Session> = Vec::new();
+    for session in sessions {
+        if session.id == query {
+            return Ok(session);
+        }
+        if session.id.starts_with(query) || session.slug.as_deref() == Some(query) {
+            matches.push(session);
+        }
+    }
+    // A slug can span several session files; prefer the latest
+    matches.sort_by_key(|s| std::cmp::Reverse(s.last_message));
+
+    match matches.as_slice() {
+        [] => bail!("No session matching '{}'", query),
+        [session] => Ok(session),
+        [latest, ..] if matches.iter().all(|s| s.slug.as_deref() == Some(query)) => Ok(latest),
+        _ => bail!("'{}' matches {} sessions, use a longer ID", query, matches.len()),
+    }
+}
+
+/// Resolve an agent by list index, name, ID or ID prefix.
+fn find_agent(manager: &AgentManager, query: &str) -> Result<usize> {
+    let agents = manager.agents();
+    if let Ok(index) = query.parse::<usize>() {
+        if index < agents.len() {
+            return Ok(index);
+        }
+    }
+    if let Some(index) = agents.iter().position(|a| a.name == query || a.id == query) {
+        return Ok(index);
+    }
+
+    let matches: Vec<usize> = agents
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| a.id.starts_with(query))
+        .map(|(i, _)| i)
+        .collect();
+    match matches.as_slice() {
+        [index] => Ok(*index),
+        [] => bail!("No agent matching '{}'", query),
+        _ => bail!("'{}' matches {} agents", query, matches.len()),
+    }
+}
+
+fn list_projects(projects: &[Project], config: &Config, json: bool) -> Result<()> {
+    if json {
+        let projects: Vec<_> = projects
+            .iter()
+            .map(|p| {
+                let tokens = p.usage.tokens();
+                json!({
+                    "name": p.display_name,
+                    "path": p.decoded_path,
+                    "encoded_path": p.encoded_path,
+                    "missing": p.missing,
+                    "session_count": p.session_count,
+                    "total_messages": p.total_messages,
+                    "last_activity": p.last_activity,
+                    "tokens": tokens,
+                    "cost_usd": p.usage.cost(config),
+                })
+            })
+            .collect();
+        return print_json(&projects);
+    }
+
+    let rows = projects
+        .iter()
+        .map(|p| {
+            vec![
+                local_time(p.last_activity),
+                p.session_count.to_string(),
+                p.total_messages.to_string(),
+                format!("${:.2}", p.usage.cost(config)),
+                if p.missing {
+                    format!("{} (missing)", p.decoded_path)
+                } else {
+                    p.decoded_path.clone()
+                },
+            ]
+        })
+        .collect();
+    print_table(&["LAST ACTIVE", "SESSIONS", "MESSAGES", "COST", "PATH"], rows);
+    Ok(())
+}
+
+fn list_sessions(sessions: &[&Session], config: &Config, json: bool) -> Result<()> {
+    if json {
+        let sessions: Vec<_> = sessions
+            .iter()
+            .map(|s| {
+                let totals = s.usage_totals();
+                json!({
+                    "id": s.id,
+                    "name": s.display_name(),
+                    "project": s.project_path,
+                    "first_message": s.first_message,
+                    "last_message": s.last_message,
+                    "message_count": s.message_count,
+                    "duration": s.duration_str(),
+                    "tokens": totals.tokens(),
+                    "cost_usd": totals.cost(config),
+                })
+            })
+            .collect();
+        return print_json(&sessions);
+    }
+
+    let rows = sessions
+        .iter()
+        .map(|s| {
+            vec![
+                s.id.clone(),
+                local_time(s.last_message),
+                s.message_count.to_string(),
+                s.duration_str(),
+                format!("${:.2}", s.usage_totals().cost(config)),
+                s.project_path.clone(),
+                truncate(&one_line(&s.preview_text), 60),
+            ]
+        })
+        .collect();
+    print_table(
+        &["ID", "LAST MESSAGE", "MSGS", "DURATION", "COST", "PROJECT", "PREVIEW"],
+        rows,
+    );
+    Ok(())
+}
+
+fn show_session(session: &Session, config: &Config, json: bool) -> Result<()> {
+    let entries = SessionStore::load_entries(&session.file_path)?;
+
+    if json {
+        let messages: Vec<_> = entries
+            .iter()
+            .filter_map(|entry| {
+                let message = entry.message.as_ref()?;
+                Some(json!({
+                    "timestamp": entry.timestamp,
+                    "role": message.role(),
+                    "text": message_text(message),
+                }))
+            })
+            .collect();
+        return print_json(&json!({ "session": session, "messages": messages }));
+    }
+
+    println!("Session:  {}", session.id);
+    println!("Name:     {}", session.display_name());
+    println!("Project:  {}", session.project_path);
+    if let Some(branch) = &session.git_branch {
+        println!("Branch:   {}", branch);
+    }
+    println!(
+        "Time:     {} → {} ({})",
+        local_time(session.first_message),
+        local_time(session.last_message),
+        session.duration_str()
+    );
+    println!("Messages: {}", session.message_count);
+    let totals = session.usage_totals();
+    if !totals.is_empty() {
+        let tokens = totals.tokens();
+        println!(
+            "Tokens:   {} in, {} out, {} cache read, {} cache write (${:.2})",
+            tokens.input,
+            tokens.output,
+            tokens.cache_read,
+            tokens.cache_creation,
+            totals.cost(config)
+        );
+    }
+
+    for entry in &entries {
+        let Some(message) = &entry.message else {
+            continue;
+        };
+        let text = message_text(message);
+        if text.trim().is_empty() {
+            continue;
+        }
+        println!();
+        println!("── {} ({}) ──", message.role(), local_time(entry.timestamp));
+        println!("{}", text.trim_end());
+    }
+    Ok(())
+}
+
+/// Print tool, Bash command and failing-session tables, or the same as JSON.
+fn print_tool_stats(stats: &ToolStats, limit: usize, json: bool) -> Result<()> {
+    let counter_json = |name: &str, c: &ToolCounter| {
+        json!({
+            "name": name,
+            "calls": c.calls,
+            "errors": c.errors,
+            "error_rate": c.error_rate(),
+            "mean_latency_ms": c.mean_latency_ms(),
+            "max_latency_ms": (c.latency_samples > 0).then_some(c.max_latency_ms),
+        })
+    };
+    let thrashing = stats.thrashing_sessions();
+
+    if json {
+        let totals = stats.totals();
+        return print_json(&json!({
+            "totals": counter_json("all", &totals),
+            "tools": stats.tools_by_calls().iter().map(|(n, c)| counter_json(n, c)).collect::<Vec<_>>(),
+            "bash_commands": stats
+                .top_bash_commands()
+                .iter()
+                .take(limit)
+                .map(|(n, c)| counter_json(n, c))
+                .collect::<Vec<_>>(),
+            "by_day": stats
+                .by_day
+                .iter()
+                .map(|(day, c)| json!({ "date": day, "calls": c.calls, "errors": c.errors }))
+                .collect::<Vec<_>>(),
+            "failing_sessions": thrashing.iter().take(limit).collect::<Vec<_>>(),
+        }));
+    }
+
+    let counter_rows = |rows: Vec<(&str, &ToolCounter)>| -> Vec<Vec<String>> {
+        rows.into_iter()
+            .take(limit)
+            .map(|(name, c)| {
+                vec![
+                    name.to_string(),
+                    c.calls.to_string(),
+                    c.errors.to_string(),
+                    format!("{:.1}%", c.error_rate() * 100.0),
+                    c.mean_latency_ms().map(format_latency).unwrap_or_default(),
+                ]
+            })
+            .collect()
+    };
+    print_table(&["TOOL", "CALLS", "ERRORS", "RATE", "AVG LATENCY"], counter_rows(stats.tools_by_calls()));
+    if !stats.bash_commands.is_empty() {
+        println!();
+        print_table(
+            &["COMMAND", "RUNS", "ERRORS", "RATE", "AVG LATENCY"],
+            counter_rows(stats.top_bash_commands()),
+        );
+    }
+    if stats.sessions.len() > 1 && !thrashing.is_empty() {
+        println!();
+        let rows = thrashing
+            .iter()
+            .take(limit)
+            .map(|s| {
+                vec![
+                    s.session_id.clone(),
+                    s.errors.to_string(),
+                    s.calls.to_string(),
+                    s.project_path.clone(),
+                    truncate(&one_line(&s.name), 50),
+                ]
+            })
+            .collect();
+        print_table(&["SESSION", "ERRORS", "CALLS", "PROJECT", "NAME"], rows);
+    }
+    Ok(())
+}
+
+/// Readable text of a message: prose, plus one-line markers for tool calls and results.
+fn message_text(message: &MessageContent) -> String {
+    match message {
+        MessageContent::Simple { content, .. } => content.clone(),
+        MessageContent::Structured { content, .. } => content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(text.clone()),
+                ContentBlock::ToolUse { name, .. } => Some(format!("[tool: {}]", name)),
+                ContentBlock::ToolResult { content, is_error, .. } => {
+                    let label = if *is_error { "tool error" } else { "tool result" };
+                    let text = one_line(&tool_result_text(content));
+                    Some(format!("[{}: {}]", label, truncate(&text, 200)))
+                }
+                ContentBlock::Thinking { .. } | ContentBlock::Unknown => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Snippet text without highlight markers.
+fn plain_snippet(segments: &[(String, bool)]) -> String {
+    segments.iter().map(|(text, _)| text.as_str()).collect()
+}
+
+/// `text` with newlines and other control characters turned into spaces.
+fn one_line(text: &str) -> String {
+    text.chars().map(|c| if c.is_control() { ' ' } else { c }).collect()
+}
+
+fn print_json(value: &impl serde::Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Print rows as whitespace-aligned columns; the last column is not padded.
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let last = cells.len().saturating_sub(1);
+        cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if i == last {
+                    cell.to_string()
+                } else {
+                    format!("{:<width$}", cell, width = widths[i])
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+
+    println!("{}", format_row(headers.to_vec()));
+    for row in &rows {
+        println!("{}", format_row(row.iter().map(String::as_str).collect()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_session() {
+        let sessions = [
+            Session::test("1a2b3c4d-0000", Some("quiet-river"), "2026-01-01T10:00:00Z"),
+            Session::test("1a2b9999-0000", Some("quiet-river"), "2026-01-02T10:00:00Z"),
+            Session::test("5e6f7a8b-0000", None, "2026-01-03T10:00:00Z"),
+        ];
+        let find = |query: &str| find_session(sessions.iter(), query).map(|s| s.id.as_str());
+
+        assert_eq!(find("5e6f7a8b-0000").unwrap(), "5e6f7a8b-0000");
+        assert_eq!(find("1a2b3").unwrap(), "1a2b3c4d-0000");
+        // A slug shared by resumed sessions picks the latest
+        assert_eq!(find("quiet-river").unwrap(), "1a2b9999-0000");
+        assert!(find("1a2b").unwrap_err().to_string().contains("matches 2 sessions"));
+        assert!(find("ffff").is_err());
+    }
+
+    #[test]
+    fn test_find_project() {
+        let projects = [
+            Project::new("-work-app".to_string(), "/work/app".to_string()),
+            Project::new("-home-me-app".to_string(), "/home/me/app".to_string()),
+            Project::new("-work-api".to_string(), "/work/api".to_string()),
+        ];
+        let find = |query: &str| find_project(&projects, query).map(|p| p.decoded_path.as_str());
+
+        assert_eq!(find("/work/app/").unwrap(), "/work/app");
+        assert_eq!(find("-home-me-app").unwrap(), "/home/me/app");
+        assert_eq!(find("api").unwrap(), "/work/api");
+        assert!(find("app").unwrap_err().to_string().contains("ambiguous"));
+        assert!(find("web").is_err());
+    }
+}
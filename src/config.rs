@@ -2,25 +2,27 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::path::PathBuf;
 
+use crate::models::agent::AgentBackend;
+
 /// Main configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub claude: ClaudeConfig,
     #[serde(default)]
     pub display: DisplayConfig,
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            claude: ClaudeConfig::default(),
-            display: DisplayConfig::default(),
-        }
-    }
+    #[serde(default)]
+    pub launcher: LauncherConfig,
+    #[serde(default)]
+    pub agents: AgentsConfig,
+    /// Per-model prices, keyed by a substring of the model name.
+    /// Entries here override the built-in table.
+    #[serde(default)]
+    pub pricing: BTreeMap<String, ModelPrice>,
 }
 
 /// Claude-related configuration.
@@ -61,6 +63,12 @@ pub struct DisplayConfig {
     /// Show agent sessions (sidechains)
     #[serde(default = "default_show_agents")]
     pub show_agent_sessions: bool,
+    /// Initial order of the project and session lists
+    #[serde(default)]
+    pub sort: SortMode,
+    /// Initial grouping of the project list
+    #[serde(default)]
+    pub group_projects: ProjectGrouping,
 }
 
 impl Default for DisplayConfig {
@@ -69,8 +77,170 @@ impl Default for DisplayConfig {
             preview_lines: default_preview_lines(),
             date_format: default_date_format(),
             show_agent_sessions: default_show_agents(),
+            sort: SortMode::default(),
+            group_projects: ProjectGrouping::default(),
+        }
+    }
+}
+
+/// Order of the browser's project and session lists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortMode {
+    /// Most recent activity first
+    #[default]
+    Recency,
+    /// Alphabetical by name
+    Name,
+    /// Most sessions first (sessions fall back to recency)
+    SessionCount,
+    /// Most messages first
+    MessageCount,
+    /// Largest transcripts first
+    FileSize,
+    /// Most tokens first
+    TokenUsage,
+}
+
+impl SortMode {
+    pub const ALL: [SortMode; 6] = [
+        SortMode::Recency,
+        SortMode::Name,
+        SortMode::SessionCount,
+        SortMode::MessageCount,
+        SortMode::FileSize,
+        SortMode::TokenUsage,
+    ];
+
+    /// Human-readable label.
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Recency => "recency",
+            SortMode::Name => "name",
+            SortMode::SessionCount => "session count",
+            SortMode::MessageCount => "message count",
+            SortMode::FileSize => "file size",
+            SortMode::TokenUsage => "token usage",
+        }
+    }
+
+    /// The mode after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// How to group the browser's project list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectGrouping {
+    /// Flat list
+    #[default]
+    None,
+    /// By parent directory
+    Parent,
+    /// By git (or jj) repository root, so worktrees sit with their repo
+    Repo,
+}
+
+impl ProjectGrouping {
+    pub const ALL: [ProjectGrouping; 3] = [ProjectGrouping::None, ProjectGrouping::Parent, ProjectGrouping::Repo];
+
+    /// Human-readable label.
+    pub fn label(self) -> &'static str {
+        match self {
+            ProjectGrouping::None => "none",
+            ProjectGrouping::Parent => "parent dir",
+            ProjectGrouping::Repo => "repo",
+        }
+    }
+
+    /// The grouping after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|g| *g == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Terminal launcher configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherConfig {
+    /// Terminal to open windows in: "auto", ghostty, wezterm, kitty,
+    /// alacritty, foot, gnome-terminal, konsole or xterm
+    #[serde(default = "default_terminal")]
+    pub terminal: String,
+    /// Window class / app-id for new windows (for window manager rules)
+    #[serde(default)]
+    pub class: Option<String>,
+    /// Where resumed and new sessions run
+    #[serde(default)]
+    pub mode: LaunchMode,
+}
+
+impl Default for LauncherConfig {
+    fn default() -> Self {
+        Self {
+            terminal: default_terminal(),
+            class: None,
+            mode: LaunchMode::default(),
+        }
+    }
+}
+
+/// Spawned agent configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentsConfig {
+    /// How new agents run: headless, or in tmux to attach to
+    #[serde(default)]
+    pub backend: AgentBackend,
+}
+
+/// Where to run claude when launching a session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum LaunchMode {
+    /// New terminal window wrapping a fresh tmux session
+    #[default]
+    NewWindow,
+    /// Replace total-recall in the current terminal
+    Exec,
+    /// New window in the current tmux session
+    TmuxWindow,
+    /// Split the current tmux pane
+    TmuxSplit,
+    /// Detached tmux session, to attach to later
+    TmuxDetached,
+}
+
+impl LaunchMode {
+    pub const ALL: [LaunchMode; 5] = [
+        LaunchMode::NewWindow,
+        LaunchMode::Exec,
+        LaunchMode::TmuxWindow,
+        LaunchMode::TmuxSplit,
+        LaunchMode::TmuxDetached,
+    ];
+
+    /// Human-readable label.
+    pub fn label(self) -> &'static str {
+        match self {
+            LaunchMode::NewWindow => "New terminal window",
+            LaunchMode::Exec => "This terminal",
+            LaunchMode::TmuxWindow => "New tmux window",
+            LaunchMode::TmuxSplit => "tmux split pane",
+            LaunchMode::TmuxDetached => "Detached tmux session",
         }
     }
+
+    /// Whether this mode needs to run inside an existing tmux client.
+    pub fn needs_tmux_client(self) -> bool {
+        matches!(self, LaunchMode::TmuxWindow | LaunchMode::TmuxSplit)
+    }
+}
+
+fn default_terminal() -> String {
+    "auto".to_string()
 }
 
 fn default_preview_lines() -> usize {
@@ -85,7 +255,48 @@ fn default_show_agents() -> bool {
     true
 }
 
+/// Price of a model in dollars per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_read: f64,
+    #[serde(default)]
+    pub cache_write: f64,
+}
+
+/// Built-in list prices (USD per million tokens), matched by model-name substring.
+const DEFAULT_PRICES: &[(&str, ModelPrice)] = &[
+    ("opus-4-5", ModelPrice { input: 5.0, output: 25.0, cache_read: 0.5, cache_write: 6.25 }),
+    ("opus", ModelPrice { input: 15.0, output: 75.0, cache_read: 1.5, cache_write: 18.75 }),
+    ("sonnet", ModelPrice { input: 3.0, output: 15.0, cache_read: 0.3, cache_write: 3.75 }),
+    ("haiku-4-5", ModelPrice { input: 1.0, output: 5.0, cache_read: 0.1, cache_write: 1.25 }),
+    ("haiku", ModelPrice { input: 0.8, output: 4.0, cache_read: 0.08, cache_write: 1.0 }),
+];
+
 impl Config {
+    /// Look up the price for a model name.
+    ///
+    /// The longest matching key wins, so `opus-4-5` beats `opus`. Configured
+    /// entries are checked before the built-in table.
+    pub fn price_for(&self, model: &str) -> Option<ModelPrice> {
+        let configured = self
+            .pricing
+            .iter()
+            .filter(|(key, _)| model.contains(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, price)| *price);
+
+        configured.or_else(|| {
+            DEFAULT_PRICES
+                .iter()
+                .filter(|(key, _)| model.contains(key))
+                .max_by_key(|(key, _)| key.len())
+                .map(|(_, price)| *price)
+        })
+    }
+
     /// Load configuration from default location.
     pub fn load() -> Result<Self> {
         let config_path = Self::default_config_path();
@@ -128,7 +339,7 @@ impl Config {
 }
 
 /// Expand ~ to home directory.
-fn expand_path(path: &str) -> String {
+pub fn expand_path(path: &str) -> String {
     if path.starts_with("~/") {
         if let Some(home) = dirs::home_dir() {
             return home.join(&path[2..]).to_string_lossy().to_string();
@@ -1,31 +1,49 @@
 //! Channel-based event system.
 //!
 //! Replaces the old `event::poll` loop with an mpsc channel that aggregates
-//! keyboard input, agent status updates, and periodic ticks into a single stream.
+//! keyboard input, agent status updates, session file changes, and periodic
+//! ticks into a single stream.
 
 use crossterm::event::{self, Event, KeyEvent, KeyEventKind};
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
+use crate::models::AgentDiff;
+
 /// Events that the application reacts to.
 #[derive(Debug)]
 pub enum AppEvent {
     /// Keyboard/mouse input from crossterm.
     Input(KeyEvent),
     /// An agent's status or output changed.
-    AgentUpdate { agent_id: String },
+    AgentUpdate { agent_id: String, diff: AgentDiff },
     /// An agent's process exited.
-    AgentExited { agent_id: String },
+    AgentExited { agent_id: String, diff: AgentDiff },
+    /// A session JSONL under the Claude projects directory changed on disk.
+    SessionChanged { path: PathBuf, change: SessionChange },
     /// Periodic tick for animations, clocks, activity checks.
     Tick,
 }
 
+/// How a session file changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionChange {
+    /// A new session file appeared.
+    Created,
+    /// New messages were written to an existing session.
+    Appended,
+    /// The session file was removed (or moved away).
+    Deleted,
+}
+
 /// Spawns background tasks that feed events into the returned receiver.
 ///
 /// - A crossterm input reader on a blocking thread
 /// - A tick timer (500ms interval)
 ///
-/// Agent monitoring tasks push events through the sender clone they receive.
+/// Agent monitoring tasks and the session watcher push events through the
+/// sender clone they receive.
 pub fn spawn_event_tasks() -> (mpsc::UnboundedSender<AppEvent>, mpsc::UnboundedReceiver<AppEvent>) {
     let (tx, rx) = mpsc::unbounded_channel();
 
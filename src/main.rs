@@ -6,6 +6,7 @@
 //! conversations across all your projects.
 
 mod app;
+mod cli;
 mod config;
 mod event;
 mod models;
@@ -15,6 +16,7 @@ mod utils;
 
 use anyhow::Result;
 use clap::Parser;
+use config::LaunchMode;
 use std::os::unix::process::CommandExt;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -50,28 +52,67 @@ struct Args {
     /// Claude directory path (default: ~/.claude)
     #[arg(long)]
     claude_dir: Option<String>,
+
+    /// Print machine-readable JSON instead of tables (subcommands only)
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Run a command instead of the TUI
+    #[command(subcommand)]
+    command: Option<cli::Command>,
 }
 
-/// Build a ghostty command for spawning a new terminal window.
-///
-/// Uses plain `ghostty` (new process) rather than `+new-window` (D-Bus IPC).
-/// In ghostty 1.3.0-dev, `+new-window -e` silently ignores the command —
-/// the window opens with the default shell instead. Plain `ghostty` supports
-/// all flags: -e, --working-directory, --class, --font-size, etc.
-fn ghostty_command() -> std::process::Command {
-    std::process::Command::new("ghostty")
+/// Append tmux status bar configuration args to a tmux argv.
+fn append_tmux_status_args(argv: &mut Vec<String>, status_text: &str) {
+    let settings = [
+        ("status", "on".to_string()),
+        ("status-position", "top".to_string()),
+        ("status-style", "bg=blue,fg=white,bold".to_string()),
+        ("status-left-length", "100".to_string()),
+        ("status-left", format!(" {} ", status_text)),
+        ("status-right", String::new()),
+        ("window-status-format", String::new()),
+        ("window-status-current-format", String::new()),
+    ];
+    for (option, value) in settings {
+        argv.extend([";".to_string(), "set".to_string(), option.to_string(), value]);
+    }
+}
+
+/// Build `tmux new-session -A` args, attaching if the session already exists.
+fn tmux_new_session_args(session_name: &str, project_path: &str, command: Option<String>) -> Vec<String> {
+    let mut argv: Vec<String> = ["tmux", "new-session", "-A", "-s", session_name, "-c", project_path]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    argv.extend(command);
+    argv
 }
 
-/// Append tmux status bar configuration args to a command.
-fn append_tmux_status_args(cmd: &mut std::process::Command, status_text: &str) {
-    cmd.arg(";").arg("set").arg("status").arg("on")
-        .arg(";").arg("set").arg("status-position").arg("top")
-        .arg(";").arg("set").arg("status-style").arg("bg=blue,fg=white,bold")
-        .arg(";").arg("set").arg("status-left-length").arg("100")
-        .arg(";").arg("set").arg("status-left").arg(format!(" {} ", status_text))
-        .arg(";").arg("set").arg("status-right").arg("")
-        .arg(";").arg("set").arg("window-status-format").arg("")
-        .arg(";").arg("set").arg("window-status-current-format").arg("");
+/// Shell command opening `file` in `editor`, at `line` for editors known to take one.
+fn editor_command(editor: &str, file: &str, line: Option<usize>) -> String {
+    let program = editor.split_whitespace().next().unwrap_or(editor);
+    let name = std::path::Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program);
+    let file = utils::shell_quote(file);
+    match (name, line) {
+        ("vi" | "vim" | "nvim" | "nano" | "emacs" | "kak" | "micro", Some(line)) => format!("{editor} +{line} {file}"),
+        ("hx" | "helix" | "subl" | "zed", Some(line)) => format!("{editor} {file}:{line}"),
+        ("code" | "codium", Some(line)) => format!("{editor} -g {file}:{line}"),
+        _ => format!("{editor} {file}"),
+    }
+}
+
+/// Open a new terminal window running `argv` in the project directory.
+fn open_terminal_window(config: &config::Config, argv: Vec<String>, project_path: &str) -> std::io::Result<()> {
+    let launcher = services::terminal_launcher::from_config(&config.launcher);
+    let window = services::TerminalCommand::new(argv)
+        .working_dir(project_path)
+        .class(config.launcher.class.as_deref());
+    tracing::debug!("Launching {}: {:?}", launcher.name(), launcher.argv(&window));
+    spawn_detached(&mut launcher.command(&window)).map(|_| ())
 }
 
 /// Build a tmux session name from project path and session ID.
@@ -145,106 +186,118 @@ fn get_git_remote_url(project_path: &str) -> Option<String> {
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+/// Run claude in the current terminal, replacing this process.
+///
+/// Changes into the project directory first; only returns by exiting on failure.
+fn exec_claude(project_path: &str, session_id: Option<&str>, skip_perms: bool) -> ! {
+    if let Err(e) = std::env::set_current_dir(project_path) {
+        eprintln!("Failed to change to project directory '{}': {}", project_path, e);
+        std::process::exit(1);
+    }
+    let mut cmd = std::process::Command::new("claude");
+    if skip_perms {
+        cmd.arg("--dangerously-skip-permissions");
+    }
+    if let Some(id) = session_id {
+        cmd.arg("--resume").arg(id);
+    }
+    let err = cmd.exec();
+    eprintln!("Failed to launch claude: {}", err);
+    std::process::exit(1);
+}
 
-    // Set up logging
-    let filter = if args.debug {
-        "total_recall=debug,info"
+/// Run a tmux command against the current server, returning stderr on failure.
+fn run_tmux(args: &[&str]) -> Result<(), String> {
+    let output = std::process::Command::new("tmux")
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
     } else {
-        "total_recall=info,warn"
-    };
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
 
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| filter.into()))
-        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
-        .init();
+/// Start claude in a project, resuming `session_id` if given, wherever `mode` says.
+fn launch_claude(config: &config::Config, project_path: &str, session_id: Option<&str>, mode: LaunchMode) {
+    if !std::path::Path::new(project_path).is_dir() {
+        eprintln!("Project directory '{}' no longer exists.", project_path);
+        eprintln!("If it moved, relink it with `total-recall relink <project> <new-path>` or `L` in the browser.");
+        return;
+    }
 
-    // Load configuration
-    let mut config = if let Some(path) = args.config {
-        config::Config::from_file(&path)?
+    let skip_perms = config.claude.dangerously_skip_permissions;
+    let mut claude_cmd = "claude".to_string();
+    if skip_perms {
+        claude_cmd.push_str(" --dangerously-skip-permissions");
+    }
+    if let Some(id) = session_id {
+        claude_cmd.push_str(&format!(" --resume {id}"));
+    }
+    // Keep the pane open after claude exits so errors stay readable
+    let shell_cmd = format!("{claude_cmd}; echo; echo Press Enter to close...; read");
+
+    let tmux_session = build_tmux_session_name(project_path, session_id);
+    let status_text = build_tmux_status(project_path, session_id);
+
+    let mode = if mode.needs_tmux_client() && std::env::var_os("TMUX").is_none() {
+        eprintln!("Not running inside tmux, opening a new terminal window instead");
+        LaunchMode::NewWindow
     } else {
-        config::Config::load()?
+        mode
     };
 
-    // Override claude_dir if specified
-    if let Some(claude_dir) = args.claude_dir {
-        config.claude.claude_dir = claude_dir;
-    }
+    let result = match mode {
+        LaunchMode::Exec => exec_claude(project_path, session_id, skip_perms),
+        LaunchMode::NewWindow => {
+            let mut argv = tmux_new_session_args(&tmux_session, project_path, Some(shell_cmd));
+            append_tmux_status_args(&mut argv, &status_text);
+            open_terminal_window(config, argv, project_path).map_err(|e| e.to_string())
+        }
+        LaunchMode::TmuxWindow => {
+            run_tmux(&["new-window", "-n", &tmux_session, "-c", project_path, &shell_cmd])
+        }
+        LaunchMode::TmuxSplit => run_tmux(&["split-window", "-h", "-c", project_path, &shell_cmd]),
+        LaunchMode::TmuxDetached => {
+            if run_tmux(&["has-session", "-t", &format!("={tmux_session}")]).is_ok() {
+                println!("tmux session {tmux_session} is already running");
+                println!("Attach with: tmux attach -t {tmux_session}");
+                return;
+            }
+            let mut argv = tmux_new_session_args(&tmux_session, project_path, Some(shell_cmd));
+            argv.insert(2, "-d".to_string());
+            append_tmux_status_args(&mut argv, &status_text);
+            let args: Vec<&str> = argv[1..].iter().map(String::as_str).collect();
+            run_tmux(&args).map(|()| {
+                println!("Started tmux session {tmux_session}");
+                println!("Attach with: tmux attach -t {tmux_session}");
+            })
+        }
+    };
 
-    let skip_perms = config.claude.dangerously_skip_permissions;
-    let skip_perms_flag = if skip_perms { " --dangerously-skip-permissions" } else { "" };
+    if let Err(e) = result {
+        eprintln!("Failed to launch ({}): {}", mode.label(), e);
+        eprintln!("Falling back to running in current terminal...");
+        exec_claude(project_path, session_id, skip_perms);
+    }
+}
 
-    // Run the TUI application
-    let mut app = app::App::new(config).await?;
-    match app.run().await? {
+/// Carry out whatever the TUI (or a CLI command) asked for on exit.
+fn launch(result: app::AppResult, config: &config::Config) {
+    match result {
         app::AppResult::Exit => {}
         app::AppResult::NewSession { project_path } => {
-            let tmux_session = build_tmux_session_name(&project_path, None);
-            let status_text = build_tmux_status(&project_path, None);
-
-            let mut cmd = ghostty_command();
-            cmd.arg("-e")
-                .arg("tmux").arg("new-session").arg("-A")
-                .arg("-s").arg(&tmux_session)
-                .arg("-c").arg(&project_path)
-                .arg(format!("claude{skip_perms_flag}; echo; echo Press Enter to close...; read"));
-            append_tmux_status_args(&mut cmd, &status_text);
-
-            if let Err(e) = spawn_detached(&mut cmd) {
-                eprintln!("Failed to launch terminal: {}", e);
-                eprintln!("Falling back to running in current terminal...");
-
-                if let Err(e) = std::env::set_current_dir(&project_path) {
-                    eprintln!("Failed to change to project directory '{}': {}", project_path, e);
-                    std::process::exit(1);
-                }
-                let mut fallback_cmd = std::process::Command::new("claude");
-                if skip_perms { fallback_cmd.arg("--dangerously-skip-permissions"); }
-                let err = fallback_cmd.exec();
-                eprintln!("Failed to launch claude: {}", err);
-                std::process::exit(1);
-            }
+            launch_claude(config, &project_path, None, config.launcher.mode);
         }
-        app::AppResult::LaunchSession { session_id, project_path } => {
-            let tmux_session = build_tmux_session_name(&project_path, Some(&session_id));
-            let status_text = build_tmux_status(&project_path, Some(&session_id));
-
-            let resume_cmd = format!("claude{skip_perms_flag} --resume {session_id}; echo; echo Press Enter to close...; read");
-            let mut cmd = ghostty_command();
-            cmd.arg("-e")
-                .arg("tmux").arg("new-session").arg("-A")
-                .arg("-s").arg(&tmux_session)
-                .arg("-c").arg(&project_path)
-                .arg(&resume_cmd);
-            append_tmux_status_args(&mut cmd, &status_text);
-
-            if let Err(e) = spawn_detached(&mut cmd) {
-                eprintln!("Failed to launch terminal: {}", e);
-                eprintln!("Falling back to running in current terminal...");
-
-                if let Err(e) = std::env::set_current_dir(&project_path) {
-                    eprintln!("Failed to change to project directory '{}': {}", project_path, e);
-                    std::process::exit(1);
-                }
-                let mut fallback_cmd = std::process::Command::new("claude");
-                if skip_perms { fallback_cmd.arg("--dangerously-skip-permissions"); }
-                let err = fallback_cmd.arg("--resume").arg(&session_id).exec();
-                eprintln!("Failed to launch claude: {}", err);
-                std::process::exit(1);
-            }
+        app::AppResult::LaunchSession { session_id, project_path, mode } => {
+            launch_claude(config, &project_path, Some(&session_id), mode);
         }
         app::AppResult::OpenLazygit { project_path } => {
             let tmux_session = build_tmux_session_name(&project_path, None);
-            let mut cmd = ghostty_command();
-            cmd.arg("-e")
-                .arg("tmux").arg("new-session").arg("-A")
-                .arg("-s").arg(&tmux_session)
-                .arg("-c").arg(&project_path)
-                .arg("lazygit");
-
-            if let Err(e) = spawn_detached(&mut cmd) {
+            let argv = tmux_new_session_args(&tmux_session, &project_path, Some("lazygit".to_string()));
+
+            if let Err(e) = open_terminal_window(config, argv, &project_path) {
                 eprintln!("Failed to launch lazygit: {}", e);
             }
         }
@@ -264,31 +317,63 @@ async fn main() -> Result<()> {
         }
         app::AppResult::OpenTerminal { project_path } => {
             let tmux_session = build_tmux_session_name(&project_path, None);
-            let mut cmd = ghostty_command();
-            cmd.arg("-e")
-                .arg("tmux").arg("new-session").arg("-A")
-                .arg("-s").arg(&tmux_session)
-                .arg("-c").arg(&project_path);
+            let argv = tmux_new_session_args(&tmux_session, &project_path, None);
 
-            if let Err(e) = spawn_detached(&mut cmd) {
+            if let Err(e) = open_terminal_window(config, argv, &project_path) {
                 eprintln!("Failed to open terminal: {}", e);
             }
         }
-        app::AppResult::OpenEditor { project_path } => {
-            let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
+        app::AppResult::OpenEditor { project_path, file, line } => {
+            let mut editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
+            if let Some(file) = file {
+                editor = editor_command(&editor, &file, line);
+            }
             let tmux_session = build_tmux_session_name(&project_path, None);
-            let mut cmd = ghostty_command();
-            cmd.arg("-e")
-                .arg("tmux").arg("new-session").arg("-A")
-                .arg("-s").arg(&tmux_session)
-                .arg("-c").arg(&project_path)
-                .arg(&editor);
-
-            if let Err(e) = spawn_detached(&mut cmd) {
+            let argv = tmux_new_session_args(&tmux_session, &project_path, Some(editor));
+
+            if let Err(e) = open_terminal_window(config, argv, &project_path) {
                 eprintln!("Failed to open editor: {}", e);
             }
         }
     }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    // Set up logging
+    let filter = if args.debug {
+        "total_recall=debug,info"
+    } else {
+        "total_recall=info,warn"
+    };
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| filter.into()))
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .init();
+
+    // Load configuration
+    let mut config = if let Some(path) = args.config {
+        config::Config::from_file(&path)?
+    } else {
+        config::Config::load()?
+    };
+
+    // Override claude_dir if specified
+    if let Some(claude_dir) = args.claude_dir {
+        config.claude.claude_dir = claude_dir;
+    }
+
+    if let Some(command) = args.command {
+        return cli::run(command, config, args.json).await;
+    }
+
+    // Run the TUI application
+    let mut app = app::App::new(config.clone()).await?;
+    let result = app.run().await?;
+    launch(result, &config);
 
     Ok(())
 }
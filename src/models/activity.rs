@@ -0,0 +1,95 @@
+//! Stretches of activity within a session, for the history screen.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest pause between messages that still counts as one stretch of work.
+pub const IDLE_GAP_MINUTES: i64 = 15;
+
+/// A run of messages with no pause longer than [`IDLE_GAP_MINUTES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivitySpan {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub messages: usize,
+}
+
+impl ActivitySpan {
+    /// Time this span overlaps `[from, to)`.
+    pub fn overlap(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Duration {
+        (self.end.min(to) - self.start.max(from)).max(Duration::zero())
+    }
+
+    /// Whether any of the span falls within `[from, to)`.
+    pub fn intersects(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
+        self.start < to && self.end >= from
+    }
+
+    /// Local day the span started on.
+    pub fn local_date(&self) -> NaiveDate {
+        self.start.with_timezone(&Local).date_naive()
+    }
+
+    /// Add a message timestamp to a session's spans, extending the last one
+    /// or starting a new span after an idle gap.
+    pub fn record(spans: &mut Vec<ActivitySpan>, timestamp: DateTime<Utc>) {
+        let gap = Duration::minutes(IDLE_GAP_MINUTES);
+        match spans.last_mut() {
+            Some(last) if timestamp <= last.end + gap && timestamp >= last.start - gap => {
+                last.start = last.start.min(timestamp);
+                last.end = last.end.max(timestamp);
+                last.messages += 1;
+            }
+            _ => spans.push(ActivitySpan {
+                start: timestamp,
+                end: timestamp,
+                messages: 1,
+            }),
+        }
+    }
+}
+
+/// Start and end of a local day, in UTC.
+pub fn local_day_bounds(date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start_of = |date: NaiveDate| {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+        Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+    };
+    (start_of(date), start_of(date + Duration::days(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        time.parse().unwrap()
+    }
+
+    #[test]
+    fn test_spans_split_on_idle_gaps() {
+        let mut spans = Vec::new();
+        for time in [
+            "2026-01-05T09:00:00Z",
+            "2026-01-05T09:10:00Z",
+            "2026-01-05T09:20:00Z",
+            "2026-01-05T11:00:00Z",
+            "2026-01-05T11:05:00Z",
+        ] {
+            ActivitySpan::record(&mut spans, at(time));
+        }
+
+        assert_eq!(spans.len(), 2);
+        assert_eq!((spans[0].messages, spans[0].end - spans[0].start), (3, Duration::minutes(20)));
+        assert_eq!(spans[1].messages, 2);
+        assert_eq!(
+            spans[0].overlap(at("2026-01-05T09:15:00Z"), at("2026-01-05T12:00:00Z")),
+            Duration::minutes(5)
+        );
+        assert!(!spans[1].intersects(at("2026-01-05T12:00:00Z"), at("2026-01-06T00:00:00Z")));
+    }
+}
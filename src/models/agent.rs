@@ -4,16 +4,21 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::usage::TokenCounts;
+
 /// Status of an agent's lifecycle.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AgentStatus {
-    /// tmux created, claude booting up
+    /// Spawned, claude booting up
     Starting,
     /// JSONL growing (messages in last 30s)
     Active,
     /// No JSONL activity for >30s
     Idle,
-    /// Process exited cleanly
+    /// Last turn ended and nothing has happened since, but claude is still
+    /// open in tmux
+    Finished,
+    /// Claude exited cleanly (exit code 0)
     Complete,
     /// Process died unexpectedly
     Failed,
@@ -28,6 +33,7 @@ impl AgentStatus {
             Self::Starting => "~",
             Self::Active => "●",
             Self::Idle => "○",
+            Self::Finished => "◇",
             Self::Complete => "◆",
             Self::Failed => "✗",
             Self::Killed => "✗",
@@ -40,18 +46,49 @@ impl AgentStatus {
             Self::Starting => "STARTING",
             Self::Active => "ACTIVE",
             Self::Idle => "IDLE",
+            Self::Finished => "FINISHED",
             Self::Complete => "DONE",
             Self::Failed => "FAILED",
             Self::Killed => "KILLED",
         }
     }
 
-    /// Whether the agent is still running (tmux session alive).
+    /// Whether the agent is still running.
     pub fn is_alive(&self) -> bool {
-        matches!(self, Self::Starting | Self::Active | Self::Idle)
+        matches!(self, Self::Starting | Self::Active | Self::Idle | Self::Finished)
+    }
+
+    /// Whether the agent has done its work, whether or not claude is still open.
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Finished | Self::Complete | Self::Failed | Self::Killed)
     }
 }
 
+/// How an agent's claude process is run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentBackend {
+    /// `claude -p` as a child process, its stream-json output written to a log
+    #[default]
+    Headless,
+    /// Interactive claude in a detached tmux session, to attach to
+    Tmux,
+}
+
+impl AgentBackend {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Headless => "headless",
+            Self::Tmux => "tmux",
+        }
+    }
+}
+
+/// Agents persisted before there was a choice of backend ran in tmux.
+fn legacy_backend() -> AgentBackend {
+    AgentBackend::Tmux
+}
+
 /// A spawned Claude Code agent tracked by total-recall.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Agent {
@@ -65,8 +102,17 @@ pub struct Agent {
     pub project_path: PathBuf,
     /// Optional jj worktree path (e.g., /tmp/tr-riri-blur-fix/).
     pub worktree_path: Option<PathBuf>,
-    /// tmux session name.
+    /// How claude is run.
+    #[serde(default = "legacy_backend")]
+    pub backend: AgentBackend,
+    /// tmux session name (empty for headless agents).
     pub tmux_session: String,
+    /// Process ID of a headless agent.
+    #[serde(default)]
+    pub pid: Option<u32>,
+    /// Stream-json log of a headless agent.
+    #[serde(default)]
+    pub log_path: Option<PathBuf>,
     /// Claude session ID discovered from JSONL after spawn.
     pub claude_session_id: Option<String>,
     /// Current lifecycle status.
@@ -81,11 +127,71 @@ pub struct Agent {
     pub message_count: usize,
     /// Last tool the agent used (from JSONL tool_use entries).
     pub last_tool: Option<String>,
-    /// Last N lines captured from the tmux pane.
+    /// Last N lines captured from the tmux pane, or rendered from the log.
     pub last_output_lines: Vec<String>,
+    /// What the agent produced, once it has finished.
+    #[serde(default)]
+    pub result: Option<AgentResult>,
+}
+
+/// Outcome of a finished agent, read from its session JSONL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentResult {
+    /// Text of the last assistant message.
+    pub final_message: Option<String>,
+    /// Files the agent edited, wrote or changed from the shell.
+    pub files_changed: Vec<String>,
+    pub tokens: TokenCounts,
+    /// Exit code of `claude`, when it exited rather than sat idle.
+    pub exit_code: Option<i32>,
+}
+
+/// What changed about an agent since it was last polled; `None` fields are unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentDiff {
+    pub status: Option<AgentStatus>,
+    pub last_activity: Option<DateTime<Utc>>,
+    pub last_output_lines: Option<Vec<String>>,
+    pub claude_session_id: Option<String>,
+    pub message_count: Option<usize>,
+    pub last_tool: Option<String>,
+    pub result: Option<AgentResult>,
+}
+
+impl AgentDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl Agent {
+    /// Apply a diff from the monitor. Returns whether the status changed.
+    pub fn apply(&mut self, diff: &AgentDiff) -> bool {
+        let status_changed = diff.status.as_ref().is_some_and(|s| *s != self.status);
+        if let Some(status) = &diff.status {
+            self.status = status.clone();
+        }
+        if let Some(at) = diff.last_activity {
+            self.last_activity = at;
+        }
+        if let Some(lines) = &diff.last_output_lines {
+            self.last_output_lines = lines.clone();
+        }
+        if let Some(id) = &diff.claude_session_id {
+            self.claude_session_id = Some(id.clone());
+        }
+        if let Some(count) = diff.message_count {
+            self.message_count = count;
+        }
+        if let Some(tool) = &diff.last_tool {
+            self.last_tool = Some(tool.clone());
+        }
+        if let Some(result) = &diff.result {
+            self.result = Some(result.clone());
+        }
+        status_changed
+    }
+
     /// Time since last activity as a human-readable string.
     pub fn time_since_activity(&self) -> String {
         let now = Utc::now();
@@ -107,6 +213,9 @@ impl Agent {
         if !self.status.is_alive() {
             return if self.status == AgentStatus::Complete { 10 } else { 0 };
         }
+        if self.status == AgentStatus::Finished {
+            return 10;
+        }
         let elapsed_mins = (Utc::now() - self.spawned_at).num_seconds() as f64 / 60.0;
         if elapsed_mins < 0.1 {
             return 1; // just started
@@ -116,3 +225,61 @@ impl Agent {
         (rate * 2.0).min(10.0) as u8
     }
 }
+
+#[cfg(test)]
+impl Agent {
+    /// A headless agent in `/work/app` with the given status.
+    pub fn test(id: &str, status: AgentStatus) -> Self {
+        let now = Utc::now();
+        Self {
+            id: id.to_string(),
+            name: format!("dev-{}", id),
+            agent_type: "dev".to_string(),
+            project_path: PathBuf::from("/work/app"),
+            worktree_path: None,
+            backend: AgentBackend::Headless,
+            tmux_session: String::new(),
+            pid: None,
+            log_path: None,
+            claude_session_id: None,
+            status,
+            task_prompt: "Add a --json flag".to_string(),
+            spawned_at: now,
+            last_activity: now,
+            message_count: 0,
+            last_tool: None,
+            last_output_lines: Vec::new(),
+            result: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_reports_status_change() {
+        let mut agent = Agent::test("a", AgentStatus::Active);
+
+        let progress = AgentDiff {
+            status: Some(AgentStatus::Active),
+            message_count: Some(4),
+            last_tool: Some("Edit".to_string()),
+            ..Default::default()
+        };
+        assert!(!agent.apply(&progress));
+        assert_eq!(agent.message_count, 4);
+        assert_eq!(agent.last_tool.as_deref(), Some("Edit"));
+
+        let done = AgentDiff {
+            status: Some(AgentStatus::Complete),
+            result: Some(AgentResult::default()),
+            ..Default::default()
+        };
+        assert!(agent.apply(&done));
+        assert_eq!(agent.status, AgentStatus::Complete);
+        assert_eq!(agent.result, Some(AgentResult::default()));
+        assert_eq!(agent.message_count, 4);
+    }
+}
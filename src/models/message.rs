@@ -3,6 +3,8 @@
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
+use super::usage::TokenCounts;
+
 /// A single entry in a session JSONL file.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -30,6 +32,22 @@ pub struct MessageEntry {
     pub agent_id: Option<String>,
 }
 
+#[cfg(test)]
+impl MessageEntry {
+    /// A `user` or `assistant` entry in `/work/app` with the given content blocks.
+    pub fn test(entry_type: &str, timestamp: &str, content: serde_json::Value) -> Self {
+        serde_json::from_value(serde_json::json!({
+            "uuid": "u",
+            "sessionId": "s",
+            "timestamp": timestamp,
+            "cwd": "/work/app",
+            "type": entry_type,
+            "message": { "role": entry_type, "content": content },
+        }))
+        .unwrap()
+    }
+}
+
 /// Message content - can be simple text or structured with tool calls.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
@@ -41,6 +59,9 @@ pub enum MessageContent {
     Structured {
         role: String,
         content: Vec<ContentBlock>,
+        /// API message ID; repeated on every entry split from the same response
+        #[serde(default)]
+        id: Option<String>,
         #[serde(default)]
         model: Option<String>,
         #[serde(default)]
@@ -79,6 +100,22 @@ impl MessageContent {
             MessageContent::Structured { model, .. } => model.as_deref(),
         }
     }
+
+    /// Get the API message ID (for assistant messages).
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            MessageContent::Simple { .. } => None,
+            MessageContent::Structured { id, .. } => id.as_deref(),
+        }
+    }
+
+    /// Get the token usage reported for this response.
+    pub fn usage(&self) -> Option<&TokenUsage> {
+        match self {
+            MessageContent::Simple { .. } => None,
+            MessageContent::Structured { usage, .. } => usage.as_ref(),
+        }
+    }
 }
 
 /// Content block within a structured message.
@@ -106,9 +143,32 @@ pub enum ContentBlock {
     Unknown,
 }
 
-/// Token usage information.
+/// Plain-text rendering of a `tool_result` block's content.
+///
+/// Content is either a string or an array of typed parts (text, image, ...).
+pub fn tool_result_text(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts
+            .iter()
+            .map(|part| match part.get("type").and_then(|t| t.as_str()) {
+                Some("text") => part
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                Some(other) => format!("[{}]", other),
+                None => part.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Token usage information (snake_case, as returned by the API).
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
 pub struct TokenUsage {
     #[serde(default)]
     pub input_tokens: u64,
@@ -120,6 +180,17 @@ pub struct TokenUsage {
     pub cache_creation_input_tokens: u64,
 }
 
+impl From<&TokenUsage> for TokenCounts {
+    fn from(usage: &TokenUsage) -> Self {
+        Self {
+            input: usage.input_tokens,
+            output: usage.output_tokens,
+            cache_read: usage.cache_read_input_tokens,
+            cache_creation: usage.cache_creation_input_tokens,
+        }
+    }
+}
+
 /// Assistant message content.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
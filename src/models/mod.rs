@@ -1,13 +1,18 @@
 //! Data models for Claude sessions and agents.
 
+pub mod activity;
 pub mod agent;
 pub mod agent_registry;
 pub mod message;
 pub mod project;
 pub mod session;
+pub mod stream_event;
+pub mod usage;
 
-pub use agent::{Agent, AgentStatus};
+pub use activity::ActivitySpan;
+pub use agent::{Agent, AgentDiff, AgentStatus};
 pub use agent_registry::AgentRegistry;
 pub use message::{AssistantContent, ContentBlock, MessageContent, MessageEntry};
 pub use project::Project;
-pub use session::Session;
+pub use session::{Session, SessionMeta};
+pub use usage::{TokenCounts, UsageRecord, UsageTotals};
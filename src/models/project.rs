@@ -1,6 +1,9 @@
 //! Project grouping for sessions.
 
 use chrono::{DateTime, Utc};
+use std::path::Path;
+
+use super::usage::UsageTotals;
 
 /// A project that contains Claude Code sessions.
 #[derive(Debug, Clone)]
@@ -15,14 +18,20 @@ pub struct Project {
     pub session_count: usize,
     /// Total messages across all sessions
     pub total_messages: usize,
+    /// Combined size of the session files in bytes
+    pub total_size: u64,
     /// Most recent activity timestamp
     pub last_activity: DateTime<Utc>,
+    /// Token usage across all sessions, by model
+    pub usage: UsageTotals,
+    /// Whether the project directory no longer exists on disk
+    pub missing: bool,
 }
 
 impl Project {
-    /// Create a new project from an encoded path.
-    pub fn new(encoded_path: String) -> Self {
-        let decoded_path = decode_project_path(&encoded_path);
+    /// Create a new project from its encoded directory name and resolved path.
+    pub fn new(encoded_path: String, decoded_path: String) -> Self {
+        let missing = !Path::new(&decoded_path).is_dir();
         let display_name = decoded_path
             .rsplit('/')
             .next()
@@ -35,16 +44,24 @@ impl Project {
             display_name,
             session_count: 0,
             total_messages: 0,
+            total_size: 0,
             last_activity: DateTime::<Utc>::MIN_UTC,
+            usage: UsageTotals::default(),
+            missing,
         }
     }
 }
 
+/// Most hyphens `decode_project_path` will search over (2^n candidate paths).
+const MAX_DECODE_HYPHENS: usize = 12;
+
 /// Decode a Claude project path from directory name format.
 /// e.g., "-home-garrett-Projects-jwst-cosmos" -> "/home/garrett/Projects/jwst-cosmos"
 ///
 /// The encoding is lossy (hyphens become indistinguishable from path separators),
-/// so we try different decodings and return the one that exists on disk.
+/// so we try different decodings and return the one that exists on disk. This is
+/// only a fallback for when no session recorded its `cwd`; names with more than
+/// `MAX_DECODE_HYPHENS` hyphens skip the search.
 pub fn decode_project_path(encoded: &str) -> String {
     if encoded.is_empty() {
         return String::new();
@@ -64,6 +81,9 @@ pub fn decode_project_path(encoded: &str) -> String {
     // Start with the most slashes (all hyphens become slashes) and work down
     // Return the first path that exists
     let num_hyphens = hyphen_positions.len();
+    if num_hyphens > MAX_DECODE_HYPHENS {
+        return encoded.replace('-', "/");
+    }
 
     // Try from all-slashes down to no-slashes-except-leading
     for num_to_convert in (0..=num_hyphens).rev() {
@@ -123,8 +143,13 @@ fn combinations<T: Clone>(items: &[T], k: usize) -> Vec<Vec<T>> {
 
 /// Encode a project path to Claude directory name format.
 /// e.g., "/home/garrett/Projects/jwst-cosmos" -> "-home-garrett-Projects-jwst-cosmos"
+///
+/// Like Claude Code, every character other than ASCII letters and digits
+/// becomes a hyphen.
 pub fn encode_project_path(path: &str) -> String {
-    path.replace('/', "-")
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
 }
 
 #[cfg(test)]
@@ -154,6 +179,13 @@ mod tests {
             encode_project_path("/home/garrett/Projects/jwst-cosmos"),
             "-home-garrett-Projects-jwst-cosmos"
         );
+        assert_eq!(encode_project_path("/home/me/.config/my_app"), "-home-me--config-my-app");
+    }
+
+    #[test]
+    fn test_decode_project_path_bounded() {
+        let encoded = "-a".repeat(MAX_DECODE_HYPHENS + 2);
+        assert_eq!(decode_project_path(&encoded), encoded.replace('-', "/"));
     }
 
     #[test]
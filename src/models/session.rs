@@ -1,10 +1,15 @@
 //! Session summary data.
 
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::activity::ActivitySpan;
+use super::usage::{UsageRecord, UsageTotals};
+use crate::utils::shell_quote;
+
 /// Summary of a Claude Code session.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     /// Session UUID
     pub id: String,
@@ -30,12 +35,70 @@ pub struct Session {
     pub is_agent: bool,
     /// Agent ID if this is an agent session
     pub agent_id: Option<String>,
+    /// Session that spawned this sidechain
+    #[serde(default)]
+    pub parent_session_id: Option<String>,
+    /// Token usage per day and model
+    #[serde(default)]
+    pub usage: Vec<UsageRecord>,
+    /// Stretches of back-and-forth, split on idle gaps
+    #[serde(default)]
+    pub activity: Vec<ActivitySpan>,
+    /// User-set title, tags and flags (stored in the metadata DB, not the JSONL)
+    #[serde(default, skip_serializing_if = "SessionMeta::is_empty")]
+    pub meta: SessionMeta,
+}
+
+/// User metadata for a session.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionMeta {
+    /// Custom title, shown instead of the slug
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Free-form tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Free-form note
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Listed before other sessions
+    #[serde(default)]
+    pub pinned: bool,
+    /// Hidden from the browser unless archived sessions are shown
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl SessionMeta {
+    /// Whether nothing has been set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the session has a tag, ignoring case.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Parse a comma- or space-separated tag list, dropping `#` prefixes and duplicates.
+    pub fn parse_tags(input: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.split(|c: char| c == ',' || c.is_whitespace()) {
+            let tag = tag.trim_start_matches('#');
+            if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
 }
 
 impl Session {
     /// Get a display name for the session.
     pub fn display_name(&self) -> String {
-        if let Some(slug) = &self.slug {
+        if let Some(title) = &self.meta.title {
+            title.clone()
+        } else if let Some(slug) = &self.slug {
             slug.clone()
         } else if let Some(agent_id) = &self.agent_id {
             format!("agent-{}", agent_id)
@@ -45,9 +108,15 @@ impl Session {
         }
     }
 
-    /// Get the resume command for this session.
-    pub fn resume_command(&self) -> String {
-        format!("claude --resume {}", self.id)
+    /// Token usage summed over the whole session, by model.
+    pub fn usage_totals(&self) -> UsageTotals {
+        UsageTotals::from_records(&self.usage)
+    }
+
+    /// Shell command that resumes this session from its project directory.
+    pub fn resume_command(&self, skip_permissions: bool) -> String {
+        let flags = if skip_permissions { " --dangerously-skip-permissions" } else { "" };
+        format!("cd {} && claude{} --resume {}", shell_quote(&self.project_path), flags, self.id)
     }
 
     /// Calculate approximate duration.
@@ -70,3 +139,29 @@ impl Session {
         }
     }
 }
+
+#[cfg(test)]
+impl Session {
+    /// A one-message session with the given ID, slug and last message time.
+    pub fn test(id: &str, slug: Option<&str>, last_message: &str) -> Self {
+        let last_message = DateTime::parse_from_rfc3339(last_message).unwrap().with_timezone(&Utc);
+        Self {
+            id: id.to_string(),
+            project_path: "/work/app".to_string(),
+            slug: slug.map(str::to_string),
+            git_branch: None,
+            first_message: last_message,
+            last_message,
+            message_count: 1,
+            preview_text: String::new(),
+            file_path: PathBuf::from(format!("/work/.claude/projects/-work-app/{}.jsonl", id)),
+            file_size: 0,
+            is_agent: false,
+            agent_id: None,
+            parent_session_id: None,
+            usage: Vec::new(),
+            activity: Vec::new(),
+            meta: SessionMeta::default(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Events printed by `claude -p --output-format stream-json`.
+
+use serde::Deserialize;
+
+use super::message::MessageContent;
+
+/// One line of a headless agent's stream-json log.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StreamEvent {
+    /// Session start, with the tools and model in use
+    System {
+        #[serde(default)]
+        session_id: Option<String>,
+    },
+    Assistant {
+        message: MessageContent,
+    },
+    /// Final event of the run
+    Result {
+        #[serde(default)]
+        is_error: bool,
+        /// Text of the final reply
+        #[serde(default)]
+        result: Option<String>,
+    },
+    /// Tool results sent back to the model, and anything newer
+    #[serde(other)]
+    Other,
+}
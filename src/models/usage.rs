@@ -0,0 +1,165 @@
+//! Token usage totals and cost.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+use crate::config::{Config, ModelPrice};
+
+/// Token counts for one or more API responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenCounts {
+    pub input: u64,
+    pub output: u64,
+    pub cache_read: u64,
+    pub cache_creation: u64,
+}
+
+impl TokenCounts {
+    /// All tokens, including cache reads and writes.
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_read + self.cache_creation
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// Dollar cost of these tokens at the given per-million-token prices.
+    pub fn cost(&self, price: &ModelPrice) -> f64 {
+        (self.input as f64 * price.input
+            + self.output as f64 * price.output
+            + self.cache_read as f64 * price.cache_read
+            + self.cache_creation as f64 * price.cache_write)
+            / 1_000_000.0
+    }
+}
+
+impl AddAssign for TokenCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_read += other.cache_read;
+        self.cache_creation += other.cache_creation;
+    }
+}
+
+/// Tokens used by one model on one (local) day of a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub date: NaiveDate,
+    pub model: String,
+    pub tokens: TokenCounts,
+}
+
+/// Token totals broken down by model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    pub by_model: BTreeMap<String, TokenCounts>,
+}
+
+impl UsageTotals {
+    /// Sum a set of per-day records.
+    pub fn from_records<'a>(records: impl IntoIterator<Item = &'a UsageRecord>) -> Self {
+        let mut totals = Self::default();
+        for record in records {
+            totals.add(&record.model, record.tokens);
+        }
+        totals
+    }
+
+    pub fn add(&mut self, model: &str, tokens: TokenCounts) {
+        *self.by_model.entry(model.to_string()).or_default() += tokens;
+    }
+
+    pub fn merge(&mut self, other: &UsageTotals) {
+        for (model, tokens) in &other.by_model {
+            self.add(model, *tokens);
+        }
+    }
+
+    /// Tokens summed over all models.
+    pub fn tokens(&self) -> TokenCounts {
+        let mut sum = TokenCounts::default();
+        for tokens in self.by_model.values() {
+            sum += *tokens;
+        }
+        sum
+    }
+
+    /// Dollar cost using the configured price table; unpriced models count as zero.
+    pub fn cost(&self, config: &Config) -> f64 {
+        self.by_model
+            .iter()
+            .filter_map(|(model, tokens)| config.price_for(model).map(|p| tokens.cost(&p)))
+            .fold(0.0, |total, cost| total + cost) // sum() of nothing is -0.0
+    }
+
+    /// Models that have usage but no entry in the price table.
+    pub fn unpriced_models<'a>(&'a self, config: &'a Config) -> impl Iterator<Item = &'a str> {
+        self.by_model
+            .keys()
+            .map(String::as_str)
+            .filter(|model| config.price_for(model).is_none())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_model.values().all(TokenCounts::is_empty)
+    }
+}
+
+/// Compact token count, e.g. "950", "12.3k", "4.1M".
+pub fn format_tokens(count: u64) -> String {
+    if count >= 1_000_000 {
+        format!("{:.1}M", count as f64 / 1_000_000.0)
+    } else if count >= 1_000 {
+        format!("{:.1}k", count as f64 / 1_000.0)
+    } else {
+        count.to_string()
+    }
+}
+
+/// Dollar amount with cent precision.
+pub fn format_cost(cost: f64) -> String {
+    format!("${:.2}", cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageContent;
+
+    #[test]
+    fn test_usage_deserializes_snake_case() {
+        let json = r#"{"role":"assistant","id":"msg_1","model":"claude-sonnet-4-5","content":[],
+            "usage":{"input_tokens":10,"output_tokens":20,"cache_read_input_tokens":30,"cache_creation_input_tokens":40}}"#;
+        let message: MessageContent = serde_json::from_str(json).unwrap();
+        let tokens = TokenCounts::from(message.usage().unwrap());
+        assert_eq!(message.id(), Some("msg_1"));
+        assert_eq!(
+            tokens,
+            TokenCounts { input: 10, output: 20, cache_read: 30, cache_creation: 40 }
+        );
+    }
+
+    #[test]
+    fn test_price_lookup_and_cost() {
+        let mut config = Config::default();
+        // Longest built-in key wins
+        assert_eq!(config.price_for("claude-opus-4-5-20251101").unwrap().input, 5.0);
+        assert_eq!(config.price_for("claude-opus-4-1-20250805").unwrap().input, 15.0);
+        assert!(config.price_for("<synthetic>").is_none());
+
+        // Configured prices take precedence
+        config.pricing.insert(
+            "sonnet".to_string(),
+            ModelPrice { input: 1.0, output: 2.0, cache_read: 0.0, cache_write: 0.0 },
+        );
+        let mut totals = UsageTotals::default();
+        totals.add("claude-sonnet-4-5", TokenCounts { input: 1_000_000, output: 500_000, ..Default::default() });
+        totals.add("<synthetic>", TokenCounts { input: 5, ..Default::default() });
+        assert!((totals.cost(&config) - 2.0).abs() < 1e-9);
+        assert_eq!(totals.unpriced_models(&config).collect::<Vec<_>>(), vec!["<synthetic>"]);
+    }
+}
@@ -48,7 +48,7 @@ impl AgentDetailScreen {
             AgentStatus::Starting => self.theme.color3,
             AgentStatus::Active => self.theme.color2,
             AgentStatus::Idle => self.theme.color3,
-            AgentStatus::Complete => self.theme.color6,
+            AgentStatus::Finished | AgentStatus::Complete => self.theme.color6,
             AgentStatus::Failed => self.theme.color1,
             AgentStatus::Killed => self.theme.color1,
         };
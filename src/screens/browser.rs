@@ -2,24 +2,34 @@
 
 use async_trait::async_trait;
 use crossterm::event::{KeyCode, KeyEvent};
+use tui_textarea::{CursorMove, TextArea};
 use ansi_to_tui::IntoText;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 use ratatui_garnish::{
     shadow::HalfShadow, GarnishableStatefulWidget, GarnishableWidget, Padding,
 };
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::config::Config;
-use crate::models::{Project, Session};
-use crate::services::{ascii_art, SessionStore, Theme};
+use crate::config::{expand_path, Config, LaunchMode, ProjectGrouping, SortMode};
+use crate::event::SessionChange;
+use crate::models::usage::{format_cost, format_tokens};
+use crate::models::{Project, Session, SessionMeta};
+use crate::services::metadata_store::SearchHit;
+use crate::services::exporter::{self, ExportFormat, ExportOptions};
+use crate::services::{ascii_art, MetadataStore, SessionStore, Theme};
+use crate::utils::fuzzy::{match_fields, FieldsMatch};
+use crate::utils::ordering::{group_projects, sort_projects, sort_sessions};
 
-use super::{Screen, ScreenAction};
+use super::{centered_rect, unless_missing, Screen, ScreenAction};
 
 /// Which pane has focus.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -28,6 +38,36 @@ enum Focus {
     Sessions,
 }
 
+/// Which piece of session metadata a prompt edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetaField {
+    Title,
+    Tags,
+    Note,
+}
+
+impl MetaField {
+    fn label(self) -> &'static str {
+        match self {
+            MetaField::Title => " Rename session ",
+            MetaField::Tags => " Tags (comma or space separated) ",
+            MetaField::Note => " Note ",
+        }
+    }
+}
+
+/// Maximum number of sessions shown in full-text search results.
+const SEARCH_RESULT_LIMIT: usize = 50;
+
+/// State of the full-text search overlay.
+#[derive(Default)]
+struct SearchState {
+    query: String,
+    hits: Vec<SearchHit>,
+    list_state: ListState,
+    error: Option<String>,
+}
+
 /// Browser screen for navigating projects and sessions.
 pub struct BrowserScreen {
     session_store: Arc<SessionStore>,
@@ -37,12 +77,45 @@ pub struct BrowserScreen {
     // UI state
     focus: Focus,
     project_state: ListState,
+    /// Row state of the rendered project list, which includes group headers
+    project_view_state: ListState,
     session_state: ListState,
     sessions_visible: bool,
+    sort: SortMode,
+    grouping: ProjectGrouping,
 
     // Cached data
     projects: Vec<Project>,
+    /// Group headers and sizes, in the order of `projects` (empty when ungrouped)
+    project_groups: Vec<(String, usize)>,
+    /// Repository root of each project path, for grouping by repository
+    repo_roots: HashMap<String, Option<PathBuf>>,
+    /// Visible session rows: top-level sessions, each followed by its
+    /// sidechains when expanded
     current_sessions: Vec<Session>,
+    /// Sessions whose sidechains are expanded
+    expanded: HashSet<String>,
+
+    // Full-text search (F6)
+    search: Option<SearchState>,
+    search_db: Option<Arc<MetadataStore>>,
+    /// Session files changed since the search index was last synced
+    search_index_stale: bool,
+
+    /// Launch mode picker (`m`): index into `LaunchMode::ALL` while open
+    launch_picker: Option<usize>,
+    /// New-location prompt for relinking a moved project (`L`)
+    relink_input: Option<String>,
+
+    /// Title/tags/note prompt for the selected session (`r`, `t`, `N`)
+    meta_input: Option<(MetaField, String)>,
+    /// Whether archived sessions are listed (`A`)
+    show_archived: bool,
+
+    // Fuzzy filter (`/`): the input being edited, for the pane it filters
+    filter_input: Option<(Focus, TextArea<'static>)>,
+    project_query: String,
+    session_query: String,
 
     // Splash art (randomly selected on startup)
     splash_art: &'static str,
@@ -58,14 +131,30 @@ impl BrowserScreen {
 
         Self {
             session_store,
-            config,
             theme,
             focus: Focus::Projects,
             project_state,
+            project_view_state: ListState::default(),
             session_state: ListState::default(),
             sessions_visible: false,
+            sort: config.display.sort,
+            grouping: config.display.group_projects,
+            config,
             projects: Vec::new(),
+            project_groups: Vec::new(),
+            repo_roots: HashMap::new(),
             current_sessions: Vec::new(),
+            expanded: HashSet::new(),
+            search: None,
+            search_db: None,
+            search_index_stale: false,
+            launch_picker: None,
+            relink_input: None,
+            meta_input: None,
+            show_archived: false,
+            filter_input: None,
+            project_query: String::new(),
+            session_query: String::new(),
             splash_art: ascii_art::random_art(),
             comic_art: ascii_art::random_comic_art(),
         }
@@ -79,17 +168,10 @@ impl BrowserScreen {
             .ok_or_else(|| anyhow::anyhow!("Session store is shared"))?;
 
         store.scan()?;
-        self.projects = store.projects().to_vec();
+        self.refresh_projects();
 
         // Load sessions for first project
-        if let Some(project) = self.projects.first() {
-            if let Some(sessions) = store.sessions_for_project(&project.encoded_path) {
-                self.current_sessions = sessions.clone();
-                if !self.current_sessions.is_empty() {
-                    self.session_state.select(Some(0));
-                }
-            }
-        }
+        self.update_sessions_for_project();
 
         Ok(())
     }
@@ -99,9 +181,101 @@ impl BrowserScreen {
         self.session_store.total_session_count()
     }
 
-    /// Get the list of projects (for spawn wizard).
+    /// The underlying session store.
+    pub fn session_store(&self) -> &SessionStore {
+        &self.session_store
+    }
+
+    /// Snapshot of every loaded session (for background indexing).
+    pub fn all_sessions(&self) -> Vec<Session> {
+        self.session_store.all_sessions().cloned().collect()
+    }
+
+    /// Bring the full-text search index up to date in the background.
+    pub fn refresh_search_index(&mut self) {
+        self.search_index_stale = false;
+        let sessions = self.all_sessions();
+        tokio::task::spawn_blocking(move || {
+            match MetadataStore::open_default().and_then(|db| db.sync_search_index(&sessions)) {
+                Ok(count) => tracing::debug!("Search index updated ({} sessions re-indexed)", count),
+                Err(e) => tracing::warn!("Failed to update search index: {}", e),
+            }
+        });
+    }
+
+    /// Apply a session file change from the watcher, keeping the current selection.
+    pub fn apply_session_change(&mut self, path: &Path, change: SessionChange) {
+        let selected_session = self.selected_session().map(|s| s.id.clone());
+
+        let Some(store) = Arc::get_mut(&mut self.session_store) else {
+            tracing::warn!("Session store is shared, ignoring change to {:?}", path);
+            return;
+        };
+        if store.apply_change(path, change).is_none() {
+            return;
+        }
+        self.search_index_stale = true;
+        self.refresh_projects();
+        self.rebuild_session_rows();
+
+        let session_index = selected_session
+            .and_then(|id| self.current_sessions.iter().position(|s| s.id == id))
+            .or_else(|| (!self.current_sessions.is_empty()).then_some(0));
+        self.session_state.select(session_index);
+
+        if self.current_sessions.is_empty() && self.focus == Focus::Sessions {
+            self.focus = Focus::Projects;
+            self.sessions_visible = false;
+        }
+    }
+
+    /// Whether the screen is capturing text input (keys shouldn't be treated as global shortcuts).
+    pub fn is_capturing_input(&self) -> bool {
+        self.search.is_some()
+            || self.launch_picker.is_some()
+            || self.relink_input.is_some()
+            || self.meta_input.is_some()
+            || self.filter_input.is_some()
+    }
+
+    /// Whether a fuzzy filter is applied (Esc clears it before quitting).
+    pub fn has_filter(&self) -> bool {
+        !self.project_query.is_empty() || !self.session_query.is_empty()
+    }
+
+    /// Get the list of all projects, ignoring the filter (for spawn wizard).
     pub fn projects(&self) -> &[Project] {
-        &self.projects
+        self.session_store.projects()
+    }
+
+    /// Rebuild the visible project list from the store, applying the filter,
+    /// sort order and grouping.
+    ///
+    /// Keeps the selected project if it is still visible, otherwise clamps
+    /// the selection into the new list.
+    fn refresh_projects(&mut self) {
+        let selected = self.selected_project().map(|p| p.encoded_path.clone());
+
+        let mut projects = self.session_store.projects().to_vec();
+        sort_projects(&mut projects, self.sort);
+        let mut matches: Vec<(i64, Project)> = projects
+            .into_iter()
+            .filter_map(|p| {
+                match_fields(&self.project_query, &[&p.display_name, &p.decoded_path]).map(|m| (m.score, p))
+            })
+            .collect();
+        // Best match first; ties keep the sort order
+        matches.sort_by_key(|(score, _)| Reverse(*score));
+        self.projects = matches.into_iter().map(|(_, p)| p).collect();
+        self.project_groups = group_projects(&mut self.projects, self.grouping, &mut self.repo_roots);
+
+        let index = selected
+            .and_then(|encoded| self.projects.iter().position(|p| p.encoded_path == encoded))
+            .or_else(|| {
+                let old = self.project_state.selected().unwrap_or(0);
+                (!self.projects.is_empty()).then(|| old.min(self.projects.len() - 1))
+            });
+        self.project_state.select(index);
     }
 
     /// Get currently selected project.
@@ -120,21 +294,87 @@ impl BrowserScreen {
 
     /// Update sessions list when project changes.
     fn update_sessions_for_project(&mut self) {
-        if let Some(project) = self.selected_project() {
-            if let Some(sessions) = self.session_store.sessions_for_project(&project.encoded_path) {
-                self.current_sessions = sessions.clone();
-                self.session_state.select(if self.current_sessions.is_empty() {
-                    None
-                } else {
-                    Some(0)
-                });
-            } else {
-                self.current_sessions.clear();
-                self.session_state.select(None);
-            }
+        self.session_query.clear();
+        self.rebuild_session_rows();
+        self.session_state.select(if self.current_sessions.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    /// Rebuild the visible session rows for the selected project, applying
+    /// the filter to top-level sessions.
+    fn rebuild_session_rows(&mut self) {
+        let store = &self.session_store;
+        let rows: Vec<Session> = self
+            .selected_project()
+            .and_then(|p| store.sessions_for_project(&p.encoded_path))
+            .map(|sessions| {
+                let mut sorted: Vec<&Session> = sessions
+                    .iter()
+                    .filter(|s| self.show_archived || !s.meta.archived)
+                    .collect();
+                sort_sessions(&mut sorted, self.sort);
+                sorted.sort_by_key(|s| !s.meta.pinned);
+                let mut matches: Vec<(i64, &Session)> = sorted
+                    .into_iter()
+                    .filter_map(|s| match_session(&self.session_query, s).map(|m| (m.score, s)))
+                    .collect();
+                matches.sort_by_key(|(score, _)| Reverse(*score));
+                matches
+                    .into_iter()
+                    .flat_map(|(_, s)| {
+                        let sidechains = if self.expanded.contains(&s.id) {
+                            self.tree_sidechains(&s.id)
+                        } else {
+                            &[]
+                        };
+                        std::iter::once(s).chain(sidechains)
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.current_sessions = rows;
+    }
+
+    /// Sidechains to show under a session, or none if agent sessions are hidden.
+    fn tree_sidechains(&self, session_id: &str) -> &[Session] {
+        if self.config.display.show_agent_sessions {
+            self.session_store.sidechains_for(session_id)
+        } else {
+            &[]
         }
     }
 
+    /// The session owning the selected row's sidechain tree, if it has sidechains.
+    fn selected_tree_parent(&self) -> Option<String> {
+        let session = self.selected_session()?;
+        let parent_id = match &session.parent_session_id {
+            Some(parent_id) if session.is_agent => parent_id.clone(),
+            _ => session.id.clone(),
+        };
+        (!self.tree_sidechains(&parent_id).is_empty()).then_some(parent_id)
+    }
+
+    /// Expand or collapse a session's sidechains, keeping the cursor on it.
+    ///
+    /// Returns false if nothing changed.
+    fn set_expanded(&mut self, parent_id: String, expand: bool) -> bool {
+        let changed = if expand {
+            self.expanded.insert(parent_id.clone())
+        } else {
+            self.expanded.remove(&parent_id)
+        };
+        if changed {
+            self.rebuild_session_rows();
+            let index = self.current_sessions.iter().position(|s| s.id == parent_id);
+            self.session_state.select(index);
+        }
+        changed
+    }
+
     /// Navigate up in current list.
     fn move_up(&mut self) {
         match self.focus {
@@ -189,6 +429,514 @@ impl BrowserScreen {
         }
     }
 
+    /// Open the full-text search overlay.
+    fn open_search(&mut self) {
+        if self.search_index_stale {
+            self.refresh_search_index();
+        }
+
+        let mut state = SearchState::default();
+        if self.search_db.is_none() {
+            match MetadataStore::open_default() {
+                Ok(db) => self.search_db = Some(Arc::new(db)),
+                Err(e) => state.error = Some(format!("Search index unavailable: {}", e)),
+            }
+        }
+        self.search = Some(state);
+    }
+
+    /// Re-run the search query against the index.
+    fn run_search(&mut self) {
+        let (Some(search), Some(db)) = (self.search.as_mut(), self.search_db.as_ref()) else {
+            return;
+        };
+
+        match db.search(&search.query, SEARCH_RESULT_LIMIT) {
+            Ok(hits) => {
+                search.list_state.select(if hits.is_empty() { None } else { Some(0) });
+                search.hits = hits;
+                search.error = None;
+            }
+            Err(e) => {
+                search.hits.clear();
+                search.list_state.select(None);
+                search.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Handle keys while the search overlay is open.
+    fn handle_search_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let Some(search) = self.search.as_mut() else {
+            return ScreenAction::None;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.search = None;
+            }
+            KeyCode::Up => {
+                if let Some(selected) = search.list_state.selected() {
+                    let new_index = if selected == 0 {
+                        search.hits.len().saturating_sub(1)
+                    } else {
+                        selected - 1
+                    };
+                    search.list_state.select(Some(new_index));
+                }
+            }
+            KeyCode::Down => {
+                if let Some(selected) = search.list_state.selected() {
+                    let new_index = if selected >= search.hits.len().saturating_sub(1) {
+                        0
+                    } else {
+                        selected + 1
+                    };
+                    search.list_state.select(Some(new_index));
+                }
+            }
+            KeyCode::Enter => {
+                let hit = search
+                    .list_state
+                    .selected()
+                    .and_then(|i| search.hits.get(i));
+                if let Some(hit) = hit {
+                    // Prefer the live session summary's cwd over the indexed one
+                    let project_path = self
+                        .session_store
+                        .find_session(&hit.session_id)
+                        .map(|s| s.project_path.clone())
+                        .unwrap_or_else(|| hit.project_path.clone());
+                    return unless_missing(ScreenAction::LaunchSession {
+                        session_id: hit.session_id.clone(),
+                        project_path,
+                        mode: None,
+                    });
+                }
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                self.run_search();
+            }
+            KeyCode::Char(c) => {
+                search.query.push(c);
+                self.run_search();
+            }
+            _ => {}
+        }
+
+        ScreenAction::None
+    }
+
+    /// Draw the search overlay (input + ranked results) in the given area.
+    fn draw_search(&mut self, f: &mut Frame, area: Rect) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+
+        let input = Paragraph::new(Line::from(vec![
+            Span::raw(" "),
+            Span::styled(&search.query, Style::default().fg(self.theme.foreground)),
+            Span::styled("█", Style::default().fg(self.theme.color6)),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Search all sessions")
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        f.render_widget(input, chunks[0]);
+
+        let results_block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Results ({})", search.hits.len()))
+            .border_style(Style::default().fg(self.theme.color6));
+
+        if let Some(error) = &search.error {
+            let message = Paragraph::new(error.as_str())
+                .block(results_block)
+                .style(Style::default().fg(self.theme.color1))
+                .wrap(ratatui::widgets::Wrap { trim: true });
+            f.render_widget(message, chunks[1]);
+            return;
+        }
+
+        let highlight = Style::default()
+            .fg(self.theme.color3)
+            .add_modifier(Modifier::BOLD);
+        let items: Vec<ListItem> = search
+            .hits
+            .iter()
+            .map(|hit| {
+                let project_name = hit
+                    .project_path
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&hit.project_path)
+                    .to_string();
+                let session = self.session_store.find_session(&hit.session_id);
+                let session_name = session
+                    .map(|s| s.display_name())
+                    .unwrap_or_else(|| hit.session_id.chars().take(8).collect());
+                let date = session
+                    .map(|s| s.last_message.format(&self.config.display.date_format).to_string())
+                    .unwrap_or_default();
+
+                let header = Line::from(vec![
+                    Span::styled(
+                        project_name,
+                        Style::default().fg(self.theme.color6).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("  {}", session_name), Style::default().fg(self.theme.foreground)),
+                    Span::styled(
+                        format!("  {}  {} · {} matches", date, hit.kind, hit.match_count),
+                        Style::default().fg(self.theme.color8),
+                    ),
+                ]);
+
+                let mut snippet = vec![Span::raw("    ")];
+                snippet.extend(hit.snippet_segments().into_iter().map(|(text, hl)| {
+                    if hl {
+                        Span::styled(text, highlight)
+                    } else {
+                        Span::styled(text, Style::default().fg(self.theme.color7))
+                    }
+                }));
+
+                ListItem::new(vec![header, Line::from(snippet)])
+            })
+            .collect();
+
+        let results = List::new(items)
+            .block(results_block)
+            .highlight_style(
+                Style::default()
+                    .bg(self.theme.color8)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("► ");
+        f.render_stateful_widget(results, chunks[1], &mut search.list_state);
+    }
+
+    /// Handle keys while the launch mode picker is open.
+    fn handle_launch_picker_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let Some(selected) = self.launch_picker else {
+            return ScreenAction::None;
+        };
+        let count = LaunchMode::ALL.len();
+
+        match key.code {
+            KeyCode::Esc => self.launch_picker = None,
+            KeyCode::Up => self.launch_picker = Some((selected + count - 1) % count),
+            KeyCode::Down => self.launch_picker = Some((selected + 1) % count),
+            KeyCode::Enter => {
+                self.launch_picker = None;
+                if let Some(session) = self.selected_session() {
+                    return unless_missing(ScreenAction::LaunchSession {
+                        session_id: session.id.clone(),
+                        project_path: session.project_path.clone(),
+                        mode: Some(LaunchMode::ALL[selected]),
+                    });
+                }
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
+    /// Draw the launch mode picker as a centered popup.
+    fn draw_launch_picker(&self, f: &mut Frame, area: Rect) {
+        let Some(selected) = self.launch_picker else {
+            return;
+        };
+
+        let popup_area = centered_rect(40, 30, area);
+        f.render_widget(Clear, popup_area);
+
+        let items: Vec<ListItem> = LaunchMode::ALL
+            .iter()
+            .map(|mode| {
+                let default_marker = if *mode == self.config.launcher.mode { " (default)" } else { "" };
+                ListItem::new(Line::from(vec![
+                    Span::styled(mode.label(), Style::default().fg(self.theme.foreground)),
+                    Span::styled(default_marker, Style::default().fg(self.theme.color8)),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default();
+        state.select(Some(selected));
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Resume in… ")
+                    .border_style(Style::default().fg(self.theme.color6)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(self.theme.color8)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, popup_area, &mut state);
+    }
+
+    /// Open the fuzzy filter for the focused pane, starting from its current query.
+    fn open_filter(&mut self) {
+        let query = match self.focus {
+            Focus::Projects => &self.project_query,
+            Focus::Sessions => &self.session_query,
+        };
+        let mut input = TextArea::new(vec![query.clone()]);
+        input.move_cursor(CursorMove::End);
+        input.set_cursor_line_style(Style::default());
+        input.set_style(Style::default().fg(self.theme.foreground));
+        input.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Filter ")
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        self.filter_input = Some((self.focus, input));
+    }
+
+    /// Set a pane's filter query and refilter its list.
+    fn set_filter(&mut self, target: Focus, query: String) {
+        match target {
+            Focus::Projects => {
+                self.project_query = query;
+                let previous = self.selected_project().map(|p| p.encoded_path.clone());
+                self.refresh_projects();
+                if self.selected_project().map(|p| &p.encoded_path) != previous.as_ref() {
+                    self.update_sessions_for_project();
+                }
+            }
+            Focus::Sessions => {
+                self.session_query = query;
+                self.refresh_sessions();
+            }
+        }
+    }
+
+    /// Rebuild the session rows, keeping the selected session if it is still shown.
+    fn refresh_sessions(&mut self) {
+        let selected = self.selected_session().map(|s| s.id.clone());
+        self.rebuild_session_rows();
+        let index = selected
+            .and_then(|id| self.current_sessions.iter().position(|s| s.id == id))
+            .or_else(|| (!self.current_sessions.is_empty()).then_some(0));
+        self.session_state.select(index);
+    }
+
+    /// Re-sort and regroup both lists after the sort mode or grouping changed.
+    fn reorder(&mut self) {
+        self.refresh_projects();
+        self.refresh_sessions();
+    }
+
+    /// Handle keys while the filter input is open.
+    fn handle_filter_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let Some((target, input)) = self.filter_input.as_mut() else {
+            return ScreenAction::None;
+        };
+        let target = *target;
+
+        match key.code {
+            KeyCode::Esc => {
+                self.filter_input = None;
+                self.set_filter(target, String::new());
+            }
+            KeyCode::Enter => self.filter_input = None,
+            KeyCode::Up => self.move_up(),
+            KeyCode::Down => self.move_down(),
+            _ => {
+                if input.input(key) {
+                    let query = input.lines().join(" ");
+                    self.set_filter(target, query);
+                }
+            }
+        }
+        ScreenAction::None
+    }
+
+    /// Handle keys while the relink prompt is open.
+    fn handle_relink_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let Some(input) = self.relink_input.as_mut() else {
+            return ScreenAction::None;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.relink_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Enter => {
+                let new_path = std::mem::take(input);
+                self.relink_input = None;
+                return self.relink_selected_project(&new_path);
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
+    /// Move the selected project's sessions over to `new_path`.
+    fn relink_selected_project(&mut self, new_path: &str) -> ScreenAction {
+        let Some(encoded_path) = self.selected_project().map(|p| p.encoded_path.clone()) else {
+            return ScreenAction::None;
+        };
+        let Some(store) = Arc::get_mut(&mut self.session_store) else {
+            return ScreenAction::StatusMessage("Session store is busy, try again".to_string());
+        };
+
+        let new_path = expand_path(new_path.trim());
+        match store.relink_project(&encoded_path, Path::new(&new_path)) {
+            Ok(new_encoded) => {
+                self.search_index_stale = true;
+                self.project_query.clear();
+                self.refresh_projects();
+                let index = self.projects.iter().position(|p| p.encoded_path == new_encoded);
+                self.project_state.select(index.or(Some(0)));
+                self.focus = Focus::Projects;
+                self.sessions_visible = false;
+                self.update_sessions_for_project();
+                ScreenAction::StatusMessage(format!("Relinked to {}", new_path))
+            }
+            Err(e) => ScreenAction::StatusMessage(format!("Relink failed: {:#}", e)),
+        }
+    }
+
+    /// Draw the relink prompt as a centered popup.
+    fn draw_relink_prompt(&self, f: &mut Frame, area: Rect) {
+        let Some(input) = &self.relink_input else {
+            return;
+        };
+        let project = self.selected_project();
+
+        let popup_area = centered_rect(60, 25, area);
+        f.render_widget(Clear, popup_area);
+
+        let old_path = project.map(|p| p.decoded_path.as_str()).unwrap_or_default();
+        let prompt = Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!(" Was: {}", old_path),
+                Style::default().fg(self.theme.color8),
+            )),
+            Line::from(vec![
+                Span::styled(" Now: ", Style::default().fg(self.theme.color7)),
+                Span::styled(input.as_str(), Style::default().fg(self.theme.foreground)),
+                Span::styled("█", Style::default().fg(self.theme.color6)),
+            ]),
+            Line::from(Span::styled(
+                " Enter relink · Esc cancel",
+                Style::default().fg(self.theme.color8),
+            )),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Relink moved project ")
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        f.render_widget(prompt, popup_area);
+    }
+
+    /// Open a prompt to edit the selected session's title, tags or note.
+    fn open_meta_prompt(&mut self, field: MetaField) {
+        let Some(meta) = self.selected_session().filter(|s| !s.is_agent).map(|s| &s.meta) else {
+            return;
+        };
+        let current = match field {
+            MetaField::Title => meta.title.clone().unwrap_or_default(),
+            MetaField::Tags => meta.tags.join(", "),
+            MetaField::Note => meta.note.clone().unwrap_or_default(),
+        };
+        self.meta_input = Some((field, current));
+    }
+
+    /// Handle keys while the metadata prompt is open.
+    fn handle_meta_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let Some((field, input)) = self.meta_input.as_mut() else {
+            return ScreenAction::None;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.meta_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Enter => {
+                let (field, value) = (*field, std::mem::take(input));
+                self.meta_input = None;
+                let value = value.trim();
+                let text = (!value.is_empty()).then(|| value.to_string());
+                return self.update_selected_meta(|meta| match field {
+                    MetaField::Title => meta.title = text,
+                    MetaField::Tags => meta.tags = SessionMeta::parse_tags(value),
+                    MetaField::Note => meta.note = text,
+                });
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
+    /// Apply `edit` to the selected session's metadata and save it.
+    fn update_selected_meta(&mut self, edit: impl FnOnce(&mut SessionMeta)) -> ScreenAction {
+        let Some(session) = self.selected_session().filter(|s| !s.is_agent) else {
+            return ScreenAction::None;
+        };
+        let session_id = session.id.clone();
+        let mut meta = session.meta.clone();
+        edit(&mut meta);
+
+        let Some(store) = Arc::get_mut(&mut self.session_store) else {
+            return ScreenAction::StatusMessage("Session store is busy, try again".to_string());
+        };
+        if let Err(e) = store.set_session_meta(&session_id, meta) {
+            return ScreenAction::StatusMessage(format!("Failed to save: {:#}", e));
+        }
+        self.refresh_sessions();
+        ScreenAction::None
+    }
+
+    /// Draw the metadata prompt as a centered popup.
+    fn draw_meta_prompt(&self, f: &mut Frame, area: Rect) {
+        let Some((field, input)) = &self.meta_input else {
+            return;
+        };
+
+        let popup_area = centered_rect(60, 20, area);
+        f.render_widget(Clear, popup_area);
+
+        let prompt = Paragraph::new(vec![
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(input.as_str(), Style::default().fg(self.theme.foreground)),
+                Span::styled("█", Style::default().fg(self.theme.color6)),
+            ]),
+            Line::from(Span::styled(
+                " Enter save (empty clears) · Esc cancel",
+                Style::default().fg(self.theme.color8),
+            )),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(field.label())
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        f.render_widget(prompt, popup_area);
+    }
+
     /// Switch focus between panes.
     fn switch_focus(&mut self) {
         self.focus = match self.focus {
@@ -198,6 +946,90 @@ impl BrowserScreen {
     }
 }
 
+/// Match the session filter against preview, display name, branch, path,
+/// note and slug (in that field order).
+///
+/// The slug is its own field so it still matches once a title hides it.
+///
+/// `#tag` terms must match one of the session's tags exactly instead.
+fn match_session(query: &str, session: &Session) -> Option<FieldsMatch> {
+    let (tags, terms): (Vec<&str>, Vec<&str>) = query
+        .split_whitespace()
+        .partition(|term| term.len() > 1 && term.starts_with('#'));
+    if !tags.iter().all(|tag| session.meta.has_tag(&tag[1..])) {
+        return None;
+    }
+
+    match_fields(
+        &terms.join(" "),
+        &[
+            &session.preview_text,
+            &session.display_name(),
+            session.git_branch.as_deref().unwrap_or(""),
+            &session.project_path,
+            session.meta.note.as_deref().unwrap_or(""),
+            session.slug.as_deref().unwrap_or(""),
+        ],
+    )
+}
+
+/// Split `text` into spans, styling the chars at `positions` (sorted char indices) with `highlight`.
+fn highlight_spans(text: &str, positions: &[usize], base: Style, highlight: Style) -> Vec<Span<'static>> {
+    if positions.is_empty() {
+        return vec![Span::styled(text.to_string(), base)];
+    }
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    for (i, c) in text.chars().enumerate() {
+        let highlighted = positions.binary_search(&i).is_ok();
+        if highlighted != run_highlighted && !run.is_empty() {
+            let style = if run_highlighted { highlight } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_highlighted = highlighted;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, if run_highlighted { highlight } else { base }));
+    }
+    spans
+}
+
+/// Carve a three-line filter input off the bottom of `area` when `editing`.
+fn split_filter_area(area: Rect, editing: bool) -> (Rect, Option<Rect>) {
+    if !editing {
+        return (area, None);
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+    (chunks[0], Some(chunks[1]))
+}
+
+/// Pane title suffix naming a non-default sort order and grouping.
+fn order_title(sort: SortMode, grouping: ProjectGrouping) -> String {
+    let mut title = String::new();
+    if sort != SortMode::Recency {
+        title.push_str(&format!(" · {}", sort.label()));
+    }
+    if grouping != ProjectGrouping::None {
+        title.push_str(&format!(" · per {}", grouping.label()));
+    }
+    title
+}
+
+/// Pane title suffix showing the filtered count and the active query.
+fn filter_title(query: &str, shown: usize, total: usize) -> String {
+    if query.is_empty() {
+        format!("({})", shown)
+    } else {
+        format!("({}/{}) /{}", shown, total, query)
+    }
+}
+
 #[async_trait]
 impl Screen for BrowserScreen {
     fn draw(&mut self, f: &mut Frame, area: Rect) {
@@ -227,25 +1059,73 @@ impl Screen for BrowserScreen {
                 .split(main_chunks[1])
         };
 
+        // Filter input sits at the bottom of the pane it filters
+        let (projects_area, project_filter_area) = split_filter_area(
+            main_chunks[0],
+            matches!(self.filter_input, Some((Focus::Projects, _))),
+        );
+        let match_style = Style::default().fg(self.theme.color3).add_modifier(Modifier::BOLD);
+
         // Projects pane
-        let project_items: Vec<ListItem> = self
-            .projects
-            .iter()
-            .map(|p| {
-                let style = Style::default().fg(self.theme.foreground);
-                ListItem::new(Line::from(vec![
-                    Span::styled(&p.display_name, style),
+        // Group headers are extra rows, so map the selection onto the rendered rows
+        let mut group_starts = Vec::new();
+        let mut start = 0;
+        for (label, count) in &self.project_groups {
+            group_starts.push((start, label, *count));
+            start += count;
+        }
+        let mut project_items: Vec<ListItem> = Vec::new();
+        let mut selected_row = None;
+        for (i, p) in self.projects.iter().enumerate() {
+            if let Some((_, label, count)) = group_starts.iter().find(|(start, _, _)| *start == i) {
+                project_items.push(ListItem::new(Line::from(vec![
                     Span::styled(
-                        format!(" ({})", p.session_count),
-                        Style::default().fg(self.theme.color8),
+                        label.to_string(),
+                        Style::default().fg(self.theme.color4).add_modifier(Modifier::BOLD),
                     ),
-                ]))
-            })
-            .collect();
+                    Span::styled(format!(" ({})", count), Style::default().fg(self.theme.color8)),
+                ])));
+            }
+            if self.project_state.selected() == Some(i) {
+                selected_row = Some(project_items.len());
+            }
+            project_items.push({
+                let style = if p.missing {
+                    Style::default().fg(self.theme.color8).add_modifier(Modifier::CROSSED_OUT)
+                } else {
+                    Style::default().fg(self.theme.foreground)
+                };
+                let positions = match_fields(&self.project_query, &[&p.display_name, &p.decoded_path])
+                    .map(|m| m.positions.into_iter().next().unwrap_or_default())
+                    .unwrap_or_default();
+                let indent = if self.project_groups.is_empty() { "" } else { "  " };
+                let mut spans = vec![Span::raw(indent)];
+                spans.extend(highlight_spans(&p.display_name, &positions, style, match_style));
+                spans.push(Span::styled(
+                    format!(" ({})", p.session_count),
+                    Style::default().fg(self.theme.color8),
+                ));
+                if p.missing {
+                    spans.push(Span::styled(" missing", Style::default().fg(self.theme.color1)));
+                }
+                if !p.usage.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" {}", format_cost(p.usage.cost(&self.config))),
+                        Style::default().fg(self.theme.color3),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            });
+        }
+        self.project_view_state.select(selected_row);
 
         let projects_block = Block::default()
             .borders(Borders::ALL)
-            .title(format!("Projects ({})", self.projects.len()))
+            .title(format!(
+                "Projects {}{}",
+                filter_title(&self.project_query, self.projects.len(), self.session_store.projects().len()),
+                order_title(self.sort, self.grouping),
+            ))
             .border_style(if self.focus == Focus::Projects {
                 Style::default().fg(self.theme.color6)
             } else {
@@ -265,9 +1145,18 @@ impl Screen for BrowserScreen {
         if self.focus == Focus::Projects {
             let garnished =
                 GarnishableStatefulWidget::garnish(projects_list, HalfShadow::default());
-            f.render_stateful_widget(garnished, main_chunks[0], &mut self.project_state);
+            f.render_stateful_widget(garnished, projects_area, &mut self.project_view_state);
         } else {
-            f.render_stateful_widget(projects_list, main_chunks[0], &mut self.project_state);
+            f.render_stateful_widget(projects_list, projects_area, &mut self.project_view_state);
+        }
+        if let (Some(area), Some((_, input))) = (project_filter_area, &self.filter_input) {
+            f.render_widget(input, area);
+        }
+
+        // Search overlay replaces the preview/sessions column
+        if self.search.is_some() {
+            self.draw_search(f, main_chunks[1]);
+            return;
         }
 
         // Preview pane (prominent, top right)
@@ -277,20 +1166,49 @@ impl Screen for BrowserScreen {
             // Use colored ANSI art - will be rendered separately
             ("total-recall", String::new())
         } else if let Some(session) = self.selected_session() {
-            let branch_info = session
+            let mut branch_info = session
                 .git_branch
                 .as_ref()
                 .map(|b| format!(" [{}]", b))
                 .unwrap_or_default();
+            if session.is_agent {
+                branch_info.push_str(" (sidechain, read-only)");
+            }
+
+            let totals = session.usage_totals();
+            let usage_info = if totals.is_empty() {
+                String::new()
+            } else {
+                let tokens = totals.tokens();
+                format!(
+                    "\n{} in | {} out | {} cache read | {} cache write | {}",
+                    format_tokens(tokens.input),
+                    format_tokens(tokens.output),
+                    format_tokens(tokens.cache_read),
+                    format_tokens(tokens.cache_creation),
+                    format_cost(totals.cost(&self.config)),
+                )
+            };
+
+            let mut meta_info = String::new();
+            if !session.meta.tags.is_empty() {
+                let tags: Vec<String> = session.meta.tags.iter().map(|t| format!("#{}", t)).collect();
+                meta_info.push_str(&format!("\n{}", tags.join(" ")));
+            }
+            if let Some(note) = &session.meta.note {
+                meta_info.push_str(&format!("\nNote: {}", note));
+            }
 
             (
                 "Preview",
                 format!(
-                    "{}{}\n{} messages | {}\n\n{}",
+                    "{}{}\n{} messages | {}{}{}\n\n{}",
                     session.display_name(),
                     branch_info,
                     session.message_count,
                     session.duration_str(),
+                    usage_info,
+                    meta_info,
                     session.preview_text
                 ),
             )
@@ -327,17 +1245,67 @@ impl Screen for BrowserScreen {
                 Span::styled("F4", Style::default().fg(self.theme.color8)),
                 Span::styled(" Terminal  ", Style::default().fg(self.theme.color7)),
                 Span::styled("F5", Style::default().fg(self.theme.color8)),
-                Span::styled(" Editor", Style::default().fg(self.theme.color7)),
+                Span::styled(" Editor  ", Style::default().fg(self.theme.color7)),
+                Span::styled("F6", Style::default().fg(self.theme.color8)),
+                Span::styled(" Search  ", Style::default().fg(self.theme.color7)),
+                Span::styled("F7", Style::default().fg(self.theme.color8)),
+                Span::styled(" Usage  ", Style::default().fg(self.theme.color7)),
+                Span::styled("F8", Style::default().fg(self.theme.color8)),
+                Span::styled(" Cleanup  ", Style::default().fg(self.theme.color7)),
+                Span::styled("F9", Style::default().fg(self.theme.color8)),
+                Span::styled(" Tools  ", Style::default().fg(self.theme.color7)),
+                Span::styled("F10", Style::default().fg(self.theme.color8)),
+                Span::styled(" History", Style::default().fg(self.theme.color7)),
             ]));
             ansi_text.lines.push(Line::from(vec![
                 Span::raw("     "),
                 Span::styled("Enter", Style::default().fg(self.theme.color8)),
                 Span::styled(" Resume  ", Style::default().fg(self.theme.color7)),
+                Span::styled("m", Style::default().fg(self.theme.color8)),
+                Span::styled(" Resume in…  ", Style::default().fg(self.theme.color7)),
+                Span::styled("v", Style::default().fg(self.theme.color8)),
+                Span::styled(" View  ", Style::default().fg(self.theme.color7)),
+                Span::styled("d", Style::default().fg(self.theme.color8)),
+                Span::styled(" Changes  ", Style::default().fg(self.theme.color7)),
+                Span::styled("x/X", Style::default().fg(self.theme.color8)),
+                Span::styled(" Export  ", Style::default().fg(self.theme.color7)),
+                Span::styled("y", Style::default().fg(self.theme.color8)),
+                Span::styled(" Copy  ", Style::default().fg(self.theme.color7)),
                 Span::styled("Ins", Style::default().fg(self.theme.color8)),
                 Span::styled(" New  ", Style::default().fg(self.theme.color7)),
                 Span::styled("Esc", Style::default().fg(self.theme.color8)),
                 Span::styled(" Quit", Style::default().fg(self.theme.color7)),
             ]));
+            ansi_text.lines.push(Line::from(vec![
+                Span::raw("     "),
+                Span::styled("Space", Style::default().fg(self.theme.color8)),
+                Span::styled(" Sidechains  ", Style::default().fg(self.theme.color7)),
+                Span::styled("L", Style::default().fg(self.theme.color8)),
+                Span::styled(" Relink  ", Style::default().fg(self.theme.color7)),
+                Span::styled("/", Style::default().fg(self.theme.color8)),
+                Span::styled(" Filter  ", Style::default().fg(self.theme.color7)),
+                Span::styled("s/S", Style::default().fg(self.theme.color8)),
+                Span::styled(" Sort/Group  ", Style::default().fg(self.theme.color7)),
+                Span::styled("Y", Style::default().fg(self.theme.color8)),
+                Span::styled(" Copy ID  ", Style::default().fg(self.theme.color7)),
+                Span::styled("c", Style::default().fg(self.theme.color8)),
+                Span::styled(" Copy path", Style::default().fg(self.theme.color7)),
+            ]));
+            ansi_text.lines.push(Line::from(vec![
+                Span::raw("     "),
+                Span::styled("p", Style::default().fg(self.theme.color8)),
+                Span::styled(" Pin  ", Style::default().fg(self.theme.color7)),
+                Span::styled("a", Style::default().fg(self.theme.color8)),
+                Span::styled(" Archive  ", Style::default().fg(self.theme.color7)),
+                Span::styled("A", Style::default().fg(self.theme.color8)),
+                Span::styled(" Show archived  ", Style::default().fg(self.theme.color7)),
+                Span::styled("r", Style::default().fg(self.theme.color8)),
+                Span::styled(" Rename  ", Style::default().fg(self.theme.color7)),
+                Span::styled("t", Style::default().fg(self.theme.color8)),
+                Span::styled(" Tags  ", Style::default().fg(self.theme.color7)),
+                Span::styled("N", Style::default().fg(self.theme.color8)),
+                Span::styled(" Note", Style::default().fg(self.theme.color7)),
+            ]));
 
             let preview = Paragraph::new(ansi_text).block(preview_block);
 
@@ -359,37 +1327,92 @@ impl Screen for BrowserScreen {
 
         // Sessions pane (bottom right) - only show when visible
         if self.sessions_visible {
+            let (sessions_area, session_filter_area) = split_filter_area(
+                right_chunks[1],
+                matches!(self.filter_input, Some((Focus::Sessions, _))),
+            );
+
             let session_items: Vec<ListItem> = self
                 .current_sessions
                 .iter()
                 .map(|s| {
                     let date = s.last_message.format(&self.config.display.date_format);
 
-                    // Use preview text instead of session name
-                    let preview = if s.preview_text.is_empty() {
-                        s.display_name()
+                    // Sidechains aren't filtered themselves, so only parents get highlights
+                    let mut positions = match (s.is_agent, match_session(&self.session_query, s)) {
+                        (false, Some(m)) => m.positions,
+                        _ => Vec::new(),
+                    };
+
+                    // Use preview text instead of session name, unless the user named it
+                    let (preview, positions) = if s.meta.title.is_some() || s.preview_text.is_empty() {
+                        (s.display_name(), positions.get_mut(1).map(std::mem::take).unwrap_or_default())
                     } else {
+                        let mut preview_positions = positions.first_mut().map(std::mem::take).unwrap_or_default();
                         // Truncate to fit in list (by chars, not bytes, to avoid UTF-8 panic)
                         let chars: Vec<char> = s.preview_text.chars().collect();
                         if chars.len() > 60 {
-                            format!("{}...", chars[..60].iter().collect::<String>())
+                            preview_positions.retain(|&i| i < 60);
+                            (format!("{}...", chars[..60].iter().collect::<String>()), preview_positions)
                         } else {
-                            s.preview_text.clone()
+                            (s.preview_text.clone(), preview_positions)
                         }
                     };
 
-                    // Show agent sessions differently
+                    // Show agent and archived sessions differently
                     let text_style = if s.is_agent {
                         Style::default().fg(self.theme.color5)
+                    } else if s.meta.archived {
+                        Style::default().fg(self.theme.color8)
+                    } else if s.meta.title.is_some() {
+                        Style::default().fg(self.theme.foreground).add_modifier(Modifier::BOLD)
                     } else {
                         Style::default().fg(self.theme.foreground)
                     };
 
-                    ListItem::new(Line::from(vec![
+                    let totals = s.usage_totals();
+                    let cost = if totals.is_empty() {
+                        String::new()
+                    } else {
+                        format_cost(totals.cost(&self.config))
+                    };
+
+                    // Tree marker: sidechains are indented under their parent
+                    let sidechain_count = self.tree_sidechains(&s.id).len();
+                    let marker = if s.is_agent {
+                        "  └ "
+                    } else if sidechain_count == 0 {
+                        "  "
+                    } else if self.expanded.contains(&s.id) {
+                        "▾ "
+                    } else {
+                        "▸ "
+                    };
+
+                    let mut spans = vec![
                         Span::styled(date.to_string(), Style::default().fg(self.theme.color8)),
-                        Span::raw("  "),
-                        Span::styled(preview, text_style),
-                    ]))
+                        Span::styled(if s.meta.pinned { " ★" } else { "  " }, Style::default().fg(self.theme.color3)),
+                        Span::styled(format!("{:>8} ", cost), Style::default().fg(self.theme.color3)),
+                        Span::styled(marker, Style::default().fg(self.theme.color5)),
+                    ];
+                    spans.extend(highlight_spans(&preview, &positions, text_style, match_style));
+                    for tag in &s.meta.tags {
+                        spans.push(Span::styled(format!(" #{}", tag), Style::default().fg(self.theme.color4)));
+                    }
+                    if s.meta.archived {
+                        spans.push(Span::styled(" archived", Style::default().fg(self.theme.color8)));
+                    }
+                    if sidechain_count > 0 {
+                        spans.push(Span::styled(
+                            format!(
+                                " [{} agent{}]",
+                                sidechain_count,
+                                if sidechain_count == 1 { "" } else { "s" }
+                            ),
+                            Style::default().fg(self.theme.color5),
+                        ));
+                    }
+                    ListItem::new(Line::from(spans))
                 })
                 .collect();
 
@@ -401,9 +1424,18 @@ impl Screen for BrowserScreen {
             let sessions_block = Block::default()
                 .borders(Borders::ALL)
                 .title(format!(
-                    "Sessions - {} ({})",
+                    "Sessions - {} {}{}",
                     project_name,
-                    self.current_sessions.len()
+                    filter_title(
+                        &self.session_query,
+                        self.current_sessions.iter().filter(|s| !s.is_agent).count(),
+                        self.selected_project().map_or(0, |p| p.session_count),
+                    ),
+                    // Sessions have no session count to sort by, so they stay by recency
+                    order_title(
+                        if self.sort == SortMode::SessionCount { SortMode::Recency } else { self.sort },
+                        ProjectGrouping::None,
+                    ),
                 ))
                 .border_style(if self.focus == Focus::Sessions {
                     Style::default().fg(self.theme.color6)
@@ -424,22 +1456,37 @@ impl Screen for BrowserScreen {
             if self.focus == Focus::Sessions {
                 let garnished =
                     GarnishableStatefulWidget::garnish(sessions_list, HalfShadow::default());
-                f.render_stateful_widget(garnished, right_chunks[1], &mut self.session_state);
+                f.render_stateful_widget(garnished, sessions_area, &mut self.session_state);
             } else {
-                f.render_stateful_widget(sessions_list, right_chunks[1], &mut self.session_state);
+                f.render_stateful_widget(sessions_list, sessions_area, &mut self.session_state);
+            }
+            if let (Some(area), Some((_, input))) = (session_filter_area, &self.filter_input) {
+                f.render_widget(input, area);
             }
         }
-    }
 
-    async fn handle_key(&mut self, key: KeyEvent) {
-        // This is called from App, which expects Option<String> for status messages
-        // But the trait requires no return. We'll handle this differently.
+        self.draw_launch_picker(f, area);
+        self.draw_relink_prompt(f, area);
+        self.draw_meta_prompt(f, area);
     }
-}
 
-impl BrowserScreen {
-    /// Handle key event and return action.
-    pub async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction {
+    async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction {
+        if self.search.is_some() {
+            return self.handle_search_key(key);
+        }
+        if self.launch_picker.is_some() {
+            return self.handle_launch_picker_key(key);
+        }
+        if self.relink_input.is_some() {
+            return self.handle_relink_key(key);
+        }
+        if self.meta_input.is_some() {
+            return self.handle_meta_key(key);
+        }
+        if self.filter_input.is_some() {
+            return self.handle_filter_key(key);
+        }
+
         match key.code {
             KeyCode::Up => {
                 self.move_up();
@@ -451,8 +1498,14 @@ impl BrowserScreen {
             }
             KeyCode::Left => {
                 if self.focus == Focus::Sessions {
-                    self.focus = Focus::Projects;
-                    self.sessions_visible = false;
+                    // Collapse the sidechain tree first, then leave the pane
+                    let collapsed = self
+                        .selected_tree_parent()
+                        .is_some_and(|parent_id| self.set_expanded(parent_id, false));
+                    if !collapsed {
+                        self.focus = Focus::Projects;
+                        self.sessions_visible = false;
+                    }
                 }
                 ScreenAction::None
             }
@@ -460,6 +1513,17 @@ impl BrowserScreen {
                 if self.focus == Focus::Projects && !self.current_sessions.is_empty() {
                     self.sessions_visible = true;
                     self.focus = Focus::Sessions;
+                } else if self.focus == Focus::Sessions {
+                    if let Some(parent_id) = self.selected_tree_parent() {
+                        self.set_expanded(parent_id, true);
+                    }
+                }
+                ScreenAction::None
+            }
+            KeyCode::Char(' ') if self.focus == Focus::Sessions => {
+                if let Some(parent_id) = self.selected_tree_parent() {
+                    let expand = !self.expanded.contains(&parent_id);
+                    self.set_expanded(parent_id, expand);
                 }
                 ScreenAction::None
             }
@@ -490,11 +1554,17 @@ impl BrowserScreen {
                         ScreenAction::None
                     }
                     Focus::Sessions => {
-                        if let Some(session) = self.selected_session() {
-                            ScreenAction::LaunchSession {
+                        if let Some(session) = self.selected_session().filter(|s| s.is_agent) {
+                            // Sidechains can't be resumed; read them instead
+                            ScreenAction::OpenTranscript {
+                                session: Box::new(session.clone()),
+                            }
+                        } else if let Some(session) = self.selected_session() {
+                            unless_missing(ScreenAction::LaunchSession {
                                 session_id: session.id.clone(),
                                 project_path: session.project_path.clone(),
-                            }
+                                mode: None,
+                            })
                         } else {
                             ScreenAction::None
                         }
@@ -504,9 +1574,9 @@ impl BrowserScreen {
             KeyCode::Insert => {
                 // Start a new session in the selected project
                 if let Some(project) = self.selected_project() {
-                    ScreenAction::NewSession {
+                    unless_missing(ScreenAction::NewSession {
                         project_path: project.decoded_path.clone(),
-                    }
+                    })
                 } else {
                     ScreenAction::None
                 }
@@ -546,11 +1616,160 @@ impl BrowserScreen {
                 if let Some(project) = self.selected_project() {
                     ScreenAction::OpenEditor {
                         project_path: project.decoded_path.clone(),
+                        file: None,
+                        line: None,
                     }
                 } else {
                     ScreenAction::None
                 }
             }
+            KeyCode::F(6) => {
+                self.open_search();
+                ScreenAction::None
+            }
+            KeyCode::F(7) => ScreenAction::OpenUsage,
+            KeyCode::F(8) => ScreenAction::OpenCleanup,
+            KeyCode::F(10) => ScreenAction::OpenHistory,
+            KeyCode::F(9) => {
+                // Scope to the selected session when the session list is focused
+                let session = self.selected_session().filter(|_| self.focus == Focus::Sessions);
+                ScreenAction::OpenToolStats {
+                    session_id: session.map(|s| s.id.clone()),
+                    project_path: session
+                        .map(|s| s.project_path.clone())
+                        .or_else(|| self.selected_project().map(|p| p.decoded_path.clone())),
+                }
+            }
+            KeyCode::Char('/') => {
+                self.open_filter();
+                ScreenAction::None
+            }
+            KeyCode::Char('s') => {
+                self.sort = self.sort.next();
+                self.reorder();
+                ScreenAction::StatusMessage(format!("Sort by {}", self.sort.label()))
+            }
+            KeyCode::Char('S') => {
+                self.grouping = self.grouping.next();
+                // Cycling back round to repo grouping picks up new checkouts
+                self.repo_roots.clear();
+                self.reorder();
+                ScreenAction::StatusMessage(format!("Group projects: {}", self.grouping.label()))
+            }
+            KeyCode::Esc => {
+                // Clear the focused pane's filter first, then the other one
+                let focused_query = match self.focus {
+                    Focus::Projects => &self.project_query,
+                    Focus::Sessions => &self.session_query,
+                };
+                let target = if !focused_query.is_empty() {
+                    self.focus
+                } else if !self.project_query.is_empty() {
+                    Focus::Projects
+                } else {
+                    Focus::Sessions
+                };
+                self.set_filter(target, String::new());
+                ScreenAction::None
+            }
+            KeyCode::Char('m') if self.focus == Focus::Sessions => {
+                // Pick where to resume, overriding the configured launch mode
+                if self.selected_session().is_some_and(|s| !s.is_agent) {
+                    let default = LaunchMode::ALL
+                        .iter()
+                        .position(|m| *m == self.config.launcher.mode)
+                        .unwrap_or(0);
+                    self.launch_picker = Some(default);
+                }
+                ScreenAction::None
+            }
+            KeyCode::Char('v') if self.focus == Focus::Sessions => {
+                // View the full transcript of the selected session
+                match self.selected_session() {
+                    Some(session) => ScreenAction::OpenTranscript {
+                        session: Box::new(session.clone()),
+                    },
+                    None => ScreenAction::None,
+                }
+            }
+            KeyCode::Char('d') if self.focus == Focus::Sessions => {
+                // Files the selected session touched
+                match self.selected_session() {
+                    Some(session) => ScreenAction::OpenChanges {
+                        session: Box::new(session.clone()),
+                    },
+                    None => ScreenAction::None,
+                }
+            }
+            KeyCode::Char('y') if self.focus == Focus::Sessions => {
+                // Sidechains can't be resumed on their own
+                match self.selected_session().filter(|s| !s.is_agent) {
+                    Some(session) => ScreenAction::CopyToClipboard {
+                        text: session.resume_command(self.config.claude.dangerously_skip_permissions),
+                        what: "resume command",
+                    },
+                    None => ScreenAction::None,
+                }
+            }
+            KeyCode::Char('Y') if self.focus == Focus::Sessions => match self.selected_session() {
+                Some(session) => ScreenAction::CopyToClipboard {
+                    text: session.id.clone(),
+                    what: "session ID",
+                },
+                None => ScreenAction::None,
+            },
+            KeyCode::Char('c') => match self.selected_project() {
+                Some(project) => ScreenAction::CopyToClipboard {
+                    text: project.decoded_path.clone(),
+                    what: "project path",
+                },
+                None => ScreenAction::None,
+            },
+            KeyCode::Char('p') if self.focus == Focus::Sessions => {
+                self.update_selected_meta(|meta| meta.pinned = !meta.pinned)
+            }
+            KeyCode::Char('a') if self.focus == Focus::Sessions => {
+                self.update_selected_meta(|meta| meta.archived = !meta.archived)
+            }
+            KeyCode::Char('A') => {
+                self.show_archived = !self.show_archived;
+                self.refresh_sessions();
+                ScreenAction::StatusMessage(if self.show_archived {
+                    "Showing archived sessions".to_string()
+                } else {
+                    "Hiding archived sessions".to_string()
+                })
+            }
+            KeyCode::Char('r') if self.focus == Focus::Sessions => {
+                self.open_meta_prompt(MetaField::Title);
+                ScreenAction::None
+            }
+            KeyCode::Char('t') if self.focus == Focus::Sessions => {
+                self.open_meta_prompt(MetaField::Tags);
+                ScreenAction::None
+            }
+            KeyCode::Char('N') if self.focus == Focus::Sessions => {
+                self.open_meta_prompt(MetaField::Note);
+                ScreenAction::None
+            }
+            KeyCode::Char('L') => {
+                // Relink the selected project to where its directory moved
+                if let Some(project) = self.selected_project() {
+                    self.relink_input = Some(project.decoded_path.clone());
+                }
+                ScreenAction::None
+            }
+            KeyCode::Char(c @ ('x' | 'X')) if self.focus == Focus::Sessions => {
+                // Export the selected session: x = Markdown, X = HTML
+                let format = if c == 'x' { ExportFormat::Markdown } else { ExportFormat::Html };
+                match self.selected_session() {
+                    Some(session) => match exporter::export_to_file(session, format, &ExportOptions::default()) {
+                        Ok(path) => ScreenAction::StatusMessage(format!("Exported to {}", path.display())),
+                        Err(e) => ScreenAction::StatusMessage(format!("Export failed: {}", e)),
+                    },
+                    None => ScreenAction::None,
+                }
+            }
             _ => ScreenAction::None,
         }
     }
@@ -0,0 +1,259 @@
+//! Changes screen — files a session touched and the edits it made.
+
+use async_trait::async_trait;
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::models::Session;
+use crate::services::file_changes::{self, ChangeKind, DiffTag, TouchedFile};
+use crate::services::{SessionStore, Theme};
+
+use super::{Screen, ScreenAction};
+
+/// Lines shown of a single written file before it is cut short.
+const MAX_WRITE_LINES: usize = 200;
+
+/// Changes screen state.
+pub struct ChangesScreen {
+    theme: Arc<Theme>,
+    config: Arc<Config>,
+    session: Option<Session>,
+    files: Vec<TouchedFile>,
+    list_state: ListState,
+    /// First visible line of the hunk pane
+    scroll: usize,
+    /// Last known height of the hunk pane (for paging)
+    page_height: usize,
+}
+
+impl ChangesScreen {
+    pub fn new(theme: Arc<Theme>, config: Arc<Config>) -> Self {
+        Self {
+            theme,
+            config,
+            session: None,
+            files: Vec::new(),
+            list_state: ListState::default(),
+            scroll: 0,
+            page_height: 20,
+        }
+    }
+
+    /// Load the files touched by a session from its JSONL.
+    pub fn open(&mut self, session: Session) -> anyhow::Result<()> {
+        let entries = SessionStore::load_entries(&session.file_path)?;
+        self.files = file_changes::files_touched(&entries);
+        self.session = Some(session);
+        self.list_state.select(if self.files.is_empty() { None } else { Some(0) });
+        self.scroll = 0;
+        Ok(())
+    }
+
+    fn select(&mut self, index: usize) {
+        if !self.files.is_empty() {
+            self.list_state.select(Some(index.min(self.files.len() - 1)));
+            self.scroll = 0;
+        }
+    }
+
+    fn selected_file(&self) -> Option<&TouchedFile> {
+        self.list_state.selected().and_then(|i| self.files.get(i))
+    }
+
+    fn project_path(&self) -> &str {
+        self.session.as_ref().map(|s| s.project_path.as_str()).unwrap_or_default()
+    }
+
+    /// Open the selected file in `$EDITOR`, at its latest edit when it can be found.
+    fn open_selected(&self) -> ScreenAction {
+        let Some(file) = self.selected_file() else {
+            return ScreenAction::None;
+        };
+        if !Path::new(&file.path).is_file() {
+            return ScreenAction::StatusMessage(format!("{} no longer exists", file.path));
+        }
+        let line = file
+            .changes
+            .iter()
+            .rev()
+            .filter(|c| c.kind != ChangeKind::Bash)
+            .find_map(|c| file_changes::find_line(&file.path, &c.new));
+        ScreenAction::OpenEditor {
+            project_path: self.project_path().to_string(),
+            file: Some(file.path.clone()),
+            line,
+        }
+    }
+
+    /// Render every change to the selected file.
+    fn hunk_lines(&self, file: &TouchedFile) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for change in &file.changes {
+            let time = change.timestamp.with_timezone(&Local).format(&self.config.display.date_format);
+            lines.push(Line::from(vec![
+                Span::styled(
+                    change.kind.label().to_string(),
+                    Style::default().fg(self.theme.color4).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  {}", time), Style::default().fg(self.theme.color8)),
+            ]));
+
+            if change.kind == ChangeKind::Bash {
+                for text in change.new.lines() {
+                    lines.push(Line::styled(format!("$ {}", text), Style::default().fg(self.theme.color7)));
+                }
+            } else {
+                let diff = file_changes::diff_lines(&change.old, &change.new);
+                let shown = if change.kind == ChangeKind::Edit { diff.len() } else { MAX_WRITE_LINES };
+                for line in diff.iter().take(shown) {
+                    let (prefix, color) = match line.tag {
+                        DiffTag::Context => (' ', self.theme.color8),
+                        DiffTag::Removed => ('-', self.theme.color1),
+                        DiffTag::Added => ('+', self.theme.color2),
+                    };
+                    lines.push(Line::styled(
+                        format!("{}{}", prefix, line.text.replace('\t', "    ")),
+                        Style::default().fg(color),
+                    ));
+                }
+                if diff.len() > shown {
+                    lines.push(Line::styled(
+                        format!("… {} more lines", diff.len() - shown),
+                        Style::default().fg(self.theme.color8),
+                    ));
+                }
+            }
+            lines.push(Line::raw(""));
+        }
+        lines
+    }
+}
+
+#[async_trait]
+impl Screen for ChangesScreen {
+    fn draw(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Files and hunks
+                Constraint::Length(2), // Help bar
+            ])
+            .split(area);
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(chunks[0]);
+
+        // File list
+        let project_path = self.project_path().to_string();
+        let items: Vec<ListItem> = self
+            .files
+            .iter()
+            .map(|file| {
+                let (added, removed) = file.line_counts();
+                let mut spans = vec![Span::styled(
+                    file.display_path(&project_path),
+                    Style::default().fg(if Path::new(&file.path).exists() {
+                        self.theme.foreground
+                    } else {
+                        self.theme.color8
+                    }),
+                )];
+                if added > 0 {
+                    spans.push(Span::styled(format!(" +{}", added), Style::default().fg(self.theme.color2)));
+                }
+                if removed > 0 {
+                    spans.push(Span::styled(format!(" -{}", removed), Style::default().fg(self.theme.color1)));
+                }
+                let counts: Vec<String> = [ChangeKind::Edit, ChangeKind::Write, ChangeKind::NotebookEdit, ChangeKind::Bash]
+                    .into_iter()
+                    .filter_map(|kind| match file.count(kind) {
+                        0 => None,
+                        n => Some(format!("{}×{}", n, kind.label())),
+                    })
+                    .collect();
+                spans.push(Span::styled(
+                    format!("  {}", counts.join(" ")),
+                    Style::default().fg(self.theme.color8),
+                ));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let title = match &self.session {
+            Some(s) => format!(" Files touched · {} ({}) ", s.display_name(), self.files.len()),
+            None => " Files touched ".to_string(),
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(self.theme.color6)),
+            )
+            .highlight_style(Style::default().bg(self.theme.color8).add_modifier(Modifier::BOLD))
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, panes[0], &mut self.list_state);
+
+        // Hunks of the selected file
+        let (title, lines) = match self.selected_file() {
+            Some(file) => (format!(" {} ", file.display_path(&project_path)), self.hunk_lines(file)),
+            None => (
+                " Edits ".to_string(),
+                vec![Line::styled(
+                    "This session made no file changes.",
+                    Style::default().fg(self.theme.color8),
+                )],
+            ),
+        };
+        self.page_height = panes[1].height.saturating_sub(2) as usize;
+        self.scroll = self.scroll.min(lines.len().saturating_sub(self.page_height));
+        let hunks = Paragraph::new(lines).scroll((self.scroll as u16, 0)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        f.render_widget(hunks, panes[1]);
+
+        let key = |k: &'static str| Span::styled(k, Style::default().fg(self.theme.color6));
+        let label = |l: &'static str| Span::styled(l, Style::default().fg(self.theme.color7));
+        let help = Paragraph::new(Line::from(vec![
+            Span::raw(" "),
+            key("↑↓"),
+            label(" File  "),
+            key("PgUp/PgDn"),
+            label(" Scroll  "),
+            key("Enter"),
+            label(" Open in $EDITOR  "),
+            key("Esc"),
+            label(" Back"),
+        ]));
+        f.render_widget(help, chunks[1]);
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let selected = self.list_state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc => return ScreenAction::BackToSessions,
+            KeyCode::Up | KeyCode::Char('k') => self.select(selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.select(selected + 1),
+            KeyCode::Home | KeyCode::Char('g') => self.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.select(self.files.len().saturating_sub(1)),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.page_height),
+            KeyCode::PageDown => self.scroll += self.page_height,
+            KeyCode::Enter | KeyCode::Char('o') => return self.open_selected(),
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
@@ -0,0 +1,462 @@
+//! Cleanup screen — archive old, large or empty sessions, and restore archives.
+
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::services::cleanup::{self, ArchiveInfo, Candidate, CleanupFilter, Protection};
+use crate::services::{SessionStore, Theme};
+
+use super::{Screen, ScreenAction};
+
+/// Age presets cycled with `1` (days).
+const AGE_PRESETS: [Option<i64>; 5] = [None, Some(30), Some(90), Some(180), Some(365)];
+/// Size presets cycled with `2` (bytes).
+const SIZE_PRESETS: [Option<u64>; 4] = [None, Some(1 << 20), Some(10 << 20), Some(50 << 20)];
+/// Message-count presets cycled with `3`.
+const MESSAGE_PRESETS: [Option<usize>; 4] = [None, Some(1), Some(5), Some(20)];
+
+/// Which list the screen shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    /// Sessions the filter would archive
+    Sessions,
+    /// Existing archives, for restore
+    Archives,
+}
+
+/// Cleanup screen state.
+pub struct CleanupScreen {
+    theme: Arc<Theme>,
+    config: Arc<Config>,
+    candidates: Vec<Candidate>,
+    /// Live-agent sessions as of opening; re-checked before archiving
+    protection: Protection,
+    filter: CleanupFilter,
+    view: View,
+    archives: Vec<ArchiveInfo>,
+    session_state: ListState,
+    archive_state: ListState,
+    /// Waiting for `y` to confirm archiving or restoring
+    confirm: bool,
+    /// Outcome of the last archive or restore
+    notice: String,
+}
+
+impl CleanupScreen {
+    pub fn new(theme: Arc<Theme>, config: Arc<Config>) -> Self {
+        Self {
+            theme,
+            config,
+            candidates: Vec::new(),
+            protection: Protection::default(),
+            filter: CleanupFilter {
+                older_than_days: Some(90),
+                ..CleanupFilter::default()
+            },
+            view: View::Sessions,
+            archives: Vec::new(),
+            session_state: ListState::default(),
+            archive_state: ListState::default(),
+            confirm: false,
+            notice: String::new(),
+        }
+    }
+
+    /// Load a snapshot of all sessions and the existing archives.
+    pub fn open(&mut self, store: &SessionStore, protection: Protection) {
+        let mut sessions: Vec<_> = store.all_sessions().collect();
+        sessions.sort_by_key(|s| s.last_message);
+        self.candidates = cleanup::candidates(store, sessions);
+        self.protection = protection;
+        self.view = View::Sessions;
+        self.confirm = false;
+        self.notice.clear();
+        self.reload_archives();
+        self.clamp_selection();
+    }
+
+    /// Archive the sessions the filter selects, re-checking live agents first.
+    ///
+    /// Returns a status line for the app.
+    pub fn archive(&mut self, protection: Protection) -> String {
+        self.protection = protection;
+        let plan = cleanup::plan(&self.candidates, &self.filter, &self.protection);
+        let (count, bytes) = (plan.selected.len(), plan.total_bytes());
+        let ids: Vec<String> = plan.selected.iter().map(|c| c.session.id.clone()).collect();
+
+        let projects_dir = self.config.claude_projects_dir();
+        let message = match cleanup::archive(&plan.selected, &self.protection, &projects_dir, &cleanup::archive_dir()) {
+            Ok(path) => {
+                self.candidates.retain(|c| !ids.contains(&c.session.id));
+                format!(
+                    "Archived {} session(s), freed {}, to {}",
+                    count,
+                    cleanup::format_size(bytes),
+                    path.display()
+                )
+            }
+            Err(e) => format!("Cleanup failed: {:#}", e),
+        };
+        self.reload_archives();
+        self.clamp_selection();
+        self.notice = message.clone();
+        message
+    }
+
+    /// Restore the selected archive into the projects directory.
+    fn restore_selected(&mut self) -> String {
+        let Some(archive) = self.archive_state.selected().and_then(|i| self.archives.get(i)) else {
+            return String::new();
+        };
+        let message = match cleanup::restore(&archive.path, &self.config.claude_projects_dir()) {
+            Ok(report) if report.skipped.is_empty() => {
+                format!("Restored {} file(s)", report.restored.len())
+            }
+            Ok(report) => format!(
+                "Restored {} file(s), skipped {} that already exist (archive kept)",
+                report.restored.len(),
+                report.skipped.len()
+            ),
+            Err(e) => format!("Restore failed: {:#}", e),
+        };
+        self.reload_archives();
+        self.clamp_selection();
+        self.notice = message.clone();
+        message
+    }
+
+    fn reload_archives(&mut self) {
+        self.archives = cleanup::list_archives().unwrap_or_else(|e| {
+            tracing::warn!("Failed to list archives: {}", e);
+            Vec::new()
+        });
+    }
+
+    /// Number of rows in the current view.
+    fn row_count(&self) -> usize {
+        match self.view {
+            View::Sessions => cleanup::plan(&self.candidates, &self.filter, &self.protection).selected.len(),
+            View::Archives => self.archives.len(),
+        }
+    }
+
+    fn list_state(&mut self) -> &mut ListState {
+        match self.view {
+            View::Sessions => &mut self.session_state,
+            View::Archives => &mut self.archive_state,
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        for view in [View::Sessions, View::Archives] {
+            let view = std::mem::replace(&mut self.view, view);
+            let len = self.row_count();
+            let state = self.list_state();
+            state.select(match state.selected() {
+                _ if len == 0 => None,
+                Some(i) => Some(i.min(len - 1)),
+                None => Some(0),
+            });
+            self.view = view;
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.row_count();
+        if len == 0 {
+            return;
+        }
+        let state = self.list_state();
+        let current = state.selected().unwrap_or(0);
+        state.select(Some(current.saturating_add_signed(delta).min(len - 1)));
+    }
+
+    /// Advance a criterion to its next preset.
+    fn cycle<T: Copy + PartialEq>(presets: &[Option<T>], current: Option<T>) -> Option<T> {
+        let index = presets.iter().position(|p| *p == current).unwrap_or(0);
+        presets[(index + 1) % presets.len()]
+    }
+
+    fn filter_changed(&mut self) {
+        self.session_state.select(Some(0));
+        self.clamp_selection();
+    }
+
+    /// "older than 90 days, ≥ 10.0 MB" description of the filter.
+    fn describe_filter(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(days) = self.filter.older_than_days {
+            parts.push(format!("older than {} days", days));
+        }
+        if let Some(bytes) = self.filter.min_bytes {
+            parts.push(format!("at least {}", cleanup::format_size(bytes)));
+        }
+        if let Some(max) = self.filter.max_messages {
+            parts.push(format!("at most {} messages", max));
+        }
+        if self.filter.empty_only {
+            parts.push("empty or aborted".to_string());
+        }
+        if parts.is_empty() {
+            "no criteria (pick at least one)".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    fn draw_sessions(&mut self, f: &mut Frame, area: Rect) {
+        let plan = cleanup::plan(&self.candidates, &self.filter, &self.protection);
+        let items: Vec<ListItem> = plan
+            .selected
+            .iter()
+            .map(|c| {
+                let session = &c.session;
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", session.last_message.format(&self.config.display.date_format)),
+                        Style::default().fg(self.theme.color8),
+                    ),
+                    Span::styled(
+                        format!("{:>9} ", cleanup::format_size(c.bytes)),
+                        Style::default().fg(self.theme.color3),
+                    ),
+                    Span::styled(
+                        format!("{:>5} msgs  ", session.message_count),
+                        Style::default().fg(self.theme.color7),
+                    ),
+                    Span::styled(
+                        format!("{}  ", session.project_path),
+                        Style::default().fg(self.theme.color4),
+                    ),
+                    Span::styled(
+                        session.preview_text.chars().take(60).collect::<String>(),
+                        Style::default().fg(self.theme.foreground),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let mut title = format!(
+            " {} session(s), {} reclaimable (dry run) ",
+            plan.selected.len(),
+            cleanup::format_size(plan.total_bytes())
+        );
+        if !plan.protected.is_empty() {
+            title.push_str(&format!("· {} in use by live agents, skipped ", plan.protected.len()));
+        }
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(self.theme.color6)),
+            )
+            .highlight_style(Style::default().bg(self.theme.color8).add_modifier(Modifier::BOLD))
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, area, &mut self.session_state);
+    }
+
+    fn draw_archives(&mut self, f: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .archives
+            .iter()
+            .map(|a| {
+                let name = a.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{}  ", a.created.format("%Y-%m-%d %H:%M")),
+                        Style::default().fg(self.theme.color8),
+                    ),
+                    Span::styled(
+                        format!("{:>9}  ", cleanup::format_size(a.bytes)),
+                        Style::default().fg(self.theme.color3),
+                    ),
+                    Span::styled(name, Style::default().fg(self.theme.foreground)),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Archives in {} ", cleanup::archive_dir().display()))
+                    .border_style(Style::default().fg(self.theme.color6)),
+            )
+            .highlight_style(Style::default().bg(self.theme.color8).add_modifier(Modifier::BOLD))
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, area, &mut self.archive_state);
+    }
+
+    fn draw_confirm(&self, f: &mut Frame, area: Rect) {
+        let question = match self.view {
+            View::Sessions => {
+                let plan = cleanup::plan(&self.candidates, &self.filter, &self.protection);
+                format!(
+                    " Move {} session(s) ({}) into an archive? ",
+                    plan.selected.len(),
+                    cleanup::format_size(plan.total_bytes())
+                )
+            }
+            View::Archives => " Restore the selected archive? ".to_string(),
+        };
+
+        let width = (question.chars().count() as u16 + 4).min(area.width);
+        let height = 4.min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+        let prompt = Paragraph::new(vec![
+            Line::from(Span::styled(question, Style::default().fg(self.theme.foreground))),
+            Line::from(Span::styled(" y confirm · any other key cancels", Style::default().fg(self.theme.color8))),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.color3)),
+        );
+        f.render_widget(prompt, popup_area);
+    }
+}
+
+#[async_trait]
+impl Screen for CleanupScreen {
+    fn draw(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4), // Filter and view tabs
+                Constraint::Min(0),    // Sessions or archives
+                Constraint::Length(2), // Help bar
+            ])
+            .split(area);
+
+        let mut tabs = vec![Span::raw(" ")];
+        for (view, label) in [(View::Sessions, "Archive sessions"), (View::Archives, "Restore")] {
+            let style = if view == self.view {
+                Style::default()
+                    .fg(self.theme.background)
+                    .bg(self.theme.color6)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.color7)
+            };
+            tabs.push(Span::styled(format!(" {} ", label), style));
+            tabs.push(Span::raw(" "));
+        }
+        let header = Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled(" Select: ", Style::default().fg(self.theme.color7)),
+                Span::styled(self.describe_filter(), Style::default().fg(self.theme.color3)),
+            ]),
+            Line::from(tabs),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Cleanup ")
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        f.render_widget(header, chunks[0]);
+
+        match self.view {
+            View::Sessions => self.draw_sessions(f, chunks[1]),
+            View::Archives => self.draw_archives(f, chunks[1]),
+        }
+
+        let key = |k: &'static str| Span::styled(k, Style::default().fg(self.theme.color6));
+        let label = |l: &'static str| Span::styled(l, Style::default().fg(self.theme.color7));
+        let help = match self.view {
+            View::Sessions => vec![
+                Span::raw(" "),
+                key("1"),
+                label(" Age  "),
+                key("2"),
+                label(" Size  "),
+                key("3"),
+                label(" Messages  "),
+                key("e"),
+                label(" Empty only  "),
+                key("Enter"),
+                label(" Archive  "),
+                key("←→"),
+                label(" Archives  "),
+                key("Esc"),
+                label(" Back"),
+            ],
+            View::Archives => vec![
+                Span::raw(" "),
+                key("↑↓"),
+                label(" Select  "),
+                key("Enter"),
+                label(" Restore  "),
+                key("←→"),
+                label(" Sessions  "),
+                key("Esc"),
+                label(" Back"),
+            ],
+        };
+        let notice = Line::from(Span::styled(format!(" {}", self.notice), Style::default().fg(self.theme.color3)));
+        f.render_widget(Paragraph::new(vec![Line::from(help), notice]), chunks[2]);
+
+        if self.confirm {
+            self.draw_confirm(f, area);
+        }
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction {
+        if self.confirm {
+            self.confirm = false;
+            return match (key.code, self.view) {
+                (KeyCode::Char('y'), View::Sessions) => ScreenAction::RunCleanup,
+                (KeyCode::Char('y'), View::Archives) => ScreenAction::StatusMessage(self.restore_selected()),
+                _ => ScreenAction::None,
+            };
+        }
+
+        match key.code {
+            KeyCode::Esc => return ScreenAction::BackToSessions,
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Left | KeyCode::Right => {
+                self.view = match self.view {
+                    View::Sessions => View::Archives,
+                    View::Archives => View::Sessions,
+                };
+            }
+            KeyCode::Enter => self.confirm = self.row_count() > 0,
+            KeyCode::Char('1') if self.view == View::Sessions => {
+                self.filter.older_than_days = Self::cycle(&AGE_PRESETS, self.filter.older_than_days);
+                self.filter_changed();
+            }
+            KeyCode::Char('2') if self.view == View::Sessions => {
+                self.filter.min_bytes = Self::cycle(&SIZE_PRESETS, self.filter.min_bytes);
+                self.filter_changed();
+            }
+            KeyCode::Char('3') if self.view == View::Sessions => {
+                self.filter.max_messages = Self::cycle(&MESSAGE_PRESETS, self.filter.max_messages);
+                self.filter_changed();
+            }
+            KeyCode::Char('e') if self.view == View::Sessions => {
+                self.filter.empty_only = !self.filter.empty_only;
+                self.filter_changed();
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
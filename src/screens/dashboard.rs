@@ -17,6 +17,7 @@ use ratatui::{
 use std::sync::Arc;
 
 use crate::models::agent::{Agent, AgentStatus};
+use crate::models::usage::format_tokens;
 use crate::services::Theme;
 
 /// Dashboard screen state.
@@ -190,7 +191,7 @@ impl DashboardScreen {
                                 AgentStatus::Starting => theme.color3,
                                 AgentStatus::Active => theme.color2,
                                 AgentStatus::Idle => theme.color3,
-                                AgentStatus::Complete => theme.color6,
+                                AgentStatus::Finished | AgentStatus::Complete => theme.color6,
                                 AgentStatus::Failed | AgentStatus::Killed => theme.color1,
                             };
 
@@ -273,7 +274,7 @@ impl DashboardScreen {
             AgentStatus::Starting => self.theme.color3,
             AgentStatus::Active => self.theme.color2,
             AgentStatus::Idle => self.theme.color3,
-            AgentStatus::Complete => self.theme.color6,
+            AgentStatus::Finished | AgentStatus::Complete => self.theme.color6,
             AgentStatus::Failed | AgentStatus::Killed => self.theme.color1,
         };
 
@@ -285,7 +286,7 @@ impl DashboardScreen {
 
         let last_action = agent.last_tool.as_deref().unwrap_or(match agent.status {
             AgentStatus::Starting => "booting...",
-            AgentStatus::Complete => "done",
+            AgentStatus::Finished | AgentStatus::Complete => "done",
             AgentStatus::Failed => "crashed",
             AgentStatus::Killed => "killed",
             _ => "waiting...",
@@ -317,10 +318,7 @@ impl DashboardScreen {
                 format!("    \"{}\"", truncate(&agent.task_prompt, 70)),
                 Style::default().fg(self.theme.color7),
             )]),
-            Line::from(vec![Span::styled(
-                format!("    Last: {}", last_action),
-                Style::default().fg(self.theme.color5),
-            )]),
+            self.last_line(agent, last_action),
         ])
         .block(
             Block::default()
@@ -330,6 +328,36 @@ impl DashboardScreen {
         f.render_widget(detail, area);
     }
 
+    /// The agent's last action, or for a finished agent what it produced.
+    fn last_line(&self, agent: &Agent, last_action: &str) -> Line<'static> {
+        let result = match &agent.result {
+            Some(result) if agent.status.is_done() => result,
+            _ => {
+                return Line::from(vec![Span::styled(
+                    format!("    Last: {}", last_action),
+                    Style::default().fg(self.theme.color5),
+                )])
+            }
+        };
+
+        let message = result.final_message.as_deref().unwrap_or("(no reply)");
+        let mut summary = format!(
+            "  {} file(s) · {} tokens",
+            result.files_changed.len(),
+            format_tokens(result.tokens.total())
+        );
+        if let Some(code) = result.exit_code {
+            summary.push_str(&format!(" · exit {}", code));
+        }
+        Line::from(vec![
+            Span::styled(
+                format!("    Result: {}", truncate(&message.replace('\n', " "), 60)),
+                Style::default().fg(self.theme.color5),
+            ),
+            Span::styled(summary, Style::default().fg(self.theme.color8)),
+        ])
+    }
+
     fn draw_help(&self, f: &mut Frame, area: Rect, empty: bool) {
         let help = if empty {
             Paragraph::new(Line::from(vec![
@@ -431,7 +459,7 @@ fn draw_alien(
             draw_alien_explosion(ctx, x, y, tick, color);
             return;
         }
-        AgentStatus::Complete => {
+        AgentStatus::Finished | AgentStatus::Complete => {
             draw_alien_saucer(ctx, x, y, tick, color);
             return;
         }
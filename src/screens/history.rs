@@ -0,0 +1,458 @@
+//! History screen — a year of activity as a calendar heatmap, a timeline of
+//! the selected day and the hours spent per project that week.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{
+        canvas::{Canvas, Line as CanvasLine},
+        Block, Borders, List, ListItem, ListState, Paragraph,
+    },
+    Frame,
+};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::models::activity::local_day_bounds;
+use crate::models::Session;
+use crate::services::Theme;
+use crate::utils::truncate;
+
+use super::{Screen, ScreenAction};
+
+/// Weeks shown in the heatmap, when there is room.
+const HEATMAP_WEEKS: i64 = 53;
+
+/// Width of the project labels on the day timeline.
+const TIMELINE_LABEL_WIDTH: f64 = 14.0;
+
+/// Heatmap glyphs, from no activity to the busiest days.
+const INTENSITY: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+/// History screen state.
+pub struct HistoryScreen {
+    theme: Arc<Theme>,
+    /// Top-level sessions (sidechains overlap their parents and are left out)
+    sessions: Vec<Session>,
+    /// Local day -> messages sent and received
+    day_counts: BTreeMap<NaiveDate, usize>,
+    today: NaiveDate,
+    selected_day: NaiveDate,
+    /// Sessions active on the selected day, by when they were first active
+    day_sessions: Vec<usize>,
+    list_state: ListState,
+}
+
+impl HistoryScreen {
+    pub fn new(theme: Arc<Theme>) -> Self {
+        let today = Local::now().date_naive();
+        Self {
+            theme,
+            sessions: Vec::new(),
+            day_counts: BTreeMap::new(),
+            today,
+            selected_day: today,
+            day_sessions: Vec::new(),
+            list_state: ListState::default(),
+        }
+    }
+
+    /// Build the history from every session, starting on today.
+    pub fn open(&mut self, sessions: Vec<Session>) {
+        self.sessions = sessions.into_iter().filter(|s| !s.is_agent).collect();
+        self.day_counts.clear();
+        for span in self.sessions.iter().flat_map(|s| &s.activity) {
+            *self.day_counts.entry(span.local_date()).or_default() += span.messages;
+        }
+        self.today = Local::now().date_naive();
+        self.select_day(self.today);
+    }
+
+    /// Earliest day the heatmap can show.
+    fn first_day(&self) -> NaiveDate {
+        self.today - Duration::days(365)
+    }
+
+    fn select_day(&mut self, day: NaiveDate) {
+        self.selected_day = day.clamp(self.first_day(), self.today);
+        let (from, to) = local_day_bounds(self.selected_day);
+
+        let mut active: Vec<(DateTime<Utc>, usize)> = self
+            .sessions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| {
+                let first = s.activity.iter().find(|span| span.intersects(from, to))?;
+                Some((first.start.max(from), i))
+            })
+            .collect();
+        active.sort();
+        self.day_sessions = active.into_iter().map(|(_, i)| i).collect();
+        self.list_state.select(if self.day_sessions.is_empty() { None } else { Some(0) });
+    }
+
+    /// Jump to the nearest earlier (or later) day with any activity.
+    fn select_active_day(&mut self, forward: bool) {
+        let next = if forward {
+            self.day_counts.range(self.selected_day + Duration::days(1)..).next()
+        } else {
+            self.day_counts.range(..self.selected_day).next_back()
+        };
+        if let Some((&day, _)) = next {
+            self.select_day(day);
+        }
+    }
+
+    fn selected_session(&self) -> Option<&Session> {
+        let index = self.day_sessions.get(self.list_state.selected()?)?;
+        self.sessions.get(*index)
+    }
+
+    /// Projects active on the selected day, in the order they were first worked on.
+    fn day_projects(&self) -> Vec<&str> {
+        let mut projects: Vec<&str> = Vec::new();
+        for &i in &self.day_sessions {
+            let path = self.sessions[i].project_path.as_str();
+            if !projects.contains(&path) {
+                projects.push(path);
+            }
+        }
+        projects
+    }
+
+    /// Time spent per project in the week (Monday to Sunday) of the selected day, longest first.
+    fn week_hours(&self) -> Vec<(&str, Duration)> {
+        let monday = self.selected_day - Duration::days(self.selected_day.weekday().num_days_from_monday() as i64);
+        let (from, _) = local_day_bounds(monday);
+        let (_, to) = local_day_bounds(monday + Duration::days(6));
+
+        let mut hours: BTreeMap<&str, Duration> = BTreeMap::new();
+        for session in &self.sessions {
+            let time = session
+                .activity
+                .iter()
+                .fold(Duration::zero(), |total, span| total + span.overlap(from, to));
+            if time > Duration::zero() {
+                *hours.entry(session.project_path.as_str()).or_insert_with(Duration::zero) += time;
+            }
+        }
+        let mut hours: Vec<_> = hours.into_iter().collect();
+        hours.sort_by_key(|(_, time)| Reverse(*time));
+        hours
+    }
+
+    fn draw_heatmap(&self, f: &mut Frame, area: Rect) {
+        let width = area.width.saturating_sub(2).max(2) as f64;
+        let weeks = (((width - 4.0) / 2.0) as i64).clamp(1, HEATMAP_WEEKS);
+        let this_monday = self.today - Duration::days(self.today.weekday().num_days_from_monday() as i64);
+        let first_monday = this_monday - Duration::weeks(weeks - 1);
+        let busiest = self
+            .day_counts
+            .range(first_monday..)
+            .map(|(_, n)| *n)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let year_total: usize = self.day_counts.range(self.first_day()..).map(|(_, n)| *n).sum();
+
+        let theme = &self.theme;
+        let canvas = Canvas::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" History · {} messages in the last year ", year_total))
+                    .border_style(Style::default().fg(theme.color6)),
+            )
+            .x_bounds([0.0, width - 1.0])
+            .y_bounds([0.0, 7.0])
+            .paint(|ctx| {
+                let dim = Style::default().fg(theme.color8);
+                for (row, label) in [(0, "Mon"), (2, "Wed"), (4, "Fri")] {
+                    ctx.print(0.0, 6.0 - row as f64, Line::styled(label, dim));
+                }
+
+                let mut last_month_x = f64::MIN;
+                for week in 0..weeks {
+                    let monday = first_monday + Duration::weeks(week);
+                    let x = 4.0 + week as f64 * 2.0;
+
+                    // Month names above the first week of each month
+                    if (week == 0 || monday.day() <= 7) && x - last_month_x >= 4.0 {
+                        ctx.print(x, 7.0, Line::styled(monday.format("%b").to_string(), dim));
+                        last_month_x = x;
+                    }
+
+                    for weekday in 0..7 {
+                        let day = monday + Duration::days(weekday);
+                        if day > self.today {
+                            break;
+                        }
+                        let count = self.day_counts.get(&day).copied().unwrap_or(0);
+                        let level = if count == 0 {
+                            0
+                        } else {
+                            1 + (count * 3 / busiest).min(3)
+                        };
+                        let style = if day == self.selected_day {
+                            Style::default().fg(theme.background).bg(theme.color3)
+                        } else if level == 0 {
+                            dim
+                        } else {
+                            Style::default().fg(theme.color2)
+                        };
+                        ctx.print(x, 6.0 - weekday as f64, Line::styled(INTENSITY[level], style));
+                    }
+                }
+            });
+        f.render_widget(canvas, area);
+    }
+
+    fn draw_timeline(&self, f: &mut Frame, area: Rect) {
+        let projects = self.day_projects();
+        let rows = projects.len().max(1) as f64;
+        let width = area.width.saturating_sub(2).max(TIMELINE_LABEL_WIDTH as u16 + 2) as f64;
+        let (from, to) = local_day_bounds(self.selected_day);
+        let hour_x = |t: DateTime<Utc>| {
+            let hours = (t.clamp(from, to) - from).num_seconds() as f64 / 3600.0;
+            TIMELINE_LABEL_WIDTH + hours / 24.0 * (width - 1.0 - TIMELINE_LABEL_WIDTH)
+        };
+        let selected = self.selected_session().map(|s| s.id.clone());
+
+        let total = self.day_sessions.iter().fold(Duration::zero(), |total, &i| {
+            self.sessions[i]
+                .activity
+                .iter()
+                .fold(total, |total, span| total + span.overlap(from, to))
+        });
+        let title = format!(
+            " {} · {} session(s) · {} ",
+            self.selected_day.format("%a %Y-%m-%d"),
+            self.day_sessions.len(),
+            format_hours(total)
+        );
+
+        let theme = &self.theme;
+        let canvas = Canvas::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(theme.color6)),
+            )
+            .marker(Marker::Block)
+            .x_bounds([0.0, width - 1.0])
+            .y_bounds([0.0, rows])
+            .paint(|ctx| {
+                let dim = Style::default().fg(theme.color8);
+                for (row, project) in projects.iter().enumerate() {
+                    let y = rows - row as f64;
+                    for &i in &self.day_sessions {
+                        let session = &self.sessions[i];
+                        if session.project_path != *project {
+                            continue;
+                        }
+                        let color = if selected.as_deref() == Some(session.id.as_str()) {
+                            theme.color3
+                        } else {
+                            theme.color4
+                        };
+                        for span in session.activity.iter().filter(|span| span.intersects(from, to)) {
+                            ctx.draw(&CanvasLine {
+                                x1: hour_x(span.start),
+                                y1: y,
+                                x2: hour_x(span.end),
+                                y2: y,
+                                color,
+                            });
+                        }
+                    }
+                }
+
+                for (row, project) in projects.iter().enumerate() {
+                    let name = project.rsplit('/').next().unwrap_or(project);
+                    let label = truncate(name, TIMELINE_LABEL_WIDTH as usize - 1);
+                    ctx.print(0.0, rows - row as f64, Line::styled(label, Style::default().fg(theme.foreground)));
+                }
+                if projects.is_empty() {
+                    ctx.print(TIMELINE_LABEL_WIDTH, 1.0, Line::styled("No activity", dim));
+                }
+
+                // Hour axis
+                let step = if width - TIMELINE_LABEL_WIDTH >= 60.0 { 3 } else { 6 };
+                for hour in (0..24).step_by(step) {
+                    let x = TIMELINE_LABEL_WIDTH + hour as f64 / 24.0 * (width - 1.0 - TIMELINE_LABEL_WIDTH);
+                    ctx.print(x, 0.0, Line::styled(format!("{:02}", hour), dim));
+                }
+            });
+        f.render_widget(canvas, area);
+    }
+
+    fn draw_sessions(&mut self, f: &mut Frame, area: Rect) {
+        let (from, to) = local_day_bounds(self.selected_day);
+        let items: Vec<ListItem> = self
+            .day_sessions
+            .iter()
+            .map(|&i| {
+                let session = &self.sessions[i];
+                let spans: Vec<_> = session.activity.iter().filter(|s| s.intersects(from, to)).collect();
+                let start = spans.first().map(|s| s.start.max(from)).unwrap_or(from);
+                let end = spans.last().map(|s| s.end.min(to)).unwrap_or(to);
+                let project = session.project_path.rsplit('/').next().unwrap_or(&session.project_path);
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{}–{} ",
+                            start.with_timezone(&Local).format("%H:%M"),
+                            end.with_timezone(&Local).format("%H:%M")
+                        ),
+                        Style::default().fg(self.theme.color3),
+                    ),
+                    Span::styled(format!("{:<14} ", truncate(project, 14)), Style::default().fg(self.theme.color4)),
+                    Span::styled(session.display_name(), Style::default().fg(self.theme.foreground)),
+                    Span::styled(format!("  {}", session.preview_text), Style::default().fg(self.theme.color8)),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Sessions ")
+                    .border_style(Style::default().fg(self.theme.color8)),
+            )
+            .highlight_style(Style::default().bg(self.theme.color8).add_modifier(Modifier::BOLD))
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn draw_week(&self, f: &mut Frame, area: Rect) {
+        let hours = self.week_hours();
+        let longest = hours.first().map(|(_, d)| d.num_minutes()).unwrap_or(0).max(1);
+        let total = hours.iter().fold(Duration::zero(), |total, (_, d)| total + *d);
+        let bar_width = (area.width as usize).saturating_sub(28).max(4);
+        let monday = self.selected_day - Duration::days(self.selected_day.weekday().num_days_from_monday() as i64);
+
+        let lines: Vec<Line> = if hours.is_empty() {
+            vec![Line::styled(" No activity this week", Style::default().fg(self.theme.color8))]
+        } else {
+            hours
+                .iter()
+                .map(|(project, time)| {
+                    let name = project.rsplit('/').next().unwrap_or(project);
+                    let len = (time.num_minutes() as f64 * bar_width as f64 / longest as f64).ceil() as usize;
+                    Line::from(vec![
+                        Span::styled(format!(" {:<14} ", truncate(name, 14)), Style::default().fg(self.theme.foreground)),
+                        Span::styled("█".repeat(len.max(1)), Style::default().fg(self.theme.color4)),
+                        Span::styled(format!(" {}", format_hours(*time)), Style::default().fg(self.theme.color7)),
+                    ])
+                })
+                .collect()
+        };
+        let week = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Week of {} · {} ", monday.format("%b %-d"), format_hours(total)))
+                .border_style(Style::default().fg(self.theme.color8)),
+        );
+        f.render_widget(week, area);
+    }
+}
+
+#[async_trait]
+impl Screen for HistoryScreen {
+    fn draw(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(10), // Heatmap
+                Constraint::Min(0),     // Day and week
+                Constraint::Length(2),  // Help bar
+            ])
+            .split(area);
+        self.draw_heatmap(f, chunks[0]);
+
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(62), Constraint::Percentage(38)])
+            .split(chunks[1]);
+        let timeline_rows = self.day_projects().len().max(1) as u16;
+        let day = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length((timeline_rows + 3).min(panes[0].height / 2).max(4)),
+                Constraint::Min(0),
+            ])
+            .split(panes[0]);
+        self.draw_timeline(f, day[0]);
+        self.draw_sessions(f, day[1]);
+        self.draw_week(f, panes[1]);
+
+        let key = |k: &'static str| Span::styled(k, Style::default().fg(self.theme.color6));
+        let label = |l: &'static str| Span::styled(l, Style::default().fg(self.theme.color7));
+        let help = Paragraph::new(Line::from(vec![
+            Span::raw(" "),
+            key("←→"),
+            label(" Day  "),
+            key("[ ]"),
+            label(" Week  "),
+            key("p/n"),
+            label(" Prev/next active day  "),
+            key("t"),
+            label(" Today  "),
+            key("↑↓"),
+            label(" Session  "),
+            key("Enter"),
+            label(" View transcript  "),
+            key("Esc"),
+            label(" Back"),
+        ]));
+        f.render_widget(help, chunks[2]);
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let rows = self.day_sessions.len();
+        match key.code {
+            KeyCode::Esc => return ScreenAction::BackToSessions,
+            KeyCode::Left | KeyCode::Char('h') => self.select_day(self.selected_day - Duration::days(1)),
+            KeyCode::Right | KeyCode::Char('l') => self.select_day(self.selected_day + Duration::days(1)),
+            KeyCode::Char('[') => self.select_day(self.selected_day - Duration::weeks(1)),
+            KeyCode::Char(']') => self.select_day(self.selected_day + Duration::weeks(1)),
+            KeyCode::Char('p') => self.select_active_day(false),
+            KeyCode::Char('n') => self.select_active_day(true),
+            KeyCode::Char('t') => self.select_day(self.today),
+            KeyCode::Up | KeyCode::Char('k') if rows > 0 => {
+                let i = self.list_state.selected().unwrap_or(0);
+                self.list_state.select(Some(i.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') if rows > 0 => {
+                let i = self.list_state.selected().unwrap_or(0);
+                self.list_state.select(Some((i + 1).min(rows - 1)));
+            }
+            KeyCode::Enter => {
+                if let Some(session) = self.selected_session() {
+                    return ScreenAction::OpenTranscript {
+                        session: Box::new(session.clone()),
+                    };
+                }
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+/// "3h 20m", "45m", "< 1m" or "0m".
+fn format_hours(time: Duration) -> String {
+    match (time.num_hours(), time.num_minutes() % 60) {
+        (0, 0) if time.is_zero() => "0m".to_string(),
+        (0, 0) => "< 1m".to_string(),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {:02}m", h, m),
+    }
+}
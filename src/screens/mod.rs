@@ -2,18 +2,34 @@
 
 pub mod agent_detail;
 pub mod browser;
+pub mod changes;
+pub mod cleanup;
 pub mod dashboard;
+pub mod history;
 pub mod spawn_wizard;
+pub mod tool_stats;
+pub mod transcript;
+pub mod usage;
 
 pub use agent_detail::AgentDetailScreen;
 pub use browser::BrowserScreen;
+pub use changes::ChangesScreen;
+pub use cleanup::CleanupScreen;
 pub use dashboard::DashboardScreen;
+pub use history::HistoryScreen;
 pub use spawn_wizard::SpawnWizard;
+pub use tool_stats::ToolStatsScreen;
+pub use transcript::TranscriptScreen;
+pub use usage::UsageScreen;
 
 use async_trait::async_trait;
 use crossterm::event::KeyEvent;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::Frame;
+use std::path::Path;
+
+use crate::config::LaunchMode;
+use crate::models::Session;
 
 /// Action returned by screen key handlers.
 #[derive(Debug)]
@@ -22,8 +38,32 @@ pub enum ScreenAction {
     None,
     /// Display a status message.
     StatusMessage(String),
+    /// Copy text to the clipboard; `what` names it in the status message.
+    CopyToClipboard { text: String, what: &'static str },
+    /// Open the full transcript of a session.
+    OpenTranscript { session: Box<Session> },
+    /// Open the files a session touched and the edits it made.
+    OpenChanges { session: Box<Session> },
+    /// Open the token usage dashboard.
+    OpenUsage,
+    /// Open tool call stats for a session, its project, or everything.
+    OpenToolStats {
+        session_id: Option<String>,
+        project_path: Option<String>,
+    },
+    /// Open the activity history.
+    OpenHistory,
+    /// Open the cleanup screen.
+    OpenCleanup,
+    /// Archive the sessions selected on the cleanup screen.
+    RunCleanup,
     /// Launch a Claude session with the given ID and project path.
-    LaunchSession { session_id: String, project_path: String },
+    /// `mode` overrides the configured launch mode for this launch.
+    LaunchSession {
+        session_id: String,
+        project_path: String,
+        mode: Option<LaunchMode>,
+    },
     /// Start a new Claude session in the given project path.
     NewSession { project_path: String },
     /// Open lazygit in the project directory.
@@ -32,8 +72,12 @@ pub enum ScreenAction {
     OpenGithub { project_path: String },
     /// Open terminal in the project directory.
     OpenTerminal { project_path: String },
-    /// Open editor in the project directory.
-    OpenEditor { project_path: String },
+    /// Open editor in the project directory, optionally at a file and line.
+    OpenEditor {
+        project_path: String,
+        file: Option<String>,
+        line: Option<usize>,
+    },
     /// Open the spawn wizard.
     OpenSpawnWizard,
     /// Kill an agent by index.
@@ -46,6 +90,8 @@ pub enum ScreenAction {
     AttachAgent { index: usize },
     /// Go back from detail to dashboard.
     BackToDashboard,
+    /// Go back from a session view (transcript, usage) to the browser.
+    BackToSessions,
 }
 
 /// Trait for screen implementations.
@@ -54,6 +100,40 @@ pub trait Screen {
     /// Draw the screen.
     fn draw(&mut self, f: &mut Frame, area: Rect);
 
-    /// Handle a key event.
-    async fn handle_key(&mut self, key: KeyEvent);
+    /// Handle a key event and return the action for the app to perform.
+    async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction;
+}
+
+/// Create a centered rectangle within the given area.
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+/// Turn a launch into a relink hint if its project directory is gone.
+pub fn unless_missing(action: ScreenAction) -> ScreenAction {
+    let project_path = match &action {
+        ScreenAction::LaunchSession { project_path, .. } | ScreenAction::NewSession { project_path } => project_path,
+        _ => return action,
+    };
+    if Path::new(project_path).is_dir() {
+        action
+    } else {
+        ScreenAction::StatusMessage(format!("{} no longer exists - press L to relink the project", project_path))
+    }
 }
@@ -10,10 +10,13 @@ use ratatui::{
 };
 use std::sync::Arc;
 
+use crate::models::agent::AgentBackend;
 use crate::models::agent_registry::AgentRegistry;
 use crate::models::Project;
 use crate::services::Theme;
 
+use super::centered_rect;
+
 /// Which step of the wizard we're on.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WizardStep {
@@ -31,6 +34,7 @@ pub struct SpawnRequest {
     pub agent_type: String,
     pub task_prompt: String,
     pub use_worktree: bool,
+    pub backend: AgentBackend,
 }
 
 /// Spawn wizard state.
@@ -50,8 +54,9 @@ pub struct SpawnWizard {
     // Step 3: Prompt input
     pub prompt_text: String,
 
-    // Step 4: Worktree toggle
+    // Step 4: Worktree and backend toggles
     pub use_worktree: bool,
+    pub backend: AgentBackend,
 }
 
 impl SpawnWizard {
@@ -66,11 +71,12 @@ impl SpawnWizard {
             type_state: ListState::default(),
             prompt_text: String::new(),
             use_worktree: true,
+            backend: AgentBackend::default(),
         }
     }
 
     /// Open the wizard with available projects and agent types.
-    pub fn open(&mut self, projects: Vec<Project>, registry: &AgentRegistry) {
+    pub fn open(&mut self, projects: Vec<Project>, registry: &AgentRegistry, backend: AgentBackend) {
         self.active = true;
         self.step = WizardStep::PickProject;
         self.projects = projects;
@@ -88,6 +94,7 @@ impl SpawnWizard {
         self.type_state.select(Some(0));
         self.prompt_text.clear();
         self.use_worktree = true;
+        self.backend = backend;
     }
 
     /// Close the wizard without spawning.
@@ -152,6 +159,7 @@ impl SpawnWizard {
                             agent_type,
                             task_prompt: self.prompt_text.clone(),
                             use_worktree: self.use_worktree,
+                            backend: self.backend,
                         })
                     }
                 }
@@ -199,6 +207,11 @@ impl SpawnWizard {
                     self.prompt_text.push(c);
                 } else if self.step == WizardStep::ToggleWorktree && (c == 'w' || c == ' ') {
                     self.use_worktree = !self.use_worktree;
+                } else if self.step == WizardStep::ToggleWorktree && c == 't' {
+                    self.backend = match self.backend {
+                        AgentBackend::Headless => AgentBackend::Tmux,
+                        AgentBackend::Tmux => AgentBackend::Headless,
+                    };
                 }
                 None
             }
@@ -357,6 +370,7 @@ impl SpawnWizard {
             .constraints([
                 Constraint::Length(2),
                 Constraint::Length(4),
+                Constraint::Length(3),
                 Constraint::Min(0),
             ])
             .split(area);
@@ -383,14 +397,31 @@ impl SpawnWizard {
         ]);
         f.render_widget(option, chunks[1]);
 
+        let tmux_toggle = if self.backend == AgentBackend::Tmux { "[x]" } else { "[ ]" };
+        let tmux_option = Paragraph::new(vec![
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled(tmux_toggle, Style::default().fg(self.theme.color6).add_modifier(Modifier::BOLD)),
+                Span::styled(" Run in tmux", Style::default().fg(self.theme.foreground)),
+            ]),
+            Line::from(vec![
+                Span::raw("      "),
+                Span::styled(
+                    "Interactive claude you can attach to, instead of headless with a log",
+                    Style::default().fg(self.theme.color8),
+                ),
+            ]),
+        ]);
+        f.render_widget(tmux_option, chunks[2]);
+
         let hint2 = Paragraph::new(vec![
             Line::raw(""),
             Line::styled(
-                " Press Space/w to toggle, Enter to continue",
+                " Press Space/w to toggle worktree, t for tmux, Enter to continue",
                 Style::default().fg(self.theme.color8),
             ),
         ]);
-        f.render_widget(hint2, chunks[2]);
+        f.render_widget(hint2, chunks[3]);
     }
 
     fn draw_confirm_step(&self, f: &mut Frame, area: Rect) {
@@ -430,6 +461,10 @@ impl SpawnWizard {
                     Style::default().fg(self.theme.foreground),
                 ),
             ]),
+            Line::from(vec![
+                Span::styled("  Backend:   ", Style::default().fg(self.theme.color8)),
+                Span::styled(self.backend.label(), Style::default().fg(self.theme.foreground)),
+            ]),
             Line::raw(""),
             Line::styled(
                 " Press Enter to spawn, Esc to go back",
@@ -440,24 +475,3 @@ impl SpawnWizard {
         f.render_widget(summary, area);
     }
 }
-
-/// Create a centered rectangle within the given area.
-fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(area);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
-}
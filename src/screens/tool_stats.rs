@@ -0,0 +1,391 @@
+//! Tool stats screen — tool calls, failures, latency and Bash commands.
+
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::sync::{mpsc, Arc};
+
+use crate::models::Session;
+use crate::services::tool_stats::{format_latency, ToolCounter, ToolStats};
+use crate::services::Theme;
+use crate::utils::truncate;
+
+use super::{Screen, ScreenAction};
+
+/// Days shown in the activity chart.
+const ACTIVITY_DAYS: usize = 14;
+
+/// Which sessions the stats cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Session,
+    Project,
+    All,
+}
+
+impl Scope {
+    const ALL: [Scope; 3] = [Scope::Session, Scope::Project, Scope::All];
+
+    fn label(self) -> &'static str {
+        match self {
+            Scope::Session => "Session",
+            Scope::Project => "Project",
+            Scope::All => "All sessions",
+        }
+    }
+}
+
+/// Tool stats screen state.
+pub struct ToolStatsScreen {
+    theme: Arc<Theme>,
+    /// Snapshot of every session, for the project and all-sessions scopes
+    sessions: Arc<Vec<Session>>,
+    session_id: Option<String>,
+    project_path: Option<String>,
+    scope: Scope,
+    stats: ToolStats,
+    /// Stats being read on the blocking pool, and how many sessions they cover
+    loading: Option<(usize, mpsc::Receiver<ToolStats>)>,
+    /// Selection in the failing-sessions list
+    list_state: ListState,
+}
+
+impl ToolStatsScreen {
+    pub fn new(theme: Arc<Theme>) -> Self {
+        Self {
+            theme,
+            sessions: Arc::new(Vec::new()),
+            session_id: None,
+            project_path: None,
+            scope: Scope::All,
+            stats: ToolStats::default(),
+            loading: None,
+            list_state: ListState::default(),
+        }
+    }
+
+    /// Show stats for the selected session, else its project, else everything.
+    pub fn open(&mut self, sessions: Vec<Session>, session_id: Option<String>, project_path: Option<String>) {
+        self.scope = if session_id.is_some() {
+            Scope::Session
+        } else if project_path.is_some() {
+            Scope::Project
+        } else {
+            Scope::All
+        };
+        self.sessions = Arc::new(sessions);
+        self.session_id = session_id;
+        self.project_path = project_path;
+        self.rebuild();
+    }
+
+    fn available(&self, scope: Scope) -> bool {
+        match scope {
+            Scope::Session => self.session_id.is_some(),
+            Scope::Project => self.project_path.is_some(),
+            Scope::All => true,
+        }
+    }
+
+    /// Step to the next (or previous) scope that has something to show.
+    fn cycle_scope(&mut self, forward: bool) {
+        let len = Scope::ALL.len();
+        let current = Scope::ALL.iter().position(|s| *s == self.scope).unwrap_or(0);
+        for step in 1..len {
+            let index = if forward { (current + step) % len } else { (current + len - step) % len };
+            if self.available(Scope::ALL[index]) {
+                self.scope = Scope::ALL[index];
+                self.rebuild();
+                return;
+            }
+        }
+    }
+
+    /// Re-read the sessions in scope.
+    ///
+    /// A single session is read right away; wider scopes can mean reading
+    /// every JSONL on disk, so they are read on the blocking pool and picked
+    /// up by `tick`.
+    fn rebuild(&mut self) {
+        let (scope, session_id, project_path) = (self.scope, self.session_id.clone(), self.project_path.clone());
+        let in_scope = move |s: &&Session| match scope {
+            Scope::Session => Some(&s.id) == session_id.as_ref(),
+            Scope::Project => Some(&s.project_path) == project_path.as_ref(),
+            Scope::All => true,
+        };
+
+        if scope == Scope::Session {
+            self.loading = None;
+            self.set_stats(ToolStats::for_sessions(self.sessions.iter().filter(in_scope)));
+            return;
+        }
+
+        let count = self.sessions.iter().filter(&in_scope).count();
+        let sessions = self.sessions.clone();
+        let (tx, rx) = mpsc::channel();
+        tokio::task::spawn_blocking(move || {
+            // The screen may have moved on to another scope meanwhile
+            let _ = tx.send(ToolStats::for_sessions(sessions.iter().filter(in_scope)));
+        });
+        self.loading = Some((count, rx));
+    }
+
+    /// Pick up stats read in the background, once they are ready.
+    pub fn tick(&mut self) {
+        let Some((_, rx)) = &self.loading else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(stats) => {
+                self.loading = None;
+                self.set_stats(stats);
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.loading = None,
+        }
+    }
+
+    fn set_stats(&mut self, stats: ToolStats) {
+        self.stats = stats;
+        let has_rows = !self.stats.thrashing_sessions().is_empty();
+        self.list_state.select(has_rows.then_some(0));
+    }
+
+    fn counter_line(&self, name: &str, counter: &ToolCounter, name_width: usize) -> Line<'static> {
+        let error_style = if counter.errors > 0 {
+            Style::default().fg(self.theme.color1)
+        } else {
+            Style::default().fg(self.theme.color8)
+        };
+        Line::from(vec![
+            Span::styled(
+                format!(" {:<width$} ", truncate(name, name_width), width = name_width),
+                Style::default().fg(self.theme.foreground),
+            ),
+            Span::styled(format!("{:>6} ", counter.calls), Style::default().fg(self.theme.color3)),
+            Span::styled(
+                format!("{:>5} {:>5.1}% ", counter.errors, counter.error_rate() * 100.0),
+                error_style,
+            ),
+            Span::styled(
+                format!(
+                    "{:>8} {:>8}",
+                    counter.mean_latency_ms().map(format_latency).unwrap_or_default(),
+                    if counter.latency_samples > 0 { format_latency(counter.max_latency_ms) } else { String::new() },
+                ),
+                Style::default().fg(self.theme.color7),
+            ),
+        ])
+    }
+
+    fn table(&self, title: &str, rows: Vec<(&str, &ToolCounter)>, name_width: usize) -> Paragraph<'static> {
+        let mut lines = vec![Line::styled(
+            format!(
+                " {:<width$} {:>6} {:>5} {:>6} {:>8} {:>8}",
+                "",
+                "calls",
+                "fail",
+                "rate",
+                "avg",
+                "max",
+                width = name_width
+            ),
+            Style::default().fg(self.theme.color8),
+        )];
+        lines.extend(rows.into_iter().map(|(name, counter)| self.counter_line(name, counter, name_width)));
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string())
+                .border_style(Style::default().fg(self.theme.color6)),
+        )
+    }
+
+    fn selected_session(&self) -> Option<&Session> {
+        let summary = self.list_state.selected().and_then(|i| self.stats.thrashing_sessions().get(i).copied())?;
+        self.sessions.iter().find(|s| s.id == summary.session_id)
+    }
+}
+
+#[async_trait]
+impl Screen for ToolStatsScreen {
+    fn draw(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),      // Totals and scope tabs
+                Constraint::Percentage(50), // Tools and Bash commands
+                Constraint::Min(0),         // Activity and failing sessions
+                Constraint::Length(2),      // Help bar
+            ])
+            .split(area);
+
+        // Header: totals and the scope selector
+        let totals = self.stats.totals();
+        let mut tabs = vec![Span::raw(" ")];
+        for scope in Scope::ALL.into_iter().filter(|s| self.available(*s)) {
+            let style = if scope == self.scope {
+                Style::default()
+                    .fg(self.theme.background)
+                    .bg(self.theme.color6)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.color7)
+            };
+            tabs.push(Span::styled(format!(" {} ", scope.label()), style));
+            tabs.push(Span::raw(" "));
+        }
+        let summary = match &self.loading {
+            Some((count, _)) => Line::styled(
+                format!(" Reading {} session(s)…", count),
+                Style::default().fg(self.theme.color7),
+            ),
+            None => Line::from(vec![
+                Span::styled(
+                    format!(" {} calls ", totals.calls),
+                    Style::default().fg(self.theme.color3).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("· {} failed ({:.1}%) ", totals.errors, totals.error_rate() * 100.0),
+                    Style::default().fg(if totals.errors > 0 { self.theme.color1 } else { self.theme.color7 }),
+                ),
+                Span::styled(
+                    format!(
+                        "· avg latency {} · {} session(s)",
+                        totals.mean_latency_ms().map(format_latency).unwrap_or_else(|| "-".to_string()),
+                        self.stats.sessions.len()
+                    ),
+                    Style::default().fg(self.theme.color7),
+                ),
+            ]),
+        };
+        let header = Paragraph::new(vec![summary, Line::from(tabs)]).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Tool Stats ")
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        f.render_widget(header, chunks[0]);
+
+        let tables = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        f.render_widget(self.table(" By tool ", self.stats.tools_by_calls(), 16), tables[0]);
+        f.render_widget(self.table(" Bash commands ", self.stats.top_bash_commands(), 18), tables[1]);
+
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[2]);
+
+        // Calls per day, failures in red
+        let days: Vec<_> = self.stats.by_day.iter().rev().take(ACTIVITY_DAYS).collect();
+        let max_calls = days.iter().map(|(_, c)| c.calls).max().unwrap_or(0).max(1);
+        let bar_width = (bottom[0].width as usize).saturating_sub(26).max(4);
+        let activity: Vec<Line> = days
+            .into_iter()
+            .map(|(day, counter)| {
+                let scale = |n: usize| (n as f64 * bar_width as f64 / max_calls as f64).ceil() as usize;
+                let len = scale(counter.calls);
+                let failed = scale(counter.errors).min(len);
+                Line::from(vec![
+                    Span::styled(format!(" {} ", day.format("%m-%d")), Style::default().fg(self.theme.color7)),
+                    Span::styled("█".repeat(len - failed), Style::default().fg(self.theme.color4)),
+                    Span::styled("█".repeat(failed), Style::default().fg(self.theme.color1)),
+                    Span::styled(
+                        format!(" {} / {} failed", counter.calls, counter.errors),
+                        Style::default().fg(self.theme.color8),
+                    ),
+                ])
+            })
+            .collect();
+        f.render_widget(
+            Paragraph::new(activity).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Recent days ")
+                    .border_style(Style::default().fg(self.theme.color8)),
+            ),
+            bottom[0],
+        );
+
+        // Sessions with the most failed calls
+        let items: Vec<ListItem> = self
+            .stats
+            .thrashing_sessions()
+            .into_iter()
+            .map(|s| {
+                let rate = s.errors as f64 / s.calls.max(1) as f64 * 100.0;
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:>4} failed ", s.errors),
+                        Style::default().fg(self.theme.color1),
+                    ),
+                    Span::styled(
+                        format!("of {:<5} {:>5.1}%  ", s.calls, rate),
+                        Style::default().fg(self.theme.color8),
+                    ),
+                    Span::styled(s.name.clone(), Style::default().fg(self.theme.foreground)),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Sessions with failed calls ")
+                    .border_style(Style::default().fg(self.theme.color8)),
+            )
+            .highlight_style(Style::default().bg(self.theme.color8).add_modifier(Modifier::BOLD))
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, bottom[1], &mut self.list_state);
+
+        let key = |k: &'static str| Span::styled(k, Style::default().fg(self.theme.color6));
+        let label = |l: &'static str| Span::styled(l, Style::default().fg(self.theme.color7));
+        let help = Paragraph::new(Line::from(vec![
+            Span::raw(" "),
+            key("←→"),
+            label(" Session/Project/All  "),
+            key("↑↓"),
+            label(" Select  "),
+            key("Enter"),
+            label(" View transcript  "),
+            key("Esc"),
+            label(" Back"),
+        ]));
+        f.render_widget(help, chunks[3]);
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let rows = self.stats.thrashing_sessions().len();
+        match key.code {
+            KeyCode::Esc => return ScreenAction::BackToSessions,
+            KeyCode::Left | KeyCode::Char('h') => self.cycle_scope(false),
+            KeyCode::Right | KeyCode::Char('l') => self.cycle_scope(true),
+            KeyCode::Up | KeyCode::Char('k') if rows > 0 => {
+                let i = self.list_state.selected().unwrap_or(0);
+                self.list_state.select(Some(i.saturating_sub(1)));
+            }
+            KeyCode::Down | KeyCode::Char('j') if rows > 0 => {
+                let i = self.list_state.selected().unwrap_or(0);
+                self.list_state.select(Some((i + 1).min(rows - 1)));
+            }
+            KeyCode::Enter => {
+                if let Some(session) = self.selected_session() {
+                    return ScreenAction::OpenTranscript {
+                        session: Box::new(session.clone()),
+                    };
+                }
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
@@ -0,0 +1,752 @@
+//! Transcript screen — full read-only view of a session's conversation.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::event::SessionChange;
+use crate::models::message::tool_result_text;
+use crate::models::{ContentBlock, MessageContent, MessageEntry, Session};
+use crate::services::exporter::fence_for;
+use crate::services::{JsonlTailer, Theme};
+
+use super::{unless_missing, Screen, ScreenAction};
+
+/// Kind of a rendered transcript item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ItemKind {
+    User,
+    Assistant,
+    Thinking,
+    ToolUse,
+    ToolResult { is_error: bool },
+}
+
+impl ItemKind {
+    /// Tool calls, results and thinking can be folded away.
+    fn collapsible(self) -> bool {
+        !matches!(self, Self::User | Self::Assistant)
+    }
+}
+
+/// One block of the conversation (a message text, tool call, result, ...).
+#[derive(Debug, Clone)]
+struct TranscriptItem {
+    kind: ItemKind,
+    timestamp: DateTime<Utc>,
+    /// Short label, e.g. the tool name
+    title: String,
+    body: String,
+    collapsed: bool,
+}
+
+/// Rendered lines for a given width, plus where each item starts.
+struct RenderedTranscript {
+    width: u16,
+    lines: Vec<Line<'static>>,
+    item_starts: Vec<usize>,
+}
+
+/// Transcript screen state.
+pub struct TranscriptScreen {
+    theme: Arc<Theme>,
+    config: Arc<Config>,
+    session: Option<Session>,
+    items: Vec<TranscriptItem>,
+    /// Reads messages appended to the open session
+    tailer: Option<JsonlTailer>,
+    /// tool_use id -> tool name, so results can be labelled
+    tool_names: HashMap<String, String>,
+
+    /// Index of the selected item (the block cursor)
+    selected: usize,
+    /// First visible line
+    scroll: usize,
+    /// Keep the selected item in view on the next draw
+    follow_selection: bool,
+    /// Last known height of the transcript area (for paging)
+    page_height: usize,
+
+    /// Search input being typed (Some while the `/` prompt is open)
+    search_input: Option<String>,
+    /// Active search query (highlighted, navigated with n/N)
+    query: String,
+
+    layout: Option<RenderedTranscript>,
+}
+
+impl TranscriptScreen {
+    pub fn new(theme: Arc<Theme>, config: Arc<Config>) -> Self {
+        Self {
+            theme,
+            config,
+            session: None,
+            items: Vec::new(),
+            tailer: None,
+            tool_names: HashMap::new(),
+            selected: 0,
+            scroll: 0,
+            follow_selection: true,
+            page_height: 20,
+            search_input: None,
+            query: String::new(),
+            layout: None,
+        }
+    }
+
+    /// Load a session's full transcript from its JSONL.
+    pub fn open(&mut self, session: Session) -> anyhow::Result<()> {
+        let mut tailer = JsonlTailer::new(&session.file_path);
+        let entries = tailer.read_new()?.entries;
+        self.tool_names.clear();
+        self.items = build_items(&entries, &mut self.tool_names);
+        self.tailer = Some(tailer);
+        self.session = Some(session);
+        self.selected = 0;
+        self.scroll = 0;
+        self.follow_selection = true;
+        self.search_input = None;
+        self.query.clear();
+        self.layout = None;
+        Ok(())
+    }
+
+    /// Pick up messages written to the open session since it was loaded.
+    ///
+    /// When the last item was selected, the selection follows the new ones.
+    pub fn apply_session_change(&mut self, path: &Path, change: SessionChange) {
+        let Some(tailer) = self.tailer.as_mut().filter(|t| t.path() == path) else {
+            return;
+        };
+        if change == SessionChange::Deleted {
+            return;
+        }
+        let read = match tailer.read_new() {
+            Ok(read) => read,
+            Err(e) => {
+                tracing::warn!("Failed to read new messages from {:?}: {}", path, e);
+                return;
+            }
+        };
+        if read.truncated {
+            self.tool_names.clear();
+            self.items.clear();
+            self.selected = 0;
+        } else if read.entries.is_empty() {
+            return;
+        }
+
+        let following = self.selected + 1 >= self.items.len();
+        self.items.extend(build_items(&read.entries, &mut self.tool_names));
+        if following {
+            self.select(self.items.len().saturating_sub(1));
+        }
+        self.layout = None;
+    }
+
+    /// Whether the search prompt is capturing keystrokes.
+    pub fn is_capturing_input(&self) -> bool {
+        self.search_input.is_some()
+    }
+
+    fn select(&mut self, index: usize) {
+        if !self.items.is_empty() {
+            self.selected = index.min(self.items.len() - 1);
+            self.follow_selection = true;
+        }
+    }
+
+    /// Jump to the next (or previous) user turn.
+    fn jump_user_turn(&mut self, forward: bool) {
+        let found = if forward {
+            (self.selected + 1..self.items.len()).find(|&i| self.items[i].kind == ItemKind::User)
+        } else {
+            (0..self.selected).rev().find(|&i| self.items[i].kind == ItemKind::User)
+        };
+        if let Some(index) = found {
+            self.select(index);
+        }
+    }
+
+    /// Jump to the next (or previous) item matching the search query, wrapping around.
+    fn jump_match(&mut self, forward: bool) -> bool {
+        if self.query.is_empty() || self.items.is_empty() {
+            return false;
+        }
+        let query = self.query.to_lowercase();
+        let len = self.items.len();
+        for step in 1..=len {
+            let index = if forward {
+                (self.selected + step) % len
+            } else {
+                (self.selected + len - step % len) % len
+            };
+            let item = &self.items[index];
+            if item.title.to_lowercase().contains(&query) || item.body.to_lowercase().contains(&query) {
+                // Reveal the match if it's folded away
+                self.items[index].collapsed = false;
+                self.layout = None;
+                self.select(index);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn toggle_selected(&mut self) {
+        if let Some(item) = self.items.get_mut(self.selected) {
+            if item.kind.collapsible() {
+                item.collapsed = !item.collapsed;
+                self.layout = None;
+            }
+        }
+    }
+
+    /// The selected item as a Markdown excerpt, for the clipboard.
+    fn selected_markdown(&self) -> Option<String> {
+        let item = self.items.get(self.selected)?;
+        let time = item.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+        let body = item.body.trim_end();
+        let markdown = match item.kind {
+            ItemKind::User => format!("**User** · {}\n\n{}\n", time, body.trim()),
+            ItemKind::Assistant => format!("**Assistant** · {}\n\n{}\n", time, body.trim()),
+            ItemKind::Thinking => {
+                let quoted: Vec<String> = body.trim().lines().map(|l| format!("> {}", l)).collect();
+                format!("**Thinking** · {}\n\n{}\n", time, quoted.join("\n"))
+            }
+            ItemKind::ToolUse => {
+                let fence = fence_for(body);
+                format!("**Tool: {}** · {}\n\n{}json\n{}\n{}\n", item.title, time, fence, body, fence)
+            }
+            ItemKind::ToolResult { is_error } => {
+                let label = if is_error { "Error" } else { "Result" };
+                let fence = fence_for(body);
+                format!("**{}: {}** · {}\n\n{}\n{}\n{}\n", label, item.title, time, fence, body, fence)
+            }
+        };
+        Some(markdown)
+    }
+
+    /// Expand everything, or collapse everything if it's all expanded.
+    fn toggle_all(&mut self) {
+        let any_collapsed = self.items.iter().any(|i| i.collapsed);
+        for item in self.items.iter_mut().filter(|i| i.kind.collapsible()) {
+            item.collapsed = !any_collapsed;
+        }
+        self.layout = None;
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        self.follow_selection = false;
+        self.scroll = self.scroll.saturating_add_signed(delta);
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) -> ScreenAction {
+        let Some(input) = self.search_input.as_mut() else {
+            return ScreenAction::None;
+        };
+        match key.code {
+            KeyCode::Esc => self.search_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Enter => {
+                self.query = self.search_input.take().unwrap_or_default();
+                self.layout = None;
+                if !self.query.is_empty() && !self.jump_match(true) {
+                    return ScreenAction::StatusMessage(format!("No matches for \"{}\"", self.query));
+                }
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+
+    /// Render all items into lines for the given width.
+    fn build_layout(&self, width: u16) -> RenderedTranscript {
+        let body_width = (width as usize).saturating_sub(4).max(10);
+        let mut lines = Vec::new();
+        let mut item_starts = Vec::with_capacity(self.items.len());
+        let query = self.query.to_lowercase();
+        let match_style = Style::default()
+            .fg(self.theme.background)
+            .bg(self.theme.color3);
+
+        for item in &self.items {
+            item_starts.push(lines.len());
+
+            let (label, color) = match item.kind {
+                ItemKind::User => ("You".to_string(), self.theme.color6),
+                ItemKind::Assistant => ("Claude".to_string(), self.theme.color5),
+                ItemKind::Thinking => ("Thinking".to_string(), self.theme.color8),
+                ItemKind::ToolUse => (format!("Tool: {}", item.title), self.theme.color4),
+                ItemKind::ToolResult { is_error: false } => (format!("Result: {}", item.title), self.theme.color2),
+                ItemKind::ToolResult { is_error: true } => (format!("Error: {}", item.title), self.theme.color1),
+            };
+            let time = item.timestamp.with_timezone(&Local).format("%H:%M:%S").to_string();
+            let body_lines = wrap_text(&item.body, body_width);
+
+            let mut header = Vec::new();
+            if item.kind.collapsible() {
+                header.push(Span::styled(
+                    if item.collapsed { "▸ " } else { "▾ " },
+                    Style::default().fg(color),
+                ));
+            }
+            header.push(Span::styled(label, Style::default().fg(color).add_modifier(Modifier::BOLD)));
+            header.push(Span::styled(format!("  {}", time), Style::default().fg(self.theme.color8)));
+            if item.collapsed {
+                header.push(Span::styled(
+                    format!("  ({} lines)", body_lines.len()),
+                    Style::default().fg(self.theme.color8),
+                ));
+            }
+            lines.push(Line::from(header));
+
+            if !item.collapsed {
+                let body_style = match item.kind {
+                    ItemKind::ToolResult { is_error: true } => Style::default().fg(self.theme.color1),
+                    ItemKind::Thinking => Style::default()
+                        .fg(self.theme.color8)
+                        .add_modifier(Modifier::ITALIC),
+                    ItemKind::ToolUse | ItemKind::ToolResult { .. } => Style::default().fg(self.theme.color7),
+                    ItemKind::User | ItemKind::Assistant => Style::default().fg(self.theme.foreground),
+                };
+                for text in body_lines {
+                    let mut spans = vec![Span::raw("  ")];
+                    spans.extend(highlight_matches(&text, &query, body_style, match_style));
+                    lines.push(Line::from(spans));
+                }
+            }
+            lines.push(Line::raw(""));
+        }
+
+        RenderedTranscript { width, lines, item_starts }
+    }
+}
+
+#[async_trait]
+impl Screen for TranscriptScreen {
+    fn draw(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Session header
+                Constraint::Min(0),    // Transcript
+                Constraint::Length(2), // Help / search bar
+            ])
+            .split(area);
+
+        // Header
+        let (title, info) = match &self.session {
+            Some(s) => (
+                if s.is_agent {
+                    format!("{} (sidechain, read-only)", s.display_name())
+                } else {
+                    s.display_name()
+                },
+                format!(
+                    " {}  {}  {} messages  {}",
+                    s.project_path,
+                    s.first_message.with_timezone(&Local).format(&self.config.display.date_format),
+                    s.message_count,
+                    s.duration_str(),
+                ),
+            ),
+            None => ("Transcript".to_string(), String::new()),
+        };
+        let header = Paragraph::new(Line::from(vec![
+            Span::styled(info, Style::default().fg(self.theme.color8)),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", title))
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        f.render_widget(header, chunks[0]);
+
+        // Transcript body
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Transcript ({} blocks)", self.items.len()))
+            .border_style(Style::default().fg(self.theme.color8));
+        let inner = block.inner(chunks[1]);
+        f.render_widget(block, chunks[1]);
+
+        if self.layout.as_ref().map(|l| l.width) != Some(inner.width) {
+            self.layout = Some(self.build_layout(inner.width));
+        }
+        let height = inner.height as usize;
+        self.page_height = height.max(1);
+
+        if let Some(layout) = &self.layout {
+            let total = layout.lines.len();
+            if self.follow_selection {
+                if let Some(&start) = layout.item_starts.get(self.selected) {
+                    let end = layout
+                        .item_starts
+                        .get(self.selected + 1)
+                        .copied()
+                        .unwrap_or(total);
+                    if start < self.scroll || end - start > height {
+                        self.scroll = start;
+                    } else if end > self.scroll + height {
+                        self.scroll = end - height;
+                    }
+                }
+            }
+            self.scroll = self.scroll.min(total.saturating_sub(height));
+
+            let selected_start = layout.item_starts.get(self.selected).copied();
+            let visible: Vec<Line> = layout
+                .lines
+                .iter()
+                .enumerate()
+                .skip(self.scroll)
+                .take(height)
+                .map(|(i, line)| {
+                    if Some(i) == selected_start {
+                        let mut line = line.clone();
+                        line.spans.insert(0, Span::styled("► ", Style::default().fg(self.theme.color6)));
+                        line.style(Style::default().bg(self.theme.color0))
+                    } else {
+                        let mut line = line.clone();
+                        line.spans.insert(0, Span::raw("  "));
+                        line
+                    }
+                })
+                .collect();
+            f.render_widget(Paragraph::new(visible), inner);
+        }
+
+        // Help bar or search prompt
+        let help = if let Some(input) = &self.search_input {
+            Paragraph::new(Line::from(vec![
+                Span::styled(" /", Style::default().fg(self.theme.color6)),
+                Span::styled(input.clone(), Style::default().fg(self.theme.foreground)),
+                Span::styled("█", Style::default().fg(self.theme.color6)),
+            ]))
+        } else {
+            let key = |k: &'static str| Span::styled(k, Style::default().fg(self.theme.color6));
+            let label = |l: &'static str| Span::styled(l, Style::default().fg(self.theme.color7));
+            let read_only = self.session.as_ref().is_some_and(|s| s.is_agent);
+            let mut spans = vec![
+                Span::raw(" "),
+                key("↑↓"),
+                label(" Select  "),
+                key("PgUp/PgDn"),
+                label(" Scroll  "),
+                key("[ ]"),
+                label(" User turns  "),
+                key("Enter"),
+                label(" Fold  "),
+                key("e"),
+                label(" Fold all  "),
+                key("/"),
+                label(" Search  "),
+                key("n/N"),
+                label(" Next/prev  "),
+                key("y"),
+                label(" Copy  "),
+            ];
+            if !read_only {
+                spans.extend([key("r"), label(" Resume  ")]);
+            }
+            spans.extend([key("Esc"), label(" Back")]);
+            Paragraph::new(Line::from(spans))
+        };
+        f.render_widget(help, chunks[2]);
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction {
+        if self.search_input.is_some() {
+            return self.handle_search_key(key);
+        }
+
+        match key.code {
+            KeyCode::Esc => return ScreenAction::BackToSessions,
+            KeyCode::Up | KeyCode::Char('k') => self.select(self.selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.select(self.selected + 1),
+            KeyCode::Home | KeyCode::Char('g') => self.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.select(self.items.len().saturating_sub(1)),
+            KeyCode::PageUp => self.scroll_by(-(self.page_height as isize)),
+            KeyCode::PageDown => self.scroll_by(self.page_height as isize),
+            KeyCode::Char(']') => self.jump_user_turn(true),
+            KeyCode::Char('[') => self.jump_user_turn(false),
+            KeyCode::Enter | KeyCode::Char(' ') => self.toggle_selected(),
+            KeyCode::Char('e') => self.toggle_all(),
+            KeyCode::Char('/') => self.search_input = Some(String::new()),
+            KeyCode::Char('y') => {
+                if let Some(text) = self.selected_markdown() {
+                    return ScreenAction::CopyToClipboard { text, what: "message" };
+                }
+            }
+            KeyCode::Char('n') => {
+                self.jump_match(true);
+            }
+            KeyCode::Char('N') => {
+                self.jump_match(false);
+            }
+            KeyCode::Char('r') => {
+                // Sidechains can't be resumed on their own
+                if let Some(session) = self.session.as_ref().filter(|s| !s.is_agent) {
+                    return unless_missing(ScreenAction::LaunchSession {
+                        session_id: session.id.clone(),
+                        project_path: session.project_path.clone(),
+                        mode: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
+
+/// Flatten JSONL entries into displayable transcript items.
+fn build_items(entries: &[MessageEntry], tool_names: &mut HashMap<String, String>) -> Vec<TranscriptItem> {
+    let mut items = Vec::new();
+
+    for entry in entries {
+        let Some(message) = &entry.message else {
+            continue;
+        };
+        let role_kind = if message.role() == "assistant" {
+            ItemKind::Assistant
+        } else {
+            ItemKind::User
+        };
+        let mut push = |kind: ItemKind, title: String, body: String| {
+            if body.trim().is_empty() && kind != ItemKind::ToolUse {
+                return;
+            }
+            items.push(TranscriptItem {
+                kind,
+                timestamp: entry.timestamp,
+                title,
+                body,
+                collapsed: kind.collapsible(),
+            });
+        };
+
+        match message {
+            MessageContent::Simple { content, .. } => push(role_kind, String::new(), content.clone()),
+            MessageContent::Structured { content, .. } => {
+                for block in content {
+                    match block {
+                        ContentBlock::Text { text } => push(role_kind, String::new(), text.clone()),
+                        ContentBlock::Thinking { thinking } => {
+                            push(ItemKind::Thinking, String::new(), thinking.clone())
+                        }
+                        ContentBlock::ToolUse { id, name, input } => {
+                            tool_names.insert(id.clone(), name.clone());
+                            let body = serde_json::to_string_pretty(input).unwrap_or_default();
+                            push(ItemKind::ToolUse, name.clone(), body);
+                        }
+                        ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+                            let title = tool_names.get(tool_use_id).cloned().unwrap_or_default();
+                            push(
+                                ItemKind::ToolResult { is_error: *is_error },
+                                title,
+                                tool_result_text(content),
+                            );
+                        }
+                        ContentBlock::Unknown => {}
+                    }
+                }
+            }
+        }
+    }
+
+    items
+}
+
+/// Hard-wrap text to `width` characters, preferring to break at spaces.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    for raw in text.lines() {
+        let line: String = raw
+            .chars()
+            .map(|c| if c == '\t' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+        let chars: Vec<char> = line.chars().collect();
+        if chars.is_empty() {
+            out.push(String::new());
+            continue;
+        }
+        let mut start = 0;
+        while start < chars.len() {
+            let mut end = (start + width).min(chars.len());
+            if end < chars.len() {
+                if let Some(space) = chars[start..end].iter().rposition(|&c| c == ' ') {
+                    if space > 0 {
+                        end = start + space + 1;
+                    }
+                }
+            }
+            out.push(chars[start..end].iter().collect());
+            start = end;
+        }
+    }
+    out
+}
+
+/// Split a line into spans, highlighting case-insensitive occurrences of `query`.
+fn highlight_matches(text: &str, query: &str, base: Style, highlight: Style) -> Vec<Span<'static>> {
+    let lower = text.to_lowercase();
+    // Lowercasing can change byte lengths for some scripts; don't guess offsets then
+    if query.is_empty() || lower.len() != text.len() {
+        return vec![Span::styled(text.to_string(), base)];
+    }
+
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    while let Some(found) = lower[pos..].find(query) {
+        let start = pos + found;
+        let end = start + query.len();
+        if start > pos {
+            spans.push(Span::styled(text[pos..start].to_string(), base));
+        }
+        spans.push(Span::styled(text[start..end].to_string(), highlight));
+        pos = end;
+    }
+    if pos < text.len() {
+        spans.push(Span::styled(text[pos..].to_string(), base));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: ItemKind, body: &str) -> TranscriptItem {
+        TranscriptItem {
+            kind,
+            timestamp: Utc::now(),
+            title: String::new(),
+            body: body.to_string(),
+            collapsed: kind.collapsible(),
+        }
+    }
+
+    fn screen(items: Vec<TranscriptItem>, query: &str) -> TranscriptScreen {
+        let mut screen = TranscriptScreen::new(Arc::new(Theme::default()), Arc::new(Config::default()));
+        screen.items = items;
+        screen.query = query.to_string();
+        screen
+    }
+
+    #[test]
+    fn test_build_items_labels_results_and_folds_tools() {
+        let entries = [
+            MessageEntry::test(
+                "assistant",
+                "2026-01-01T10:00:00Z",
+                serde_json::json!([
+                    { "type": "text", "text": "Let me look." },
+                    { "type": "tool_use", "id": "t1", "name": "Read", "input": { "file_path": "a.rs" } },
+                ]),
+            ),
+            MessageEntry::test(
+                "user",
+                "2026-01-01T10:00:01Z",
+                serde_json::json!([{ "type": "tool_result", "tool_use_id": "t1", "content": "fn main() {}" }]),
+            ),
+            MessageEntry::test("user", "2026-01-01T10:00:02Z", serde_json::json!([{ "type": "text", "text": "  " }])),
+        ];
+
+        let items = build_items(&entries, &mut HashMap::new());
+        let kinds: Vec<ItemKind> = items.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            [ItemKind::Assistant, ItemKind::ToolUse, ItemKind::ToolResult { is_error: false }]
+        );
+        assert_eq!(items[2].title, "Read");
+        assert_eq!(items.iter().map(|i| i.collapsed).collect::<Vec<_>>(), [false, true, true]);
+    }
+
+    #[test]
+    fn test_wrap_text_multibyte() {
+        assert_eq!(wrap_text("héllo wörld ünïcode", 7), ["héllo ", "wörld ", "ünïcode"]);
+        assert_eq!(wrap_text("日本語のテキスト", 3), ["日本語", "のテキ", "スト"]);
+        assert_eq!(wrap_text("a\tb\n\nc", 10), ["a b", "", "c"]);
+    }
+
+    #[test]
+    fn test_highlight_matches_keeps_case_and_multibyte() {
+        let (base, highlight) = (Style::default(), Style::default().add_modifier(Modifier::BOLD));
+        let spans = highlight_matches("café Café", "café", base, highlight);
+        let parts: Vec<(&str, bool)> = spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style == highlight))
+            .collect();
+        assert_eq!(parts, [("café", true), (" ", false), ("Café", true)]);
+    }
+
+    #[test]
+    fn test_highlight_across_wrapped_lines() {
+        let screen = screen(
+            vec![item(ItemKind::Assistant, "Needle in the haystack, another needle here")],
+            "needle",
+        );
+        // Body wraps at 20 chars: "Needle in the ", "haystack, another ", "needle here"
+        let layout = screen.build_layout(24);
+        let highlight_bg = Theme::default().color3;
+        let matches: Vec<(usize, String)> = layout
+            .lines
+            .iter()
+            .enumerate()
+            .flat_map(|(i, line)| {
+                line.spans
+                    .iter()
+                    .filter(|s| s.style.bg == Some(highlight_bg))
+                    .map(move |s| (i, s.content.to_string()))
+            })
+            .collect();
+        assert_eq!(matches, [(1, "Needle".to_string()), (3, "needle".to_string())]);
+    }
+
+    #[test]
+    fn test_jump_match_wraps_around() {
+        let mut screen = screen(
+            vec![
+                item(ItemKind::User, "find the bug"),
+                item(ItemKind::Assistant, "looking"),
+                item(ItemKind::ToolUse, "grep FIND"),
+                item(ItemKind::Assistant, "found it, find done"),
+            ],
+            "find",
+        );
+
+        assert!(screen.jump_match(true));
+        assert_eq!(screen.selected, 2);
+        assert!(!screen.items[2].collapsed, "a folded match is revealed");
+        assert!(screen.jump_match(true));
+        assert_eq!(screen.selected, 3);
+        // n on the last match wraps to the first
+        assert!(screen.jump_match(true));
+        assert_eq!(screen.selected, 0);
+        // N on the first match wraps to the last
+        assert!(screen.jump_match(false));
+        assert_eq!(screen.selected, 3);
+
+        screen.query = "absent".to_string();
+        assert!(!screen.jump_match(true));
+        assert_eq!(screen.selected, 3);
+    }
+}
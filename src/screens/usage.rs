@@ -0,0 +1,362 @@
+//! Usage screen — token spend rolled up by day, week or month.
+
+use async_trait::async_trait;
+use chrono::{Datelike, Duration, NaiveDate};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::models::usage::{format_cost, format_tokens};
+use crate::models::{UsageRecord, UsageTotals};
+use crate::services::{SessionStore, Theme};
+
+use super::{Screen, ScreenAction};
+
+/// Rollup granularity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Period {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Period {
+    const ALL: [Period; 3] = [Period::Daily, Period::Weekly, Period::Monthly];
+
+    fn label(self) -> &'static str {
+        match self {
+            Period::Daily => "Daily",
+            Period::Weekly => "Weekly",
+            Period::Monthly => "Monthly",
+        }
+    }
+
+    /// First day of the period containing `date`.
+    fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Daily => date,
+            Period::Weekly => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Period::Monthly => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    fn format(self, start: NaiveDate) -> String {
+        match self {
+            Period::Daily => start.format("%Y-%m-%d %a").to_string(),
+            Period::Weekly => start.format("Week of %Y-%m-%d").to_string(),
+            Period::Monthly => start.format("%B %Y").to_string(),
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Period::Daily => Period::Weekly,
+            Period::Weekly => Period::Monthly,
+            Period::Monthly => Period::Daily,
+        }
+    }
+
+    fn prev(self) -> Self {
+        self.next().next()
+    }
+}
+
+/// Usage within one period.
+struct PeriodRow {
+    start: NaiveDate,
+    totals: UsageTotals,
+    /// Project path -> usage within the period
+    by_project: BTreeMap<String, UsageTotals>,
+}
+
+/// Usage screen state.
+pub struct UsageScreen {
+    theme: Arc<Theme>,
+    config: Arc<Config>,
+    /// (project path, record) for every session's usage
+    records: Vec<(String, UsageRecord)>,
+    all_time: UsageTotals,
+    period: Period,
+    rows: Vec<PeriodRow>,
+    list_state: ListState,
+}
+
+impl UsageScreen {
+    pub fn new(theme: Arc<Theme>, config: Arc<Config>) -> Self {
+        Self {
+            theme,
+            config,
+            records: Vec::new(),
+            all_time: UsageTotals::default(),
+            period: Period::Daily,
+            rows: Vec::new(),
+            list_state: ListState::default(),
+        }
+    }
+
+    /// Load usage from every session and its sidechains.
+    pub fn open(&mut self, store: &SessionStore) {
+        self.records = store
+            .all_sessions()
+            .flat_map(|s| std::iter::once(s).chain(store.sidechains_for(&s.id)))
+            .flat_map(|s| s.usage.iter().map(|r| (s.project_path.clone(), r.clone())))
+            .collect();
+        self.all_time = UsageTotals::from_records(self.records.iter().map(|(_, r)| r));
+        self.rebuild();
+    }
+
+    /// Recompute the period rows (newest first) for the current granularity.
+    fn rebuild(&mut self) {
+        let mut rows: BTreeMap<NaiveDate, PeriodRow> = BTreeMap::new();
+        for (project, record) in &self.records {
+            let start = self.period.start_of(record.date);
+            let row = rows.entry(start).or_insert_with(|| PeriodRow {
+                start,
+                totals: UsageTotals::default(),
+                by_project: BTreeMap::new(),
+            });
+            row.totals.add(&record.model, record.tokens);
+            row.by_project
+                .entry(project.clone())
+                .or_default()
+                .add(&record.model, record.tokens);
+        }
+        self.rows = rows.into_values().rev().collect();
+        self.list_state
+            .select(if self.rows.is_empty() { None } else { Some(0) });
+    }
+
+    fn set_period(&mut self, period: Period) {
+        self.period = period;
+        self.rebuild();
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(self.rows.len() - 1);
+        self.list_state.select(Some(next));
+    }
+
+    /// "12.3k in | 4.5k out | ..." summary of a set of totals.
+    fn tokens_summary(totals: &UsageTotals) -> String {
+        let tokens = totals.tokens();
+        format!(
+            "{} in | {} out | {} cache read | {} cache write",
+            format_tokens(tokens.input),
+            format_tokens(tokens.output),
+            format_tokens(tokens.cache_read),
+            format_tokens(tokens.cache_creation),
+        )
+    }
+
+    /// Breakdown list items, sorted by cost then tokens, most expensive first.
+    fn breakdown_items<'a>(
+        &self,
+        entries: impl Iterator<Item = (&'a str, UsageTotals)>,
+    ) -> Vec<ListItem<'static>> {
+        let mut entries: Vec<(&str, UsageTotals, f64)> = entries
+            .map(|(name, totals)| {
+                let cost = totals.cost(&self.config);
+                (name, totals, cost)
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.2.total_cmp(&a.2)
+                .then_with(|| b.1.tokens().total().cmp(&a.1.tokens().total()))
+        });
+
+        entries
+            .into_iter()
+            .map(|(name, totals, cost)| {
+                let priced = totals.unpriced_models(&self.config).next().is_none();
+                ListItem::new(vec![
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:>9}{} ", format_cost(cost), if priced { " " } else { "?" }),
+                            Style::default().fg(self.theme.color3),
+                        ),
+                        Span::styled(name.to_string(), Style::default().fg(self.theme.foreground)),
+                    ]),
+                    Line::from(Span::styled(
+                        format!("           {}", Self::tokens_summary(&totals)),
+                        Style::default().fg(self.theme.color8),
+                    )),
+                ])
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Screen for UsageScreen {
+    fn draw(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4), // All-time totals and period tabs
+                Constraint::Percentage(45), // Period rows
+                Constraint::Min(0),    // Breakdown of the selected period
+                Constraint::Length(2), // Help bar
+            ])
+            .split(area);
+
+        // Header: all-time totals and the period selector
+        let mut tabs = vec![Span::raw(" ")];
+        for period in Period::ALL {
+            let style = if period == self.period {
+                Style::default()
+                    .fg(self.theme.background)
+                    .bg(self.theme.color6)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.color7)
+            };
+            tabs.push(Span::styled(format!(" {} ", period.label()), style));
+            tabs.push(Span::raw(" "));
+        }
+        let header = Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled(
+                    format!(" {} ", format_cost(self.all_time.cost(&self.config))),
+                    Style::default().fg(self.theme.color3).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    Self::tokens_summary(&self.all_time),
+                    Style::default().fg(self.theme.color7),
+                ),
+            ]),
+            Line::from(tabs),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Token Usage — all time ")
+                .border_style(Style::default().fg(self.theme.color6)),
+        );
+        f.render_widget(header, chunks[0]);
+
+        // Period rows with a cost bar relative to the most expensive period
+        let costs: Vec<f64> = self.rows.iter().map(|r| r.totals.cost(&self.config)).collect();
+        let max_cost = costs.iter().copied().fold(0.0, f64::max);
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .zip(&costs)
+            .map(|(row, &cost)| {
+                let bar_len = if max_cost > 0.0 {
+                    ((cost / max_cost) * 20.0).round() as usize
+                } else {
+                    0
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<22}", self.period.format(row.start)),
+                        Style::default().fg(self.theme.foreground),
+                    ),
+                    Span::styled(
+                        format!("{:>10}  ", format_cost(cost)),
+                        Style::default().fg(self.theme.color3),
+                    ),
+                    Span::styled(
+                        format!("{:<20}  ", "█".repeat(bar_len)),
+                        Style::default().fg(self.theme.color4),
+                    ),
+                    Span::styled(
+                        Self::tokens_summary(&row.totals),
+                        Style::default().fg(self.theme.color8),
+                    ),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("{} ({})", self.period.label(), self.rows.len()))
+                    .border_style(Style::default().fg(self.theme.color6)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(self.theme.color8)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("► ");
+        f.render_stateful_widget(list, chunks[1], &mut self.list_state);
+
+        // Breakdown of the selected period by model and by project
+        let breakdown = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[2]);
+        let selected = self.list_state.selected().and_then(|i| self.rows.get(i));
+        let (model_items, project_items) = match selected {
+            Some(row) => (
+                self.breakdown_items(row.totals.by_model.iter().map(|(model, tokens)| {
+                    let mut totals = UsageTotals::default();
+                    totals.add(model, *tokens);
+                    (model.as_str(), totals)
+                })),
+                self.breakdown_items(
+                    row.by_project
+                        .iter()
+                        .map(|(project, totals)| (project.as_str(), totals.clone())),
+                ),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        let breakdown_block = |title: &str| {
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string())
+                .border_style(Style::default().fg(self.theme.color8))
+        };
+        f.render_widget(List::new(model_items).block(breakdown_block("By model")), breakdown[0]);
+        f.render_widget(
+            List::new(project_items).block(breakdown_block("By project")),
+            breakdown[1],
+        );
+
+        // Help bar
+        let key = |k: &'static str| Span::styled(k, Style::default().fg(self.theme.color6));
+        let label = |l: &'static str| Span::styled(l, Style::default().fg(self.theme.color7));
+        let help = Paragraph::new(Line::from(vec![
+            Span::raw(" "),
+            key("↑↓"),
+            label(" Select  "),
+            key("←→"),
+            label(" Day/Week/Month  "),
+            key("?"),
+            label(" = model missing from price table  "),
+            key("Esc"),
+            label(" Back"),
+        ]));
+        f.render_widget(help, chunks[3]);
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) -> ScreenAction {
+        match key.code {
+            KeyCode::Esc => return ScreenAction::BackToSessions,
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Home => self.move_selection(isize::MIN),
+            KeyCode::End => self.move_selection(isize::MAX),
+            KeyCode::Left => self.set_period(self.period.prev()),
+            KeyCode::Right => self.set_period(self.period.next()),
+            _ => {}
+        }
+        ScreenAction::None
+    }
+}
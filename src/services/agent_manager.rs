@@ -1,38 +1,180 @@
 //! Core agent orchestration — spawn, monitor, kill, persist.
 
 use anyhow::{Context, Result};
-use chrono::Utc;
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::process::Command;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
 use std::time::Duration;
 use tokio::sync::mpsc;
 
 use crate::config::Config;
 use crate::event::AppEvent;
-use crate::models::agent::{Agent, AgentStatus};
+use crate::models::agent::{Agent, AgentBackend, AgentDiff, AgentResult, AgentStatus};
+use crate::models::stream_event::StreamEvent;
+use crate::models::project::encode_project_path;
+use crate::models::{ContentBlock, MessageContent, MessageEntry, TokenCounts};
 use crate::models::agent_registry::AgentRegistry;
+use crate::services::file_changes::files_touched;
+use crate::services::jsonl_tailer::JsonlTailer;
+use crate::services::session_store::SessionStore;
 use crate::services::worktree_manager::WorktreeManager;
+use crate::utils::shell_quote;
+
+/// How often the monitor polls alive agents.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How long an agent must sit idle after its last reply to count as finished.
+const COMPLETION_IDLE_SECS: i64 = 120;
 
 /// Persistence file for agents across restarts.
 fn agents_file() -> PathBuf {
     Config::data_dir().join("agents.json")
 }
 
+/// File the spawn command writes claude's exit code to.
+fn exit_file(agent_id: &str) -> PathBuf {
+    Config::data_dir().join("agents").join(format!("{}.exit", agent_id))
+}
+
+/// Stream-json output of a headless agent.
+fn log_file(agent_id: &str) -> PathBuf {
+    Config::data_dir().join("agents").join(format!("{}.log", agent_id))
+}
+
+/// Run `claude -p` in the background, its output going to `log_path`.
+///
+/// The process gets its own process group, so it outlives total-recall and
+/// can be killed as a whole. Returns its PID.
+fn launch_headless(
+    working_dir: &Path,
+    claude_session_id: &str,
+    task_prompt: &str,
+    exit_path: &Path,
+    log_path: &Path,
+) -> Result<u32> {
+    let log = std::fs::File::create(log_path).with_context(|| format!("Failed to create {:?}", log_path))?;
+    // Always skip permissions for spawned agents — nobody is there to answer
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(r#"claude "$@"; echo $? > "$TR_EXIT_FILE""#)
+        .arg("claude")
+        .args(["-p", "--output-format", "stream-json", "--verbose"])
+        .args(["--session-id", claude_session_id, "--dangerously-skip-permissions", task_prompt])
+        .env("TR_EXIT_FILE", exit_path)
+        .current_dir(working_dir)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .process_group(0)
+        .spawn()
+        .context("Failed to start claude")?;
+
+    // Reap it on exit, or it lingers as a zombie that still looks alive
+    let pid = child.id();
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(pid)
+}
+
+/// Create a detached tmux session and type the claude command into it.
+fn launch_tmux(
+    tmux_session: &str,
+    working_dir: &Path,
+    claude_session_id: &str,
+    task_prompt: &str,
+    exit_path: &Path,
+) -> Result<()> {
+    let output = Command::new("tmux")
+        .arg("new-session")
+        .arg("-d")
+        .arg("-s")
+        .arg(tmux_session)
+        .arg("-c")
+        .arg(working_dir.to_str().unwrap_or("."))
+        .output()
+        .context("Failed to create tmux session")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("tmux new-session failed: {}", stderr);
+    }
+
+    // Source wallust colors
+    let _ = Command::new("tmux")
+        .arg("send-keys")
+        .arg("-t")
+        .arg(tmux_session)
+        .arg("source ~/.cache/wallust/tmux.conf 2>/dev/null; clear")
+        .arg("Enter")
+        .output();
+
+    // Small delay to let the source command finish
+    std::thread::sleep(Duration::from_millis(200));
+
+    // Its exit code is written out for the monitor; `sh -c` keeps `$?`
+    // working under any shell.
+    // Always skip permissions for spawned agents — they run unattended in tmux
+    let script = format!(
+        "claude --session-id {} --dangerously-skip-permissions {}; echo $? > {}",
+        claude_session_id,
+        shell_quote(task_prompt),
+        shell_quote(&exit_path.to_string_lossy())
+    );
+    let claude_cmd = format!("sh -c {}", shell_quote(&script));
+
+    let _ = Command::new("tmux")
+        .arg("send-keys")
+        .arg("-t")
+        .arg(tmux_session)
+        .arg(&claude_cmd)
+        .arg("Enter")
+        .output();
+    Ok(())
+}
+
+/// Kill an agent's tmux session, or the process group of a headless agent
+/// that is still running (harmless if already gone).
+fn stop_agent(agent: &Agent) {
+    match agent.backend {
+        AgentBackend::Tmux => {
+            let _ = Command::new("tmux")
+                .arg("kill-session")
+                .arg("-t")
+                .arg(&agent.tmux_session)
+                .output();
+        }
+        AgentBackend::Headless => {
+            // An exited agent's PID may belong to something else by now
+            if let Some(pid) = agent.pid.filter(|_| agent.status.is_alive()) {
+                unsafe {
+                    libc::kill(-(pid as libc::pid_t), libc::SIGTERM);
+                }
+            }
+        }
+    }
+}
+
+/// Whether a process is still running.
+fn process_alive(pid: u32) -> bool {
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+}
+
 /// Manages the lifecycle of all spawned agents.
 pub struct AgentManager {
     agents: Vec<Agent>,
     registry: AgentRegistry,
     config: std::sync::Arc<Config>,
-    event_tx: mpsc::UnboundedSender<AppEvent>,
+    /// Commands to the monitor task, once started
+    monitor: Option<mpsc::UnboundedSender<MonitorCommand>>,
 }
 
 impl AgentManager {
     /// Create a new manager, loading persisted agents and the registry.
-    pub fn new(
-        config: std::sync::Arc<Config>,
-        event_tx: mpsc::UnboundedSender<AppEvent>,
-    ) -> Result<Self> {
+    pub fn new(config: std::sync::Arc<Config>) -> Result<Self> {
         let registry = AgentRegistry::load().unwrap_or_else(|e| {
             tracing::warn!("Failed to load agent registry: {}", e);
             AgentRegistry {
@@ -46,7 +188,7 @@ impl AgentManager {
             agents,
             registry,
             config,
-            event_tx,
+            monitor: None,
         })
     }
 
@@ -75,29 +217,24 @@ impl AgentManager {
         self.agents.get(index)
     }
 
-    /// Get mutable agent by ID.
-    pub fn get_by_id_mut(&mut self, id: &str) -> Option<&mut Agent> {
-        self.agents.iter_mut().find(|a| a.id == id)
-    }
-
     /// Spawn a new agent.
     ///
     /// 1. Optionally create a jj worktree
-    /// 2. Create a tmux session
-    /// 3. Send the claude command
+    /// 2. Start claude: headless as a child process logging stream-json,
+    ///    or typed into a new detached tmux session
     pub fn spawn(
         &mut self,
         project_path: PathBuf,
         agent_type: String,
         task_prompt: String,
         use_worktree: bool,
+        backend: AgentBackend,
     ) -> Result<usize> {
         let id = uuid::Uuid::new_v4().to_string();
         let short_id = &id[..8];
 
         // Generate a name from agent type + short id
         let name = format!("{}-{}", agent_type, short_id);
-        let tmux_session = format!("tr-{}", name);
 
         // Determine working directory
         let (working_dir, worktree_path) = if use_worktree {
@@ -108,49 +245,36 @@ impl AgentManager {
             (project_path.clone(), None)
         };
 
-        // Create tmux session
-        let output = Command::new("tmux")
-            .arg("new-session")
-            .arg("-d")
-            .arg("-s")
-            .arg(&tmux_session)
-            .arg("-c")
-            .arg(working_dir.to_str().unwrap_or("."))
-            .output()
-            .context("Failed to create tmux session")?;
-
-        if !output.status.success() {
-            // Clean up worktree if tmux failed
-            if let Some(ref wt) = worktree_path {
-                let _ = WorktreeManager::destroy(&project_path, &name, wt);
+        // A session ID of our own, so the agent's JSONL can be found without
+        // guessing, and a file for claude's exit code
+        let claude_session_id = uuid::Uuid::new_v4().to_string();
+        let exit_path = exit_file(&id);
+        let launched = exit_path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .context("Failed to create agent data dir")
+            .and_then(|_| match backend {
+                AgentBackend::Headless => {
+                    let log_path = log_file(&id);
+                    let pid = launch_headless(&working_dir, &claude_session_id, &task_prompt, &exit_path, &log_path)?;
+                    Ok((String::new(), Some(pid), Some(log_path)))
+                }
+                AgentBackend::Tmux => {
+                    let tmux_session = format!("tr-{}", name);
+                    launch_tmux(&tmux_session, &working_dir, &claude_session_id, &task_prompt, &exit_path)?;
+                    Ok((tmux_session, None, None))
+                }
+            });
+        let (tmux_session, pid, log_path) = match launched {
+            Ok(launched) => launched,
+            Err(e) => {
+                // Clean up worktree if claude could not be started
+                if let Some(ref wt) = worktree_path {
+                    let _ = WorktreeManager::destroy(&project_path, &name, wt);
+                }
+                return Err(e);
             }
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("tmux new-session failed: {}", stderr);
-        }
-
-        // Source wallust colors
-        let _ = Command::new("tmux")
-            .arg("send-keys")
-            .arg("-t")
-            .arg(&tmux_session)
-            .arg("source ~/.cache/wallust/tmux.conf 2>/dev/null; clear")
-            .arg("Enter")
-            .output();
-
-        // Small delay to let the source command finish
-        std::thread::sleep(Duration::from_millis(200));
-
-        // Build and send the claude command
-        // Always skip permissions for spawned agents — they run unattended in tmux
-        let claude_cmd = format!("claude --dangerously-skip-permissions '{}'", task_prompt.replace('\'', "'\\''"));
-
-        let _ = Command::new("tmux")
-            .arg("send-keys")
-            .arg("-t")
-            .arg(&tmux_session)
-            .arg(&claude_cmd)
-            .arg("Enter")
-            .output();
+        };
 
         let now = Utc::now();
         let agent = Agent {
@@ -159,8 +283,11 @@ impl AgentManager {
             agent_type,
             project_path,
             worktree_path,
+            backend,
             tmux_session,
-            claude_session_id: None,
+            pid,
+            log_path,
+            claude_session_id: Some(claude_session_id),
             status: AgentStatus::Starting,
             task_prompt,
             spawned_at: now,
@@ -168,8 +295,10 @@ impl AgentManager {
             message_count: 0,
             last_tool: None,
             last_output_lines: Vec::new(),
+            result: None,
         };
 
+        self.send_monitor(MonitorCommand::Track(Box::new(agent.clone())));
         self.agents.push(agent);
         let index = self.agents.len() - 1;
 
@@ -179,7 +308,7 @@ impl AgentManager {
         Ok(index)
     }
 
-    /// Kill an agent's tmux session.
+    /// Kill an agent's tmux session or process.
     ///
     /// Tolerant of already-dead agents: if the agent is already `Killed`,
     /// this is a no-op. If `Complete`/`Failed`, it transitions to `Killed`
@@ -195,16 +324,13 @@ impl AgentManager {
             return Ok(());
         }
 
-        // Kill tmux session (harmless if already gone)
-        let _ = Command::new("tmux")
-            .arg("kill-session")
-            .arg("-t")
-            .arg(&agent.tmux_session)
-            .output();
+        stop_agent(agent);
 
         agent.status = AgentStatus::Killed;
         agent.last_activity = Utc::now();
         let name = agent.name.clone();
+        let id = agent.id.clone();
+        self.send_monitor(MonitorCommand::Untrack(id));
 
         self.persist();
         tracing::info!("Killed agent {}", name);
@@ -221,11 +347,7 @@ impl AgentManager {
 
         // Kill if still alive
         if agent.status.is_alive() {
-            let _ = Command::new("tmux")
-                .arg("kill-session")
-                .arg("-t")
-                .arg(&agent.tmux_session)
-                .output();
+            stop_agent(agent);
         }
 
         // Destroy worktree if it exists
@@ -236,265 +358,853 @@ impl AgentManager {
         }
 
         let name = self.agents[index].name.clone();
-        self.agents.remove(index);
+        let agent = self.agents.remove(index);
+        let _ = std::fs::remove_file(exit_file(&agent.id));
+        if let Some(log_path) = &agent.log_path {
+            let _ = std::fs::remove_file(log_path);
+        }
+        self.send_monitor(MonitorCommand::Untrack(agent.id));
         self.persist();
         tracing::info!("Deleted agent {}", name);
         Ok(())
     }
 
-    /// Poll all alive agents for status changes.
+    /// Poll all alive agents for status changes, blocking until done.
     ///
-    /// Called periodically from the monitoring task.
+    /// For one-off commands; the TUI leaves polling to the monitor task.
     pub fn poll_agents(&mut self) {
-        let mut events = Vec::new();
+        let projects_dir = self.config.claude_projects_dir();
+        let mut changed = false;
+        for agent in self.agents.iter_mut().filter(|a| a.status.is_alive()) {
+            let diff = poll_agent(agent, &projects_dir, &mut PollState::default());
+            if !diff.is_empty() {
+                agent.apply(&diff);
+                changed = true;
+            }
+        }
+        if changed {
+            self.persist();
+        }
+    }
+
+    /// Apply a diff sent by the monitor.
+    ///
+    /// Agents killed or deleted since the poll are left alone. Returns the
+    /// agent when its status changed.
+    pub fn apply_diff(&mut self, agent_id: &str, diff: &AgentDiff) -> Option<&Agent> {
+        let agent = self.agents.iter_mut().find(|a| a.id == agent_id && a.status.is_alive())?;
+        let status_changed = agent.apply(diff);
+        self.persist();
+        status_changed.then(|| self.agents.iter().find(|a| a.id == agent_id)).flatten()
+    }
+
+    /// Start the background monitor for the alive agents.
+    ///
+    /// It polls every few seconds and sends what changed through `event_tx`
+    /// as `AgentUpdate`/`AgentExited` events; agents spawned, killed or
+    /// deleted afterwards are tracked through this manager.
+    pub fn start_monitor(&mut self, event_tx: mpsc::UnboundedSender<AppEvent>) {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let agents = self.agents.iter().filter(|a| a.status.is_alive()).cloned().collect();
+        tokio::spawn(monitor_loop(agents, self.config.claude_projects_dir(), cmd_rx, event_tx));
+        self.monitor = Some(cmd_tx);
+    }
+
+    /// Ask the monitor to poll now rather than at the next interval.
+    pub fn refresh(&self) {
+        self.send_monitor(MonitorCommand::PollNow);
+    }
+
+    /// Stop the monitor task.
+    pub fn stop_monitor(&mut self) {
+        self.send_monitor(MonitorCommand::Stop);
+        self.monitor = None;
+    }
 
-        for agent in &mut self.agents {
-            if !agent.status.is_alive() {
+    fn send_monitor(&self, command: MonitorCommand) {
+        if let Some(tx) = &self.monitor {
+            let _ = tx.send(command);
+        }
+    }
+
+    /// Persist agents to disk.
+    fn persist(&self) {
+        let dir = Config::data_dir();
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            tracing::warn!("Failed to create data dir: {}", e);
+            return;
+        }
+        let file = agents_file();
+        match serde_json::to_string_pretty(&self.agents) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&file, json) {
+                    tracing::warn!("Failed to persist agents: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize agents: {}", e),
+        }
+    }
+
+    /// Load persisted agents from disk.
+    fn load_persisted() -> Result<Vec<Agent>> {
+        let file = agents_file();
+        if !file.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&file)?;
+        let agents: Vec<Agent> = serde_json::from_str(&content)?;
+        Ok(agents)
+    }
+}
+
+/// Commands for the background monitor task.
+pub enum MonitorCommand {
+    /// Start polling a newly spawned agent.
+    Track(Box<Agent>),
+    /// Stop polling a killed or deleted agent.
+    Untrack(String),
+    /// Poll now instead of waiting for the next interval.
+    PollNow,
+    Stop,
+}
+
+/// Poll the tracked agents until stopped, sending a diff for each one that changed.
+async fn monitor_loop(
+    mut agents: Vec<Agent>,
+    projects_dir: PathBuf,
+    mut cmd_rx: mpsc::UnboundedReceiver<MonitorCommand>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    // Agent ID -> what has been read of its session JSONL or log, kept between polls
+    let mut progress: HashMap<String, PollState> = HashMap::new();
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            cmd = cmd_rx.recv() => match cmd {
+                Some(MonitorCommand::Track(agent)) => {
+                    agents.retain(|a| a.id != agent.id);
+                    agents.push(*agent);
+                    continue;
+                }
+                Some(MonitorCommand::Untrack(id)) => {
+                    agents.retain(|a| a.id != id);
+                    progress.remove(&id);
+                    continue;
+                }
+                Some(MonitorCommand::PollNow) => {}
+                Some(MonitorCommand::Stop) | None => break,
+            },
+        }
+
+        // tmux and the JSONL files are read on the blocking pool
+        let snapshot: Vec<_> = agents
+            .iter()
+            .map(|a| (a.clone(), progress.remove(&a.id).unwrap_or_default()))
+            .collect();
+        let projects_dir = projects_dir.clone();
+        let polled = tokio::task::spawn_blocking(move || {
+            snapshot
+                .into_iter()
+                .map(|(agent, mut state)| (poll_agent(&agent, &projects_dir, &mut state), state))
+                .collect::<Vec<_>>()
+        });
+        let diffs = match polled.await {
+            Ok(diffs) => diffs,
+            Err(e) => {
+                tracing::warn!("Agent poll failed: {}", e);
                 continue;
             }
+        };
 
-            // Check if tmux session is still alive
-            let alive = Command::new("tmux")
-                .arg("has-session")
-                .arg("-t")
-                .arg(&agent.tmux_session)
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false);
-
-            if !alive {
-                let old_status = agent.status.clone();
-                agent.status = if old_status == AgentStatus::Starting {
-                    AgentStatus::Failed
-                } else {
-                    AgentStatus::Complete
-                };
-                agent.last_activity = Utc::now();
-                events.push(AppEvent::AgentExited { agent_id: agent.id.clone() });
+        for (agent, (diff, state)) in agents.iter_mut().zip(diffs) {
+            progress.insert(agent.id.clone(), state);
+            if diff.is_empty() {
                 continue;
             }
+            agent.apply(&diff);
+            let agent_id = agent.id.clone();
+            let event = if agent.status.is_alive() {
+                AppEvent::AgentUpdate { agent_id, diff }
+            } else {
+                AppEvent::AgentExited { agent_id, diff }
+            };
+            if event_tx.send(event).is_err() {
+                return; // app is shutting down
+            }
+        }
+        agents.retain(|a| a.status.is_alive());
+        progress.retain(|id, _| agents.iter().any(|a| a.id == *id));
+    }
+}
 
-            // Capture last 10 lines from tmux pane
-            if let Ok(output) = Command::new("tmux")
-                .arg("capture-pane")
-                .arg("-p")
-                .arg("-t")
-                .arg(&agent.tmux_session)
-                .arg("-S")
-                .arg("-10")
-                .output()
-            {
-                if output.status.success() {
-                    let text = String::from_utf8_lossy(&output.stdout);
-                    let new_lines: Vec<String> = text
-                        .lines()
-                        .map(|l| l.to_string())
-                        .filter(|l| !l.trim().is_empty())
-                        .collect();
-
-                    // Detect activity by comparing output
-                    if new_lines != agent.last_output_lines {
-                        agent.last_activity = Utc::now();
-                        if agent.status != AgentStatus::Active {
-                            agent.status = AgentStatus::Active;
-                        }
-                        agent.last_output_lines = new_lines;
-                        events.push(AppEvent::AgentUpdate { agent_id: agent.id.clone() });
-                    } else {
-                        // No change — check if idle
-                        let idle_secs = (Utc::now() - agent.last_activity).num_seconds();
-                        if idle_secs > 30 && agent.status == AgentStatus::Active {
-                            agent.status = AgentStatus::Idle;
-                            events.push(AppEvent::AgentUpdate { agent_id: agent.id.clone() });
-                        }
-                    }
-                }
+/// What the monitor has read for an agent, kept between polls.
+#[derive(Default)]
+struct PollState {
+    session: Option<SessionProgress>,
+    stream: Option<StreamProgress>,
+}
+
+/// Check an agent for changes, reading only what is new since `state`.
+///
+/// Blocking: shells out to tmux and reads the agent's files.
+fn poll_agent(agent: &Agent, projects_dir: &Path, state: &mut PollState) -> AgentDiff {
+    match agent.backend {
+        AgentBackend::Tmux => poll_tmux_agent(agent, projects_dir, &mut state.session),
+        AgentBackend::Headless => poll_headless_agent(agent, projects_dir, &mut state.stream),
+    }
+}
+
+/// Check a tmux agent's session, JSONL and exit code for changes.
+///
+/// Reads what was appended to the session file since `session` last read
+/// it (or all of it, without one). An agent is complete once claude exits
+/// or its tmux session goes away. If its last reply left no tool call
+/// pending and nothing has happened since for [`COMPLETION_IDLE_SECS`], it
+/// is `Finished` but still alive, and becomes active again on new output.
+/// Either way the result is read from the whole JSONL.
+fn poll_tmux_agent(agent: &Agent, projects_dir: &Path, session: &mut Option<SessionProgress>) -> AgentDiff {
+    let mut diff = AgentDiff::default();
+    let now = Utc::now();
+
+    // Try to discover JSONL session and extract tool info
+    let session_id = agent
+        .claude_session_id
+        .clone()
+        .or_else(|| discover_session(agent, projects_dir));
+    let jsonl_path = session_id
+        .as_ref()
+        .map(|id| agent_project_dir(agent, projects_dir).join(format!("{}.jsonl", id)));
+    if let Some(path) = &jsonl_path {
+        if let Some((message_count, last_tool)) = read_jsonl_progress(agent, path, session) {
+            if message_count != agent.message_count {
+                diff.message_count = Some(message_count);
             }
+            if last_tool.is_some() && last_tool != agent.last_tool {
+                diff.last_tool = last_tool;
+            }
+        }
+    }
+    if agent.claude_session_id.is_none() {
+        diff.claude_session_id = session_id;
+    }
 
-            // Try to discover JSONL session and extract tool info
-            Self::try_discover_session(agent);
+    let exit_code = read_exit_code(&agent.id);
+
+    // Check if tmux session is still alive
+    let alive = Command::new("tmux")
+        .arg("has-session")
+        .arg("-t")
+        .arg(&agent.tmux_session)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    let mut finished = match exit_code {
+        Some(0) => Some(AgentStatus::Complete),
+        Some(_) => Some(AgentStatus::Failed),
+        None if !alive && agent.status == AgentStatus::Starting => Some(AgentStatus::Failed),
+        None if !alive => Some(AgentStatus::Complete),
+        None => None,
+    };
+
+    if finished.is_none() {
+        let pane_changed = capture_pane(agent, now, &mut diff);
+        let turn_idle = session.as_ref().is_some_and(|s| {
+            s.turn_finished && s.last_message_at.is_some_and(|at| (now - at).num_seconds() >= COMPLETION_IDLE_SECS)
+        });
+        // Its work looks done, but claude is still open: keep watching it
+        let idle = !pane_changed && (now - agent.last_activity).num_seconds() >= COMPLETION_IDLE_SECS;
+        if turn_idle && idle && agent.status != AgentStatus::Finished {
+            finished = Some(AgentStatus::Finished);
         }
+    }
+
+    if let Some(status) = finished {
+        diff.status = Some(status);
+        diff.last_activity = Some(now);
+        diff.result = Some(match &jsonl_path {
+            Some(path) => collect_result(path, exit_code),
+            None => AgentResult {
+                exit_code,
+                ..AgentResult::default()
+            },
+        });
+    }
+
+    diff
+}
+
+/// Check a headless agent's log and process for changes.
+///
+/// The agent is finished once its process has exited; the final event in
+/// the log says whether it succeeded.
+fn poll_headless_agent(agent: &Agent, projects_dir: &Path, stream: &mut Option<StreamProgress>) -> AgentDiff {
+    let mut diff = AgentDiff::default();
+    let now = Utc::now();
+
+    // Checked before reading the log, so an exited agent's log is complete
+    let exit_code = read_exit_code(&agent.id);
+    let running = exit_code.is_none() && agent.pid.is_some_and(process_alive);
+
+    if let Some(log_path) = &agent.log_path {
+        let progress = match stream {
+            Some(p) => p,
+            None => stream.insert(StreamProgress::new(log_path)),
+        };
+        // Nothing to read until claude has written something
+        if let Ok(read) = progress.tailer.read_new_as::<StreamEvent>() {
+            if read.truncated {
+                progress.reset();
+            }
+            let got_events = !read.entries.is_empty();
+            for event in read.entries {
+                progress.observe(event);
+            }
 
-        // Send all events
-        for event in events {
-            let _ = self.event_tx.send(event);
+            if got_events {
+                diff.last_activity = Some(now);
+                if agent.status != AgentStatus::Active {
+                    diff.status = Some(AgentStatus::Active);
+                }
+            } else if (now - agent.last_activity).num_seconds() > 30 && agent.status == AgentStatus::Active {
+                diff.status = Some(AgentStatus::Idle);
+            }
+
+            let lines: Vec<String> = progress.lines.iter().cloned().collect();
+            if lines != agent.last_output_lines {
+                diff.last_output_lines = Some(lines);
+            }
+            if progress.message_count != agent.message_count {
+                diff.message_count = Some(progress.message_count);
+            }
+            if progress.last_tool.is_some() && progress.last_tool != agent.last_tool {
+                diff.last_tool = progress.last_tool.clone();
+            }
+            if agent.claude_session_id.is_none() {
+                diff.claude_session_id = progress.session_id.clone();
+            }
         }
+    }
 
-        // Persist if anything changed
-        self.persist();
+    if running {
+        return diff;
     }
 
-    /// Try to find the Claude session JSONL for an agent and extract metadata.
-    fn try_discover_session(agent: &mut Agent) {
-        if agent.claude_session_id.is_some() {
-            // Already discovered — just try to read latest tool use
-            Self::update_from_jsonl(agent);
-            return;
+    let reply = stream.as_ref().and_then(|p| p.result.clone());
+    diff.status = Some(match (&reply, exit_code) {
+        (Some((true, _)), _) => AgentStatus::Failed,
+        (Some((false, _)), _) | (None, Some(0)) => AgentStatus::Complete,
+        (None, _) => AgentStatus::Failed,
+    });
+    diff.last_activity = Some(now);
+
+    let session_id = agent.claude_session_id.as_ref().or(diff.claude_session_id.as_ref());
+    let mut result = match session_id {
+        Some(id) => collect_result(&agent_project_dir(agent, projects_dir).join(format!("{}.jsonl", id)), exit_code),
+        None => AgentResult {
+            exit_code,
+            ..AgentResult::default()
+        },
+    };
+    if let Some((_, Some(text))) = reply {
+        result.final_message = Some(text);
+    }
+    diff.result = Some(result);
+    diff
+}
+
+/// Exit code the spawn command wrote once claude exited.
+fn read_exit_code(agent_id: &str) -> Option<i32> {
+    std::fs::read_to_string(exit_file(agent_id))
+        .ok()
+        .and_then(|code| code.trim().parse().ok())
+}
+
+/// Capture the last lines of the agent's tmux pane into `diff`, updating its
+/// activity. Returns whether the pane changed.
+fn capture_pane(agent: &Agent, now: DateTime<Utc>, diff: &mut AgentDiff) -> bool {
+    let Ok(output) = Command::new("tmux")
+        .arg("capture-pane")
+        .arg("-p")
+        .arg("-t")
+        .arg(&agent.tmux_session)
+        .arg("-S")
+        .arg("-10")
+        .output()
+    else {
+        return false;
+    };
+    if !output.status.success() {
+        return false;
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let new_lines: Vec<String> = text
+        .lines()
+        .map(|l| l.to_string())
+        .filter(|l| !l.trim().is_empty())
+        .collect();
+
+    // Detect activity by comparing output
+    if new_lines != agent.last_output_lines {
+        diff.last_activity = Some(now);
+        if agent.status != AgentStatus::Active {
+            diff.status = Some(AgentStatus::Active);
+        }
+        diff.last_output_lines = Some(new_lines);
+        true
+    } else {
+        // No change — check if idle
+        let idle_secs = (now - agent.last_activity).num_seconds();
+        if idle_secs > 30 && agent.status == AgentStatus::Active {
+            diff.status = Some(AgentStatus::Idle);
         }
+        false
+    }
+}
 
-        // Look in ~/.claude/projects/ for JSONL files created after agent spawn
-        let home = match dirs::home_dir() {
-            Some(h) => h,
-            None => return,
+/// Claude projects directory holding an agent's session files.
+fn agent_project_dir(agent: &Agent, projects_dir: &Path) -> PathBuf {
+    let working_dir = agent.worktree_path.as_ref().unwrap_or(&agent.project_path);
+    projects_dir.join(encode_project_path(&working_dir.to_string_lossy()))
+}
+
+/// Find the session of an agent spawned without `--session-id`.
+///
+/// Candidates are sessions in the agent's working directory that started
+/// after the spawn with the task prompt as their first user message; the
+/// earliest one wins. Returns `None` rather than guess when nothing matches.
+fn discover_session(agent: &Agent, projects_dir: &Path) -> Option<String> {
+    let working_dir = agent.worktree_path.as_ref().unwrap_or(&agent.project_path);
+    let entries = std::fs::read_dir(agent_project_dir(agent, projects_dir)).ok()?;
+    let mut found: Option<(chrono::DateTime<Utc>, String)> = None;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            continue;
+        }
+        let Ok(modified) = path.metadata().and_then(|m| m.modified()) else {
+            continue;
+        };
+        if chrono::DateTime::<Utc>::from(modified) <= agent.spawned_at {
+            continue;
+        }
+        let Some(first) = first_user_entry(&path) else {
+            continue;
+        };
+        let prompt_matches = first
+            .message
+            .as_ref()
+            .is_some_and(|m| m.text().trim() == agent.task_prompt.trim());
+        if first.timestamp < agent.spawned_at
+            || first.cwd.as_deref().map(Path::new) != Some(working_dir.as_path())
+            || !prompt_matches
+        {
+            continue;
+        }
+        let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
         };
+        let earliest = match &found {
+            Some((started, _)) => first.timestamp < *started,
+            None => true,
+        };
+        if earliest {
+            found = Some((first.timestamp, session_id.to_string()));
+        }
+    }
+    found.map(|(_, id)| id)
+}
 
-        let working_dir = agent.worktree_path.as_ref().unwrap_or(&agent.project_path);
-        let encoded = working_dir
-            .to_str()
-            .unwrap_or("")
-            .replace('/', "-");
+/// The first user message of a session JSONL, looking only at its opening lines.
+fn first_user_entry(path: &Path) -> Option<MessageEntry> {
+    const MAX_LINES: usize = 20;
+    let file = std::fs::File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(MAX_LINES)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<MessageEntry>(&line).ok())
+        .find(|entry| entry.entry_type == "user")
+}
 
-        let project_dir = home.join(".claude").join("projects").join(&encoded);
-        if !project_dir.exists() {
-            return;
+/// What has been read of an agent's session JSONL between polls.
+struct SessionProgress {
+    tailer: JsonlTailer,
+    /// tool_use IDs still waiting for their result
+    pending_tools: HashSet<String>,
+    /// The last message was an assistant reply with no tool call pending
+    turn_finished: bool,
+    last_message_at: Option<DateTime<Utc>>,
+    /// API message ID of the last assistant entry
+    last_response_id: Option<String>,
+}
+
+impl SessionProgress {
+    fn new(path: &Path) -> Self {
+        Self {
+            tailer: JsonlTailer::new(path),
+            pending_tools: HashSet::new(),
+            turn_finished: false,
+            last_message_at: None,
+            last_response_id: None,
         }
+    }
 
-        // Find JSONL files created after spawn time
-        let spawn_time = agent.spawned_at;
-        if let Ok(entries) = std::fs::read_dir(&project_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
-                    continue;
-                }
-                if let Ok(meta) = path.metadata() {
-                    if let Ok(modified) = meta.modified() {
-                        let modified_chrono: chrono::DateTime<Utc> = modified.into();
-                        if modified_chrono > spawn_time {
-                            // Read first line to check cwd
-                            if let Ok(content) = std::fs::read_to_string(&path) {
-                                if let Some(first_line) = content.lines().next() {
-                                    if let Ok(entry) = serde_json::from_str::<serde_json::Value>(first_line) {
-                                        if let Some(session_id) = entry.get("sessionId").and_then(|v| v.as_str()) {
-                                            agent.claude_session_id = Some(session_id.to_string());
-                                            Self::update_from_jsonl(agent);
-                                            return;
-                                        }
-                                    }
-                                }
-                            }
-                        }
+    /// Track tool calls and turns through one more entry.
+    fn observe(&mut self, entry: &MessageEntry) {
+        if entry.entry_type != "user" && entry.entry_type != "assistant" {
+            return;
+        }
+        self.last_message_at = Some(entry.timestamp);
+        let mut calls_tool = false;
+        if let Some(MessageContent::Structured { content, .. }) = &entry.message {
+            for block in content {
+                match block {
+                    ContentBlock::ToolUse { id, .. } => {
+                        self.pending_tools.insert(id.clone());
+                        calls_tool = true;
                     }
+                    ContentBlock::ToolResult { tool_use_id, .. } => {
+                        self.pending_tools.remove(tool_use_id);
+                    }
+                    _ => {}
                 }
             }
         }
+        self.turn_finished = entry.entry_type == "assistant" && !calls_tool && self.pending_tools.is_empty();
     }
 
-    /// Read the JSONL to get message count and last tool use.
-    fn update_from_jsonl(agent: &mut Agent) {
-        let session_id = match &agent.claude_session_id {
-            Some(id) => id.clone(),
-            None => return,
-        };
+    /// Whether an assistant entry starts a new response, rather than carrying
+    /// more content blocks of the previous one.
+    fn starts_response(&mut self, entry: &MessageEntry) -> bool {
+        if entry.entry_type != "assistant" {
+            return false;
+        }
+        let id = entry.message.as_ref().and_then(|m| m.id()).map(str::to_string);
+        let continues = id.is_some() && id == self.last_response_id;
+        self.last_response_id = id;
+        !continues
+    }
+}
 
-        let home = match dirs::home_dir() {
-            Some(h) => h,
-            None => return,
-        };
+/// Read newly appended JSONL entries to update the assistant message count
+/// and find the last tool used.
+fn read_jsonl_progress(agent: &Agent, jsonl_path: &Path, session: &mut Option<SessionProgress>) -> Option<(usize, Option<String>)> {
+    let session = match session {
+        Some(s) if s.tailer.path() == jsonl_path => s,
+        _ => session.insert(SessionProgress::new(jsonl_path)),
+    };
+
+    // Counts start over when the whole file is read
+    let from_start = session.tailer.offset() == 0;
+    let read = session.tailer.read_new().ok()?;
+    if read.truncated {
+        session.pending_tools.clear();
+        session.turn_finished = false;
+        session.last_message_at = None;
+        session.last_response_id = None;
+    }
+    let mut msg_count = if from_start || read.truncated { 0 } else { agent.message_count };
+    let mut last_tool: Option<String> = None;
 
-        let working_dir = agent.worktree_path.as_ref().unwrap_or(&agent.project_path);
-        let encoded = working_dir
-            .to_str()
-            .unwrap_or("")
-            .replace('/', "-");
+    for entry in &read.entries {
+        session.observe(entry);
 
-        let project_dir = home.join(".claude").join("projects").join(&encoded);
-        let jsonl_path = project_dir.join(format!("{}.jsonl", session_id));
+        // Count assistant responses, not the entries they're split into
+        if session.starts_response(entry) {
+            msg_count += 1;
+        }
 
-        if !jsonl_path.exists() {
-            return;
+        // Find tool_use in content blocks
+        if let Some(MessageContent::Structured { content, .. }) = &entry.message {
+            for block in content {
+                if let ContentBlock::ToolUse { name, .. } = block {
+                    last_tool = Some(name.clone());
+                }
+            }
         }
+    }
 
-        if let Ok(content) = std::fs::read_to_string(&jsonl_path) {
-            let mut msg_count = 0;
-            let mut last_tool: Option<String> = None;
+    Some((msg_count, last_tool))
+}
 
-            for line in content.lines() {
-                if let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) {
-                    // Count assistant messages
-                    if entry.get("type").and_then(|v| v.as_str()) == Some("assistant") {
-                        msg_count += 1;
-                    }
+/// What has been read of a headless agent's stream-json log between polls.
+struct StreamProgress {
+    tailer: JsonlTailer,
+    message_count: usize,
+    last_tool: Option<String>,
+    /// Recent output rendered as text, oldest first
+    lines: VecDeque<String>,
+    session_id: Option<String>,
+    /// The final event: whether it was an error, and the reply
+    result: Option<(bool, Option<String>)>,
+}
 
-                    // Find tool_use in content blocks
-                    if let Some(message) = entry.get("message") {
-                        if let Some(content) = message.get("content") {
-                            if let Some(blocks) = content.as_array() {
-                                for block in blocks {
-                                    if block.get("type").and_then(|v| v.as_str()) == Some("tool_use") {
-                                        if let Some(name) = block.get("name").and_then(|v| v.as_str()) {
-                                            last_tool = Some(name.to_string());
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+impl StreamProgress {
+    /// Lines of output kept for the agent view.
+    const MAX_LINES: usize = 50;
 
-            agent.message_count = msg_count;
-            if last_tool.is_some() {
-                agent.last_tool = last_tool;
-            }
+    fn new(path: &Path) -> Self {
+        Self {
+            tailer: JsonlTailer::new(path),
+            message_count: 0,
+            last_tool: None,
+            lines: VecDeque::new(),
+            session_id: None,
+            result: None,
         }
     }
 
-    /// Start the background monitoring task.
-    pub fn start_monitor(event_tx: mpsc::UnboundedSender<AppEvent>) -> mpsc::UnboundedSender<MonitorCommand> {
-        let (cmd_tx, mut cmd_rx) = mpsc::unbounded_channel::<MonitorCommand>();
+    /// Forget what was read, when the log is read again from the start.
+    fn reset(&mut self) {
+        self.message_count = 0;
+        self.last_tool = None;
+        self.lines.clear();
+        self.session_id = None;
+        self.result = None;
+    }
 
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(3));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        // The actual polling happens in App::handle_tick() which calls poll_agents()
-                        // We just need to make sure ticks are flowing
-                    }
-                    cmd = cmd_rx.recv() => {
-                        match cmd {
-                            Some(MonitorCommand::Stop) | None => break,
+    fn observe(&mut self, event: StreamEvent) {
+        match event {
+            StreamEvent::System { session_id } => {
+                if session_id.is_some() {
+                    self.session_id = session_id;
+                }
+            }
+            StreamEvent::Assistant { message } => {
+                self.message_count += 1;
+                let MessageContent::Structured { content, .. } = message else {
+                    return;
+                };
+                for block in content {
+                    match block {
+                        ContentBlock::Text { text } => {
+                            for line in text.lines().filter(|l| !l.trim().is_empty()) {
+                                self.push(line.to_string());
+                            }
                         }
+                        ContentBlock::ToolUse { name, input, .. } => {
+                            self.push(format!("● {}{}", name, tool_target(&input)));
+                            self.last_tool = Some(name);
+                        }
+                        _ => {}
                     }
                 }
             }
-        });
-
-        cmd_tx
+            StreamEvent::Result { is_error, result } => {
+                self.push(if is_error { "✗ Failed" } else { "◆ Done" }.to_string());
+                self.result = Some((is_error, result));
+            }
+            StreamEvent::Other => {}
+        }
     }
 
-    /// Persist agents to disk.
-    fn persist(&self) {
-        let dir = Config::data_dir();
-        if let Err(e) = std::fs::create_dir_all(&dir) {
-            tracing::warn!("Failed to create data dir: {}", e);
-            return;
+    fn push(&mut self, line: String) {
+        if self.lines.len() == Self::MAX_LINES {
+            self.lines.pop_front();
         }
-        let file = agents_file();
-        match serde_json::to_string_pretty(&self.agents) {
-            Ok(json) => {
-                if let Err(e) = std::fs::write(&file, json) {
-                    tracing::warn!("Failed to persist agents: {}", e);
-                }
+        self.lines.push_back(line);
+    }
+}
+
+/// " <file or command>" a tool call works on, if it has one.
+fn tool_target(input: &serde_json::Value) -> String {
+    ["file_path", "command", "pattern", "path", "url"]
+        .iter()
+        .find_map(|key| input.get(key).and_then(|v| v.as_str()))
+        .and_then(|target| target.lines().next())
+        .map(|target| format!(" {}", target))
+        .unwrap_or_default()
+}
+
+/// Read a finished agent's result from its whole session JSONL.
+fn collect_result(jsonl_path: &Path, exit_code: Option<i32>) -> AgentResult {
+    match SessionStore::load_entries(jsonl_path) {
+        Ok(entries) => agent_result(&entries, exit_code),
+        Err(e) => {
+            tracing::warn!("Failed to read agent result from {:?}: {}", jsonl_path, e);
+            AgentResult {
+                exit_code,
+                ..AgentResult::default()
             }
-            Err(e) => tracing::warn!("Failed to serialize agents: {}", e),
         }
     }
+}
 
-    /// Load persisted agents from disk.
-    fn load_persisted() -> Result<Vec<Agent>> {
-        let file = agents_file();
-        if !file.exists() {
-            return Ok(Vec::new());
+/// Final reply, files changed and tokens used over a session's entries.
+fn agent_result(entries: &[MessageEntry], exit_code: Option<i32>) -> AgentResult {
+    let final_message = entries
+        .iter()
+        .rev()
+        .filter(|e| e.entry_type == "assistant")
+        .filter_map(|e| e.message.as_ref())
+        .map(|m| m.text().trim().to_string())
+        .find(|text| !text.is_empty());
+
+    // Usage is repeated on every entry split from one response
+    let mut tokens = TokenCounts::default();
+    let mut seen_responses = HashSet::new();
+    for msg in entries.iter().filter_map(|e| e.message.as_ref()) {
+        let Some(usage) = msg.usage() else {
+            continue;
+        };
+        let first_seen = match msg.id() {
+            Some(id) => seen_responses.insert(id),
+            None => true,
+        };
+        if first_seen {
+            tokens += TokenCounts::from(usage);
         }
-        let content = std::fs::read_to_string(&file)?;
-        let agents: Vec<Agent> = serde_json::from_str(&content)?;
-        Ok(agents)
+    }
+
+    AgentResult {
+        final_message,
+        files_changed: files_touched(entries).into_iter().map(|f| f.path).collect(),
+        tokens,
+        exit_code,
     }
 }
 
-/// Commands for the background monitor task.
-pub enum MonitorCommand {
-    Stop,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_session(dir: &Path, id: &str, time: &str, cwd: &str, prompt: &str) {
+        let line = serde_json::json!({
+            "uuid": "u1",
+            "sessionId": id,
+            "timestamp": time,
+            "cwd": cwd,
+            "type": "user",
+            "message": { "role": "user", "content": prompt },
+        });
+        std::fs::write(dir.join(format!("{}.jsonl", id)), format!("{}\n", line)).unwrap();
+    }
+
+    #[test]
+    fn test_discover_session_matches_prompt_and_cwd() {
+        let projects_dir = std::env::temp_dir().join(format!("tr-agents-{}", uuid::Uuid::new_v4()));
+        let project_dir = projects_dir.join(encode_project_path("/work/app"));
+        std::fs::create_dir_all(&project_dir).unwrap();
+
+        let spawned_at = Utc::now() - chrono::Duration::minutes(10);
+        let at = |minutes: i64| (spawned_at + chrono::Duration::minutes(minutes)).to_rfc3339();
+        write_session(&project_dir, "human", &at(1), "/work/app", "Fix the tests");
+        write_session(&project_dir, "other-dir", &at(1), "/work/app/sub", "Add a --json flag");
+        write_session(&project_dir, "agent", &at(2), "/work/app", "Add a --json flag");
+        write_session(&project_dir, "rerun", &at(5), "/work/app", "Add a --json flag");
+
+        let agent = Agent {
+            backend: AgentBackend::Tmux,
+            tmux_session: "tr-dev-a".to_string(),
+            spawned_at,
+            last_activity: spawned_at,
+            ..Agent::test("a", AgentStatus::Active)
+        };
+        assert_eq!(discover_session(&agent, &projects_dir).as_deref(), Some("agent"));
+
+        std::fs::remove_dir_all(&projects_dir).unwrap();
+    }
+
+    #[test]
+    fn test_apply_diff_ignores_dead_agents() {
+        let mut manager = AgentManager {
+            agents: vec![Agent::test("killed", AgentStatus::Killed), Agent::test("done", AgentStatus::Complete)],
+            registry: AgentRegistry {
+                claude_code_agents: HashMap::new(),
+            },
+            config: std::sync::Arc::new(Config::default()),
+            monitor: None,
+        };
+        let diff = AgentDiff {
+            status: Some(AgentStatus::Failed),
+            message_count: Some(3),
+            ..Default::default()
+        };
+
+        // A late poll for a killed agent, or one deleted meanwhile
+        assert!(manager.apply_diff("killed", &diff).is_none());
+        assert!(manager.apply_diff("deleted", &diff).is_none());
+        assert!(manager.apply_diff("done", &diff).is_none());
+        assert_eq!(manager.agents()[0].status, AgentStatus::Killed);
+        assert_eq!(manager.agents()[0].message_count, 0);
+        assert_eq!(manager.agents()[1].status, AgentStatus::Complete);
+    }
+
+    fn reply(id: &str, blocks: serde_json::Value) -> MessageEntry {
+        let mut entry = MessageEntry::test("assistant", "2026-01-01T10:00:00Z", blocks);
+        if let Some(MessageContent::Structured { id: message_id, usage, .. }) = &mut entry.message {
+            *message_id = Some(id.to_string());
+            *usage = serde_json::from_value(serde_json::json!({ "input_tokens": 10, "output_tokens": 5 })).ok();
+        }
+        entry
+    }
+
+    #[test]
+    fn test_finished_turn_and_result() {
+        let entries = vec![
+            reply(
+                "m1",
+                serde_json::json!([{ "type": "tool_use", "id": "t1", "name": "Edit",
+                    "input": { "file_path": "/work/app/src/main.rs", "old_string": "a", "new_string": "b" } }]),
+            ),
+            MessageEntry::test("user", "2026-01-01T10:00:01Z", serde_json::json!([{ "type": "tool_result", "tool_use_id": "t1", "content": "ok" }])),
+            reply("m2", serde_json::json!([{ "type": "text", "text": "Added the flag." }])),
+            // Same response split into a second entry: tokens counted once
+            reply("m2", serde_json::json!([{ "type": "text", "text": "Tests pass." }])),
+        ];
+
+        let mut progress = SessionProgress::new(Path::new("s.jsonl"));
+        let finished: Vec<bool> = entries
+            .iter()
+            .map(|e| {
+                progress.observe(e);
+                progress.turn_finished
+            })
+            .collect();
+        assert_eq!(finished, [false, false, true, true]);
+
+        let result = agent_result(&entries, Some(0));
+        assert_eq!(result.final_message.as_deref(), Some("Tests pass."));
+        assert_eq!(result.files_changed, ["/work/app/src/main.rs"]);
+        assert_eq!((result.tokens.input, result.tokens.output), (20, 10));
+        assert_eq!(result.exit_code, Some(0));
+    }
+
+    #[test]
+    fn test_split_responses_count_once() {
+        let entries = [
+            reply("m1", serde_json::json!([{ "type": "thinking", "thinking": "..." }])),
+            reply("m1", serde_json::json!([{ "type": "text", "text": "Running it." }])),
+            reply("m1", serde_json::json!([{ "type": "tool_use", "id": "t1", "name": "Bash", "input": {} }])),
+            MessageEntry::test("user", "2026-01-01T10:00:01Z", serde_json::json!([{ "type": "tool_result", "tool_use_id": "t1", "content": "ok" }])),
+            reply("m2", serde_json::json!([{ "type": "text", "text": "Done." }])),
+        ];
+
+        let mut progress = SessionProgress::new(Path::new("s.jsonl"));
+        let responses = entries.iter().filter(|e| progress.starts_response(e)).count();
+        assert_eq!(responses, 2);
+    }
+
+    #[test]
+    fn test_stream_progress_renders_events() {
+        let log = [
+            r#"{"type":"system","subtype":"init","session_id":"s1","tools":["Bash"]}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Running the tests.\n"},{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"cargo test\ncargo clippy"}}]}}"#,
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#,
+            r#"{"type":"result","subtype":"success","is_error":false,"result":"All green."}"#,
+        ];
+
+        let mut progress = StreamProgress::new(Path::new("a.log"));
+        for line in log {
+            progress.observe(serde_json::from_str(line).unwrap());
+        }
+        assert_eq!(progress.session_id.as_deref(), Some("s1"));
+        assert_eq!((progress.message_count, progress.last_tool.as_deref()), (1, Some("Bash")));
+        assert_eq!(progress.lines, ["Running the tests.", "● Bash cargo test", "◆ Done"]);
+        assert_eq!(progress.result, Some((false, Some("All green.".to_string()))));
+    }
 }
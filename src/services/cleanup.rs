@@ -0,0 +1,481 @@
+//! Session pruning - select old, large or empty sessions and move them into
+//! compressed archives under the data directory, with restore.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::config::Config;
+use crate::models::{Agent, Session};
+use crate::services::SessionStore;
+
+/// Which sessions to clean up. Every criterion that is set must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CleanupFilter {
+    /// Last message more than this many days ago
+    pub older_than_days: Option<i64>,
+    /// Files (including sidechains) at least this many bytes
+    pub min_bytes: Option<u64>,
+    /// At most this many messages
+    pub max_messages: Option<usize>,
+    /// Only empty or aborted sessions (see `is_empty_session`)
+    pub empty_only: bool,
+}
+
+impl CleanupFilter {
+    /// Whether no criterion is set (which would select everything).
+    pub fn is_unbounded(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn matches(&self, candidate: &Candidate, now: DateTime<Utc>) -> bool {
+        let session = &candidate.session;
+        let old_enough = match self.older_than_days {
+            Some(days) => session.last_message < now - Duration::days(days),
+            None => true,
+        };
+        let large_enough = match self.min_bytes {
+            Some(bytes) => candidate.bytes >= bytes,
+            None => true,
+        };
+        let short_enough = match self.max_messages {
+            Some(max) => session.message_count <= max,
+            None => true,
+        };
+        old_enough && large_enough && short_enough && (!self.empty_only || is_empty_session(session))
+    }
+}
+
+/// A session that never got going: no prompt, or nothing after the first message.
+pub fn is_empty_session(session: &Session) -> bool {
+    session.preview_text.is_empty() || session.message_count <= 1
+}
+
+/// Sessions that live agents are using, which cleanup must not touch.
+#[derive(Debug, Default)]
+pub struct Protection {
+    /// Claude session IDs of live agents
+    session_ids: HashSet<String>,
+    /// Working directories of live agents whose session isn't known yet
+    dirs: Vec<PathBuf>,
+}
+
+impl Protection {
+    /// Protect the sessions of every live agent.
+    ///
+    /// Agents whose session isn't known yet protect every session in their
+    /// project directory and worktree instead.
+    pub fn from_agents(agents: &[Agent]) -> Self {
+        let mut protection = Self::default();
+        for agent in agents.iter().filter(|a| a.status.is_alive()) {
+            match &agent.claude_session_id {
+                Some(id) => {
+                    protection.session_ids.insert(id.clone());
+                }
+                None => {
+                    protection.dirs.push(agent.project_path.clone());
+                    protection.dirs.extend(agent.worktree_path.clone());
+                }
+            }
+        }
+        protection
+    }
+
+    /// Whether a live agent may be writing to this session.
+    pub fn covers(&self, session: &Session) -> bool {
+        self.session_ids.contains(&session.id)
+            || self.dirs.iter().any(|dir| Path::new(&session.project_path) == dir)
+    }
+}
+
+/// A session and every file that belongs to it.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub session: Session,
+    /// The JSONL file, sidechain files beside it and its `<id>/` directory
+    pub files: Vec<PathBuf>,
+    /// Combined size on disk
+    pub bytes: u64,
+}
+
+/// Gather the files of each session so they can be filtered and archived.
+pub fn candidates<'a>(store: &SessionStore, sessions: impl IntoIterator<Item = &'a Session>) -> Vec<Candidate> {
+    sessions
+        .into_iter()
+        .map(|session| {
+            let mut files = vec![session.file_path.clone()];
+            // Subagent transcripts and tool output live in `<project>/<id>/`
+            let session_dir = session.file_path.with_extension("");
+            if session_dir.is_dir() {
+                files.push(session_dir.clone());
+            }
+            for sidechain in store.sidechains_for(&session.id) {
+                if !sidechain.file_path.starts_with(&session_dir) {
+                    files.push(sidechain.file_path.clone());
+                }
+            }
+
+            let bytes = files.iter().map(|path| disk_usage(path)).sum();
+            Candidate {
+                session: session.clone(),
+                files,
+                bytes,
+            }
+        })
+        .collect()
+}
+
+/// Result of applying a filter: what would be archived, and what was held back.
+#[derive(Debug, Default)]
+pub struct CleanupPlan<'a> {
+    pub selected: Vec<&'a Candidate>,
+    /// Matched the filter but are in use by a live agent
+    pub protected: Vec<&'a Candidate>,
+}
+
+impl CleanupPlan<'_> {
+    /// Bytes that archiving the selection would free.
+    pub fn total_bytes(&self) -> u64 {
+        self.selected.iter().map(|c| c.bytes).sum()
+    }
+}
+
+/// Select the candidates matching `filter`. An unbounded filter selects nothing.
+pub fn plan<'a>(candidates: &'a [Candidate], filter: &CleanupFilter, protection: &Protection) -> CleanupPlan<'a> {
+    let mut plan = CleanupPlan::default();
+    if filter.is_unbounded() {
+        return plan;
+    }
+
+    let now = Utc::now();
+    for candidate in candidates.iter().filter(|c| filter.matches(c, now)) {
+        if protection.covers(&candidate.session) {
+            plan.protected.push(candidate);
+        } else {
+            plan.selected.push(candidate);
+        }
+    }
+    plan
+}
+
+/// Where cleanup archives are written.
+pub fn archive_dir() -> PathBuf {
+    Config::data_dir().join("archives")
+}
+
+/// Move the selected sessions into a new `.tar.gz` under `dir`, normally
+/// `archive_dir()`.
+///
+/// Paths inside the archive are relative to the projects directory. The
+/// originals are only removed once the archive is completely written.
+/// Refuses outright if any session is now in use by a live agent.
+pub fn archive(selected: &[&Candidate], protection: &Protection, projects_dir: &Path, dir: &Path) -> Result<PathBuf> {
+    if selected.is_empty() {
+        bail!("no sessions selected");
+    }
+    if let Some(candidate) = selected.iter().find(|c| protection.covers(&c.session)) {
+        bail!("session {} is in use by a live agent", candidate.session.id);
+    }
+
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let mut path = dir.join(format!("sessions-{}.tar.gz", stamp));
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = dir.join(format!("sessions-{}-{}.tar.gz", stamp, n));
+    }
+    let partial = path.with_extension("gz.partial");
+
+    let write = || -> Result<()> {
+        let file = File::create(&partial).with_context(|| format!("Failed to create {:?}", partial))?;
+        let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        for candidate in selected {
+            for file in &candidate.files {
+                let name = file
+                    .strip_prefix(projects_dir)
+                    .with_context(|| format!("{:?} is outside the projects directory", file))?;
+                if file.is_dir() {
+                    tar.append_dir_all(name, file)
+                } else {
+                    tar.append_path_with_name(file, name)
+                }
+                .with_context(|| format!("Failed to archive {:?}", file))?;
+            }
+        }
+        let file = tar.into_inner()?.finish()?;
+        file.sync_all()?;
+        Ok(())
+    };
+    if let Err(e) = write() {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    fs::rename(&partial, &path).with_context(|| format!("Failed to finalize {:?}", path))?;
+
+    for file in selected.iter().flat_map(|c| &c.files) {
+        let removed = if file.is_dir() {
+            fs::remove_dir_all(file)
+        } else {
+            fs::remove_file(file)
+        };
+        if let Err(e) = removed {
+            tracing::warn!("Archived {:?} but failed to remove it: {}", file, e);
+        }
+    }
+
+    Ok(path)
+}
+
+/// Outcome of restoring an archive.
+#[derive(Debug, Default)]
+pub struct RestoreReport {
+    /// Files written back into the projects directory
+    pub restored: Vec<PathBuf>,
+    /// Files left alone because something already exists at their path
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Unpack an archive made by `archive` back into the projects directory.
+///
+/// Never overwrites existing files. The archive is deleted once every file
+/// has been restored, and kept otherwise.
+pub fn restore(archive: &Path, projects_dir: &Path) -> Result<RestoreReport> {
+    let file = File::open(archive).with_context(|| format!("Failed to open {:?}", archive))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+    let mut report = RestoreReport::default();
+
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.into_owned();
+        if !name.components().all(|c| matches!(c, Component::Normal(_))) {
+            bail!("{:?} contains an unsafe path {:?}", archive, name);
+        }
+        let target = projects_dir.join(&name);
+        if entry.header().entry_type().is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if target.exists() {
+            report.skipped.push(target);
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        entry
+            .unpack(&target)
+            .with_context(|| format!("Failed to restore {:?}", target))?;
+        report.restored.push(target);
+    }
+
+    if report.skipped.is_empty() {
+        fs::remove_file(archive).with_context(|| format!("Failed to remove {:?}", archive))?;
+    }
+    Ok(report)
+}
+
+/// A cleanup archive on disk.
+#[derive(Debug, Clone)]
+pub struct ArchiveInfo {
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub created: DateTime<Local>,
+}
+
+/// Existing archives, newest first.
+pub fn list_archives() -> Result<Vec<ArchiveInfo>> {
+    let dir = archive_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut archives = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if !path.to_string_lossy().ends_with(".tar.gz") {
+            continue;
+        }
+        let meta = fs::metadata(&path)?;
+        archives.push(ArchiveInfo {
+            bytes: meta.len(),
+            created: meta.modified().map(DateTime::from).unwrap_or_else(|_| Local::now()),
+            path,
+        });
+    }
+    archives.sort_by_key(|a| std::cmp::Reverse(a.created));
+    Ok(archives)
+}
+
+/// Find an archive by path or by file name in `archive_dir()`.
+pub fn find_archive(name: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(name);
+    if path.is_file() {
+        return Ok(path);
+    }
+    let dir = archive_dir();
+    [dir.join(name), dir.join(format!("{}.tar.gz", name))]
+        .into_iter()
+        .find(|p| p.is_file())
+        .with_context(|| format!("No archive named {:?} in {:?}", name, dir))
+}
+
+/// Parse a size like `500K`, `20M` or `1G` (binary units) into bytes.
+pub fn parse_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let split = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let (digits, unit) = input.split_at(split);
+    let number: u64 = digits.parse().map_err(|_| format!("invalid size {:?}", input))?;
+    let multiplier = match unit.trim().to_ascii_uppercase().trim_end_matches(['B', 'I']) {
+        "" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => return Err(format!("unknown size unit in {:?}", input)),
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("size {:?} is too large", input))
+}
+
+/// Format a byte count for display, e.g. `12.3 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Size of a file, or of everything under a directory.
+fn disk_usage(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512"), Ok(512));
+        assert_eq!(parse_size("20M"), Ok(20 << 20));
+        assert_eq!(parse_size("1gb"), Ok(1 << 30));
+        assert_eq!(parse_size("3KiB"), Ok(3 << 10));
+        assert!(parse_size("big").is_err());
+        assert!(parse_size("5T").is_err());
+        assert!(parse_size("99999999999999999999").is_err());
+        assert!(parse_size("18014398509481984G").is_err());
+    }
+
+    fn candidate(projects: &Path, id: &str) -> Candidate {
+        let file_path = projects.join(format!("-tmp-app/{}.jsonl", id));
+        let session_dir = file_path.with_extension("");
+        fs::create_dir_all(session_dir.join("subagents")).unwrap();
+        fs::write(&file_path, "{}\n").unwrap();
+        fs::write(session_dir.join("subagents/agent-1.jsonl"), "{}\n").unwrap();
+        Candidate {
+            session: Session {
+                project_path: "/tmp/app".to_string(),
+                file_path: file_path.clone(),
+                ..Session::test(id, None, "2026-01-01T10:00:00Z")
+            },
+            files: vec![file_path, session_dir],
+            bytes: 6,
+        }
+    }
+
+    #[test]
+    fn test_archive_restore_roundtrip() {
+        let tmp = std::env::temp_dir().join(format!("tr-cleanup-{}", uuid::Uuid::new_v4()));
+        let projects = tmp.join("projects");
+        let abc = candidate(&projects, "abc");
+
+        let archive = archive(&[&abc], &Protection::default(), &projects, &tmp.join("archives")).unwrap();
+        assert!(archive.starts_with(tmp.join("archives")));
+        assert!(!abc.files.iter().any(|f| f.exists()), "originals are removed");
+
+        let report = restore(&archive, &projects).unwrap();
+        assert_eq!(report.restored.len(), 2);
+        assert!(report.skipped.is_empty());
+        assert_eq!(fs::read_to_string(projects.join("-tmp-app/abc/subagents/agent-1.jsonl")).unwrap(), "{}\n");
+        assert!(!archive.exists(), "archive is removed once fully restored");
+
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+
+    #[test]
+    fn test_protection_covers_headless_agents_without_session() {
+        let agent = Agent {
+            worktree_path: Some(PathBuf::from("/work/app-wt")),
+            ..Agent::test("a", crate::models::AgentStatus::Starting)
+        };
+        let dead = Agent {
+            project_path: PathBuf::from("/work/other"),
+            ..Agent::test("b", crate::models::AgentStatus::Failed)
+        };
+        let protection = Protection::from_agents(&[agent, dead]);
+
+        let in_dir = |dir: &str| Session {
+            project_path: dir.to_string(),
+            ..Session::test("abc", None, "2026-01-01T10:00:00Z")
+        };
+        assert!(protection.covers(&in_dir("/work/app")));
+        assert!(protection.covers(&in_dir("/work/app-wt")));
+        assert!(!protection.covers(&in_dir("/work/other")));
+    }
+
+    #[test]
+    fn test_archive_refuses_protected_sessions() {
+        let tmp = std::env::temp_dir().join(format!("tr-cleanup-{}", uuid::Uuid::new_v4()));
+        let projects = tmp.join("projects");
+        let abc = candidate(&projects, "abc");
+        let agent = Agent {
+            claude_session_id: Some("abc".to_string()),
+            ..Agent::test("a", crate::models::AgentStatus::Active)
+        };
+
+        let result = archive(&[&abc], &Protection::from_agents(&[agent]), &projects, &tmp.join("archives"));
+        assert!(result.is_err());
+        assert!(abc.files.iter().all(|f| f.exists()));
+        assert!(!tmp.join("archives").exists());
+
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+
+    #[test]
+    fn test_restore_skips_existing_files() {
+        let tmp = std::env::temp_dir().join(format!("tr-cleanup-{}", uuid::Uuid::new_v4()));
+        let projects = tmp.join("projects");
+        let abc = candidate(&projects, "abc");
+        let archive = archive(&[&abc], &Protection::default(), &projects, &tmp.join("archives")).unwrap();
+
+        // The session came back some other way; only the subagent is missing
+        fs::write(&abc.files[0], "{}\n").unwrap();
+        let report = restore(&archive, &projects).unwrap();
+        assert_eq!(report.restored, vec![projects.join("-tmp-app/abc/subagents/agent-1.jsonl")]);
+        assert_eq!(report.skipped, vec![abc.files[0].clone()]);
+        assert!(archive.exists(), "archive is kept while files were skipped");
+
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+//! Clipboard access: the system clipboard when a display server is
+//! available, OSC 52 terminal escapes otherwise (e.g. over SSH).
+
+use anyhow::Result;
+use std::io::Write;
+
+/// How a copy reached the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyMethod {
+    System,
+    /// Asked the terminal to set it; whether it did depends on the terminal
+    Osc52,
+}
+
+/// Clipboard handle, kept for the lifetime of the app.
+///
+/// On X11 the copied text is served by this process, so dropping the
+/// handle early would empty the clipboard again.
+#[derive(Default)]
+pub struct Clipboard {
+    /// `None` until first use; `Some(None)` when there is no system clipboard
+    system: Option<Option<arboard::Clipboard>>,
+}
+
+impl Clipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy `text`, falling back to OSC 52 when the system clipboard is unavailable.
+    pub fn copy(&mut self, text: &str) -> Result<CopyMethod> {
+        let system = self.system.get_or_insert_with(|| {
+            if !has_display() {
+                return None;
+            }
+            arboard::Clipboard::new()
+                .map_err(|e| tracing::warn!("System clipboard unavailable: {}", e))
+                .ok()
+        });
+
+        if let Some(clipboard) = system {
+            match clipboard.set_text(text) {
+                Ok(()) => return Ok(CopyMethod::System),
+                Err(e) => tracing::warn!("Failed to set system clipboard, using OSC 52: {}", e),
+            }
+        }
+        write_osc52(&mut std::io::stdout(), text)?;
+        Ok(CopyMethod::Osc52)
+    }
+}
+
+/// Whether a display server is reachable (always true off Linux/BSD).
+fn has_display() -> bool {
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        return true;
+    }
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+}
+
+/// Write an OSC 52 "set clipboard" sequence for `text`.
+///
+/// Inside tmux the sequence is also sent wrapped for passthrough, since
+/// tmux only forwards bare OSC 52 when `set-clipboard` is `on`.
+fn write_osc52(out: &mut impl Write, text: &str) -> Result<()> {
+    let sequence = format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+    out.write_all(sequence.as_bytes())?;
+    if std::env::var_os("TMUX").is_some() {
+        out.write_all(format!("\x1bPtmux;\x1b{}\x1b\\", sequence).as_bytes())?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Standard base64 with padding.
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len() * 4 / 3 + 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode("claude --resume ✓".as_bytes()), "Y2xhdWRlIC0tcmVzdW1lIOKckw==");
+    }
+}
@@ -0,0 +1,545 @@
+//! Session export to Markdown, self-contained HTML and normalized JSON.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::models::message::tool_result_text;
+use crate::models::{ContentBlock, MessageContent, MessageEntry, Session};
+use crate::services::SessionStore;
+use crate::utils::local_time;
+
+/// Output format for an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    #[value(alias = "md")]
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    /// File extension for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// What to include in an export.
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// Include the model's thinking blocks
+    pub include_thinking: bool,
+    /// Include tool calls and their results
+    pub include_tools: bool,
+    /// Truncate tool results longer than this many characters
+    pub max_tool_result_chars: Option<usize>,
+    /// Replace the home directory with `~` everywhere
+    pub redact_home: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            include_thinking: false,
+            include_tools: true,
+            max_tool_result_chars: Some(2_000),
+            redact_home: true,
+        }
+    }
+}
+
+/// One conversation turn after normalization.
+#[derive(Debug, Serialize)]
+struct ExportMessage {
+    role: String,
+    timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    blocks: Vec<ExportBlock>,
+    /// API response ID, used to merge split entries
+    #[serde(skip)]
+    response_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ExportBlock {
+    Text {
+        text: String,
+    },
+    Thinking {
+        text: String,
+    },
+    ToolUse {
+        name: String,
+        input: serde_json::Value,
+    },
+    ToolResult {
+        name: String,
+        content: String,
+        is_error: bool,
+        truncated: bool,
+    },
+}
+
+/// Session header fields for the JSON export.
+#[derive(Debug, Serialize)]
+struct ExportSession {
+    id: String,
+    name: String,
+    project_path: String,
+    git_branch: Option<String>,
+    first_message: DateTime<Utc>,
+    last_message: DateTime<Utc>,
+    message_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportDocument {
+    session: ExportSession,
+    messages: Vec<ExportMessage>,
+}
+
+/// Render a session transcript in the given format.
+pub fn export(
+    session: &Session,
+    entries: &[MessageEntry],
+    format: ExportFormat,
+    options: &ExportOptions,
+) -> String {
+    let redactor = Redactor::new(options.redact_home);
+    let document = ExportDocument {
+        session: ExportSession {
+            id: session.id.clone(),
+            name: session.display_name(),
+            project_path: redactor.text(&session.project_path),
+            git_branch: session.git_branch.clone(),
+            first_message: session.first_message,
+            last_message: session.last_message,
+            message_count: session.message_count,
+        },
+        messages: normalize(entries, options, &redactor),
+    };
+
+    match format {
+        ExportFormat::Markdown => render_markdown(&document),
+        ExportFormat::Html => render_html(&document),
+        ExportFormat::Json => serde_json::to_string_pretty(&document).unwrap_or_default(),
+    }
+}
+
+/// Export a session to `<data dir>/exports/<name>.<ext>`, returning the path.
+///
+/// The name is the slug followed by the start of the session ID, since
+/// resumed sessions share their slug.
+pub fn export_to_file(session: &Session, format: ExportFormat, options: &ExportOptions) -> Result<PathBuf> {
+    let entries = SessionStore::load_entries(&session.file_path)?;
+    let dir = Config::data_dir().join("exports");
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {:?}", dir))?;
+
+    let short_id: String = session.id.chars().take(8).collect();
+    let name = match &session.slug {
+        Some(slug) => format!("{}-{}", slug, short_id),
+        None => session.id.clone(),
+    };
+    let path = dir.join(format!("{}.{}", name, format.extension()));
+    std::fs::write(&path, export(session, &entries, format, options))
+        .with_context(|| format!("Failed to write {:?}", path))?;
+    Ok(path)
+}
+
+/// Turn raw entries into conversation turns.
+///
+/// Entries split from one API response are merged, and tool results (which
+/// arrive as "user" entries) are attached to the turn that made the call.
+fn normalize(entries: &[MessageEntry], options: &ExportOptions, redactor: &Redactor) -> Vec<ExportMessage> {
+    let mut messages: Vec<ExportMessage> = Vec::new();
+    let mut tool_names: HashMap<String, String> = HashMap::new();
+
+    for entry in entries {
+        let Some(message) = &entry.message else {
+            continue;
+        };
+
+        let mut blocks = Vec::new();
+        let mut only_tool_results = true;
+        match message {
+            MessageContent::Simple { content, .. } => {
+                only_tool_results = false;
+                blocks.push(ExportBlock::Text { text: redactor.text(content) });
+            }
+            MessageContent::Structured { content, .. } => {
+                for block in content {
+                    match block {
+                        ContentBlock::Text { text } => {
+                            only_tool_results = false;
+                            if !text.trim().is_empty() {
+                                blocks.push(ExportBlock::Text { text: redactor.text(text) });
+                            }
+                        }
+                        ContentBlock::Thinking { thinking } => {
+                            only_tool_results = false;
+                            if options.include_thinking && !thinking.trim().is_empty() {
+                                blocks.push(ExportBlock::Thinking { text: redactor.text(thinking) });
+                            }
+                        }
+                        ContentBlock::ToolUse { id, name, input } => {
+                            only_tool_results = false;
+                            tool_names.insert(id.clone(), name.clone());
+                            if options.include_tools {
+                                blocks.push(ExportBlock::ToolUse {
+                                    name: name.clone(),
+                                    input: redactor.json(input),
+                                });
+                            }
+                        }
+                        ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+                            if options.include_tools {
+                                let (content, truncated) =
+                                    truncate_chars(&tool_result_text(content), options.max_tool_result_chars);
+                                blocks.push(ExportBlock::ToolResult {
+                                    name: tool_names.get(tool_use_id).cloned().unwrap_or_default(),
+                                    content: redactor.text(&content),
+                                    is_error: *is_error,
+                                    truncated,
+                                });
+                            }
+                        }
+                        ContentBlock::Unknown => {}
+                    }
+                }
+            }
+        }
+
+        let response_id = message.id().map(str::to_string);
+        if let Some(previous) = messages.last_mut() {
+            let continues_response = response_id.is_some() && response_id == previous.response_id;
+            let is_tool_result_turn = only_tool_results && message.role() == "user";
+            if continues_response || is_tool_result_turn {
+                previous.blocks.extend(blocks);
+                continue;
+            }
+        }
+        if blocks.is_empty() {
+            continue;
+        }
+        messages.push(ExportMessage {
+            role: message.role().to_string(),
+            timestamp: entry.timestamp,
+            model: message.model().map(str::to_string),
+            blocks,
+            response_id,
+        });
+    }
+
+    messages
+}
+
+/// Truncate to `max` characters (if set), reporting whether anything was cut.
+fn truncate_chars(text: &str, max: Option<usize>) -> (String, bool) {
+    match max {
+        Some(max) if text.chars().count() > max => (text.chars().take(max).collect(), true),
+        _ => (text.to_string(), false),
+    }
+}
+
+/// Replaces the user's home directory with `~`.
+struct Redactor {
+    home: Option<String>,
+}
+
+impl Redactor {
+    fn new(enabled: bool) -> Self {
+        let home = enabled
+            .then(dirs::home_dir)
+            .flatten()
+            .map(|h| h.to_string_lossy().trim_end_matches('/').to_string())
+            .filter(|h| !h.is_empty());
+        Self { home }
+    }
+
+    fn text(&self, text: &str) -> String {
+        match &self.home {
+            Some(home) => redact_prefix(text, home),
+            None => text.to_string(),
+        }
+    }
+
+    fn json(&self, value: &serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+        match value {
+            Value::String(s) => Value::String(self.text(s)),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.json(v)).collect()),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), self.json(v)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+/// Replace `home` with `~` wherever it appears as a whole path prefix
+/// (so `/home/me` doesn't rewrite `/home/meg`).
+fn redact_prefix(text: &str, home: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(home) {
+        let after = &rest[pos + home.len()..];
+        let boundary = match after.chars().next() {
+            Some(c) => !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'),
+            None => true,
+        };
+        out.push_str(&rest[..pos]);
+        out.push_str(if boundary { "~" } else { home });
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+fn role_label(role: &str) -> &'static str {
+    match role {
+        "assistant" => "Assistant",
+        "user" => "User",
+        _ => "System",
+    }
+}
+
+/// A code fence longer than any backtick run in `text`.
+pub fn fence_for(text: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn pretty_json(value: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn render_markdown(doc: &ExportDocument) -> String {
+    let s = &doc.session;
+    let mut out = format!("# {}\n\n", s.name);
+    out.push_str(&format!("- **Project:** `{}`\n", s.project_path));
+    out.push_str(&format!("- **Session:** `{}`\n", s.id));
+    if let Some(branch) = &s.git_branch {
+        out.push_str(&format!("- **Branch:** `{}`\n", branch));
+    }
+    out.push_str(&format!(
+        "- **Time:** {} – {}\n",
+        local_time(s.first_message),
+        local_time(s.last_message)
+    ));
+    out.push_str("\n---\n");
+
+    for message in &doc.messages {
+        if !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "## {} · {}\n\n",
+            role_label(&message.role),
+            message.timestamp.with_timezone(&Local).format("%H:%M")
+        ));
+        for block in &message.blocks {
+            match block {
+                ExportBlock::Text { text } => {
+                    out.push_str(text.trim());
+                    out.push_str("\n\n");
+                }
+                ExportBlock::Thinking { text } => {
+                    out.push_str("<details><summary>Thinking</summary>\n\n");
+                    for line in text.trim().lines() {
+                        out.push_str(&format!("> {}\n", line));
+                    }
+                    out.push_str("\n</details>\n\n");
+                }
+                ExportBlock::ToolUse { name, input } => {
+                    let body = pretty_json(input);
+                    let fence = fence_for(&body);
+                    out.push_str(&format!(
+                        "<details><summary>Tool: {}</summary>\n\n{}json\n{}\n{}\n\n</details>\n\n",
+                        name, fence, body, fence
+                    ));
+                }
+                ExportBlock::ToolResult { name, content, is_error, truncated } => {
+                    let label = if *is_error { "Error" } else { "Result" };
+                    let fence = fence_for(content);
+                    let note = if *truncated { "\n\n*(truncated)*" } else { "" };
+                    out.push_str(&format!(
+                        "<details><summary>{}: {}</summary>\n\n{}\n{}\n{}{}\n\n</details>\n\n",
+                        label,
+                        name,
+                        fence,
+                        content.trim_end(),
+                        fence,
+                        note
+                    ));
+                }
+            }
+        }
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = "\
+body{font-family:system-ui,sans-serif;max-width:50rem;margin:2rem auto;padding:0 1rem;color:#222;background:#fafafa}\
+header{border-bottom:1px solid #ddd;margin-bottom:1.5rem}\
+header dl{display:grid;grid-template-columns:max-content 1fr;gap:.25rem 1rem;color:#555}\
+section{margin:1rem 0;padding:.75rem 1rem;border-radius:6px;background:#fff;border:1px solid #e4e4e4}\
+section.user{border-left:4px solid #2a7ae2}\
+section.assistant{border-left:4px solid #9b59b6}\
+h2{font-size:.9rem;margin:0 0 .5rem;color:#666}\
+.text{white-space:pre-wrap}\
+pre{background:#f3f3f3;padding:.5rem;overflow-x:auto;white-space:pre-wrap;word-break:break-word}\
+details{margin:.5rem 0}summary{cursor:pointer;color:#555}\
+.error summary{color:#c0392b}.thinking{color:#777;font-style:italic}";
+
+fn render_html(doc: &ExportDocument) -> String {
+    let s = &doc.session;
+    let mut out = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape_html(&s.name)));
+    out.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", HTML_STYLE));
+
+    out.push_str(&format!("<header>\n<h1>{}</h1>\n<dl>\n", escape_html(&s.name)));
+    let mut field = |label: &str, value: &str| {
+        out.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", label, escape_html(value)));
+    };
+    field("Project", &s.project_path);
+    field("Session", &s.id);
+    if let Some(branch) = &s.git_branch {
+        field("Branch", branch);
+    }
+    field(
+        "Time",
+        &format!("{} – {}", local_time(s.first_message), local_time(s.last_message)),
+    );
+    out.push_str("</dl>\n</header>\n");
+
+    for message in &doc.messages {
+        out.push_str(&format!(
+            "<section class=\"{}\">\n<h2>{} · {}</h2>\n",
+            escape_html(&message.role),
+            role_label(&message.role),
+            message.timestamp.with_timezone(&Local).format("%H:%M")
+        ));
+        for block in &message.blocks {
+            match block {
+                ExportBlock::Text { text } => {
+                    out.push_str(&format!("<div class=\"text\">{}</div>\n", escape_html(text.trim())));
+                }
+                ExportBlock::Thinking { text } => {
+                    out.push_str(&format!(
+                        "<details class=\"thinking\"><summary>Thinking</summary><div class=\"text\">{}</div></details>\n",
+                        escape_html(text.trim())
+                    ));
+                }
+                ExportBlock::ToolUse { name, input } => {
+                    out.push_str(&format!(
+                        "<details><summary>Tool: {}</summary><pre>{}</pre></details>\n",
+                        escape_html(name),
+                        escape_html(&pretty_json(input))
+                    ));
+                }
+                ExportBlock::ToolResult { name, content, is_error, truncated } => {
+                    let (class, label) = if *is_error { (" class=\"error\"", "Error") } else { ("", "Result") };
+                    let note = if *truncated { "<p><em>(truncated)</em></p>" } else { "" };
+                    out.push_str(&format!(
+                        "<details{}><summary>{}: {}</summary><pre>{}</pre>{}</details>\n",
+                        class,
+                        label,
+                        escape_html(name),
+                        escape_html(content.trim_end()),
+                        note
+                    ));
+                }
+            }
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(lines: &[&str]) -> Vec<MessageEntry> {
+        lines.iter().map(|l| serde_json::from_str(l).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_normalize_merges_responses_and_tool_results() {
+        let entries = entries(&[
+            r#"{"uuid":"1","sessionId":"s","timestamp":"2025-01-01T10:00:00Z","type":"user","message":{"role":"user","content":"list files in /home/me/proj"}}"#,
+            r#"{"uuid":"2","sessionId":"s","timestamp":"2025-01-01T10:00:01Z","type":"assistant","message":{"role":"assistant","id":"m1","content":[{"type":"thinking","thinking":"hmm"}]}}"#,
+            r#"{"uuid":"3","sessionId":"s","timestamp":"2025-01-01T10:00:02Z","type":"assistant","message":{"role":"assistant","id":"m1","content":[{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"ls /home/me/proj"}}]}}"#,
+            r#"{"uuid":"4","sessionId":"s","timestamp":"2025-01-01T10:00:03Z","type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"a.rs\nb.rs\nc.rs"}]}}"#,
+        ]);
+        let redactor = Redactor { home: Some("/home/me".to_string()) };
+        let options = ExportOptions {
+            max_tool_result_chars: Some(4),
+            ..ExportOptions::default()
+        };
+
+        let messages = normalize(&entries, &options, &redactor);
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(&messages[0].blocks[0], ExportBlock::Text { text } if text == "list files in ~/proj"));
+
+        // Thinking is excluded by default; the tool call and its result share the turn
+        let blocks = &messages[1].blocks;
+        assert_eq!(blocks.len(), 2);
+        assert!(matches!(&blocks[0], ExportBlock::ToolUse { input, .. } if input["command"] == "ls ~/proj"));
+        assert!(matches!(
+            &blocks[1],
+            ExportBlock::ToolResult { name, content, truncated: true, .. } if name == "Bash" && content == "a.rs"
+        ));
+    }
+
+    #[test]
+    fn test_redact_prefix_only_whole_paths() {
+        assert_eq!(redact_prefix("/home/me and /home/meg", "/home/me"), "~ and /home/meg");
+        assert_eq!(redact_prefix("cd /home/me/src", "/home/me"), "cd ~/src");
+    }
+
+    #[test]
+    fn test_fence_longer_than_content() {
+        assert_eq!(fence_for("no ticks"), "```");
+        assert_eq!(fence_for("has ```` four"), "`````");
+    }
+}
@@ -0,0 +1,352 @@
+//! Files a session touched, reconstructed from its tool calls.
+//!
+//! Edit, MultiEdit, Write and NotebookEdit inputs carry the file and the
+//! text changed. Bash commands are scanned for the usual file-writing
+//! commands and redirections, which is a best guess.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::models::{ContentBlock, MessageContent, MessageEntry};
+
+/// Tool that changed a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Edit,
+    Write,
+    NotebookEdit,
+    Bash,
+}
+
+impl ChangeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeKind::Edit => "Edit",
+            ChangeKind::Write => "Write",
+            ChangeKind::NotebookEdit => "NotebookEdit",
+            ChangeKind::Bash => "Bash",
+        }
+    }
+}
+
+/// One change to a file.
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    pub kind: ChangeKind,
+    pub timestamp: DateTime<Utc>,
+    /// Replaced text (empty for writes and commands)
+    pub old: String,
+    /// New text, or the command for `Bash`
+    pub new: String,
+}
+
+/// A file and every change the session made to it, oldest first.
+#[derive(Debug, Clone)]
+pub struct TouchedFile {
+    /// Absolute path
+    pub path: String,
+    pub changes: Vec<FileChange>,
+}
+
+impl TouchedFile {
+    /// Number of changes made with `kind`.
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+
+    /// Lines added and removed over all edits and writes.
+    pub fn line_counts(&self) -> (usize, usize) {
+        self.changes
+            .iter()
+            .filter(|c| c.kind != ChangeKind::Bash)
+            .flat_map(|c| diff_lines(&c.old, &c.new))
+            .fold((0, 0), |(added, removed), line| match line.tag {
+                DiffTag::Added => (added + 1, removed),
+                DiffTag::Removed => (added, removed + 1),
+                DiffTag::Context => (added, removed),
+            })
+    }
+
+    /// Path relative to `project_path` when inside it.
+    pub fn display_path(&self, project_path: &str) -> String {
+        match Path::new(&self.path).strip_prefix(project_path) {
+            Ok(rel) if !rel.as_os_str().is_empty() => rel.display().to_string(),
+            _ => self.path.clone(),
+        }
+    }
+}
+
+/// Whether a hunk line is kept, removed or added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffTag {
+    Context,
+    Removed,
+    Added,
+}
+
+/// One line of a reconstructed hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub tag: DiffTag,
+    pub text: String,
+}
+
+/// Lines shared by the old and new text kept around a hunk.
+const CONTEXT_LINES: usize = 2;
+
+/// Collect the files a session changed, in the order it first touched them.
+///
+/// Tool calls whose result was an error changed nothing and are skipped.
+pub fn files_touched(entries: &[MessageEntry]) -> Vec<TouchedFile> {
+    let failed: HashSet<&str> = entries
+        .iter()
+        .flat_map(blocks)
+        .filter_map(|block| match block {
+            ContentBlock::ToolResult { tool_use_id, is_error: true, .. } => Some(tool_use_id.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let mut files: Vec<TouchedFile> = Vec::new();
+    let mut record = |path: String, change: FileChange| match files.iter_mut().find(|f| f.path == path) {
+        Some(file) => file.changes.push(change),
+        None => files.push(TouchedFile {
+            path,
+            changes: vec![change],
+        }),
+    };
+
+    for entry in entries {
+        for block in blocks(entry) {
+            let ContentBlock::ToolUse { id, name, input } = block else {
+                continue;
+            };
+            if failed.contains(id.as_str()) {
+                continue;
+            }
+            let change = |kind, old: &str, new: &str| FileChange {
+                kind,
+                timestamp: entry.timestamp,
+                old: old.to_string(),
+                new: new.to_string(),
+            };
+            let text = |key: &str| input.get(key).and_then(Value::as_str).unwrap_or_default();
+
+            match name.as_str() {
+                "Edit" => record(text("file_path").to_string(), change(ChangeKind::Edit, text("old_string"), text("new_string"))),
+                "MultiEdit" => {
+                    let edits = input.get("edits").and_then(Value::as_array).cloned().unwrap_or_default();
+                    for edit in edits {
+                        let field = |key: &str| edit.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+                        record(
+                            text("file_path").to_string(),
+                            change(ChangeKind::Edit, &field("old_string"), &field("new_string")),
+                        );
+                    }
+                }
+                "Write" => record(text("file_path").to_string(), change(ChangeKind::Write, "", text("content"))),
+                "NotebookEdit" => record(
+                    text("notebook_path").to_string(),
+                    change(ChangeKind::NotebookEdit, "", text("new_source")),
+                ),
+                "Bash" => {
+                    let command = text("command");
+                    let cwd = entry.cwd.as_deref().unwrap_or("/");
+                    for path in bash_targets(command) {
+                        record(absolute(cwd, &path), change(ChangeKind::Bash, "", command));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    files.retain(|f| !f.path.is_empty());
+    files
+}
+
+fn blocks(entry: &MessageEntry) -> &[ContentBlock] {
+    match &entry.message {
+        Some(MessageContent::Structured { content, .. }) => content,
+        _ => &[],
+    }
+}
+
+fn absolute(cwd: &str, path: &str) -> String {
+    Path::new(cwd).join(path).display().to_string()
+}
+
+/// Files a shell command likely writes, moves or deletes.
+///
+/// Understands `rm`, `mv`, `cp`, `touch`, `tee`, `sed -i`, `git rm`/`git mv`
+/// and `>`/`>>` redirections. Globs and variables can't be resolved and are
+/// left out.
+fn bash_targets(command: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    let segments = command.split([';', '|', '&', '\n']);
+
+    for segment in segments {
+        let words: Vec<String> = segment
+            .split_whitespace()
+            .map(|w| w.trim_matches(['"', '\'']).to_string())
+            .collect();
+
+        // Redirections anywhere in the segment
+        let mut args = Vec::new();
+        let mut iter = words.iter();
+        while let Some(word) = iter.next() {
+            let target = match word.trim_start_matches(|c: char| c.is_ascii_digit()) {
+                ">" | ">>" => iter.next().cloned(),
+                w if w.starts_with('>') => Some(w.trim_start_matches('>').to_string()),
+                _ => {
+                    args.push(word.as_str());
+                    continue;
+                }
+            };
+            targets.extend(target.filter(|t| !t.starts_with('&') && t != "/dev/null"));
+        }
+
+        // Skip `sudo` and leading VAR=value assignments
+        let start = args
+            .iter()
+            .position(|w| *w != "sudo" && (!w.contains('=') || w.starts_with('-')))
+            .unwrap_or(args.len());
+        let args = &args[start..];
+        let Some((program, rest)) = args.split_first() else {
+            continue;
+        };
+        let operands: Vec<&str> = rest.iter().copied().filter(|w| !w.starts_with('-')).collect();
+
+        let files: Vec<String> = match *program {
+            "rm" | "touch" | "tee" | "truncate" => operands.iter().map(|s| s.to_string()).collect(),
+            "mv" => copy_targets(&operands, true),
+            "cp" => copy_targets(&operands, false),
+            "sed" if rest.iter().any(|w| w.starts_with("-i")) => {
+                operands.iter().skip(1).map(|s| s.to_string()).collect()
+            }
+            "git" => match operands.split_first() {
+                Some((&"rm", paths)) => paths.iter().map(|s| s.to_string()).collect(),
+                Some((&"mv", paths)) => copy_targets(paths, true),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        targets.extend(files);
+    }
+
+    // Directories (`rm -r build/`) aren't files
+    targets.retain(|t| !t.is_empty() && !t.ends_with('/') && !t.contains(['*', '?', '$', '{', '`']));
+    let mut seen = HashSet::new();
+    targets.retain(|t| seen.insert(t.clone()));
+    targets
+}
+
+/// Files written by `cp`/`mv` operands: the destination, or each source's
+/// name inside it when it is a directory (`cp a.rs dest/`), plus the moved
+/// sources themselves.
+fn copy_targets(operands: &[&str], moves: bool) -> Vec<String> {
+    let Some((dest, sources)) = operands.split_last() else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = if moves {
+        sources.iter().map(|s| s.to_string()).collect()
+    } else {
+        Vec::new()
+    };
+    if dest.ends_with('/') {
+        files.extend(sources.iter().map(|source| {
+            let name = source.trim_end_matches('/').rsplit('/').next().unwrap_or(source);
+            format!("{}{}", dest, name)
+        }));
+    } else {
+        files.push(dest.to_string());
+    }
+    files
+}
+
+/// Reconstruct a hunk from replaced and replacement text.
+///
+/// Lines shared at the start and end become context, trimmed to a couple
+/// of lines each side.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let line = |tag, text: &&str| DiffLine {
+        tag,
+        text: text.to_string(),
+    };
+    let mut lines: Vec<DiffLine> = old[prefix.saturating_sub(CONTEXT_LINES)..prefix]
+        .iter()
+        .map(|t| line(DiffTag::Context, t))
+        .collect();
+    lines.extend(old[prefix..old.len() - suffix].iter().map(|t| line(DiffTag::Removed, t)));
+    lines.extend(new[prefix..new.len() - suffix].iter().map(|t| line(DiffTag::Added, t)));
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .take(CONTEXT_LINES)
+            .map(|t| line(DiffTag::Context, t)),
+    );
+    lines
+}
+
+/// 1-based line in `path` where `text` currently starts, to open an editor there.
+pub fn find_line(path: &str, text: &str) -> Option<usize> {
+    let first = text.lines().find(|l| !l.trim().is_empty())?;
+    let contents = std::fs::read_to_string(path).ok()?;
+    contents.lines().position(|l| l.contains(first)).map(|i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_touched_skips_failed_calls() {
+        let entries = vec![
+            MessageEntry::test("assistant", "2026-01-01T00:00:00Z", serde_json::json!([
+                { "type": "tool_use", "id": "1", "name": "Edit",
+                  "input": { "file_path": "/work/app/src/lib.rs", "old_string": "a\nb\nc", "new_string": "a\nB\nc" } },
+                { "type": "tool_use", "id": "2", "name": "Write",
+                  "input": { "file_path": "/work/app/README.md", "content": "hi" } },
+                { "type": "tool_use", "id": "3", "name": "Bash",
+                  "input": { "command": "cargo fmt && rm -f old.txt > /dev/null 2>&1" } },
+            ])),
+            MessageEntry::test("user", "2026-01-01T00:00:01Z", serde_json::json!([
+                { "type": "tool_result", "tool_use_id": "2", "content": "denied", "is_error": true },
+            ])),
+        ];
+
+        let files = files_touched(&entries);
+        let paths: Vec<String> = files.iter().map(|f| f.display_path("/work/app")).collect();
+        assert_eq!(paths, ["src/lib.rs", "old.txt"]);
+        assert_eq!(files[0].line_counts(), (1, 1));
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nB\nc").iter().map(|l| l.tag).collect::<Vec<_>>(),
+            [DiffTag::Context, DiffTag::Removed, DiffTag::Added, DiffTag::Context]
+        );
+    }
+
+    #[test]
+    fn test_bash_targets() {
+        assert_eq!(bash_targets("echo hi > out.log; cat a | tee -a b.txt"), ["out.log", "b.txt"]);
+        assert_eq!(bash_targets("sed -i 's/x/y/' src/main.rs"), ["src/main.rs"]);
+        assert_eq!(
+            bash_targets("git mv old.rs new.rs && cp a.rs src/b.rs dest/"),
+            ["old.rs", "new.rs", "dest/a.rs", "dest/b.rs"]
+        );
+        assert_eq!(bash_targets("mv x.rs lib/ && rm -r build/"), ["x.rs", "lib/x.rs"]);
+        assert!(bash_targets("ls -la && rm *.tmp && cat $FILE").is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+//! Incremental reading of a session JSONL as it grows.
+//!
+//! The tailer remembers how far into the file it has parsed, so each read
+//! only touches bytes appended since the last one. A trailing line that is
+//! still being written is left for the next read.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::models::MessageEntry;
+
+/// Entries parsed by one [`JsonlTailer::read_new`].
+#[derive(Debug)]
+pub struct TailRead<T = MessageEntry> {
+    pub entries: Vec<T>,
+    /// The file shrank since the last read and was re-read from the start
+    pub truncated: bool,
+}
+
+/// Reads the complete lines appended to a JSONL file since the last read.
+#[derive(Debug, Clone)]
+pub struct JsonlTailer {
+    path: PathBuf,
+    /// Bytes parsed so far (always at a line boundary)
+    offset: u64,
+}
+
+impl JsonlTailer {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            offset: 0,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes of the file parsed so far; zero before the first read.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Parse the lines appended since the last read, skipping unparseable ones.
+    ///
+    /// A final line without a newline is only consumed once it is valid
+    /// JSON, so a half-written entry is picked up whole on a later read.
+    pub fn read_new(&mut self) -> Result<TailRead> {
+        self.read_new_as()
+    }
+
+    /// [`read_new`](Self::read_new) for lines of another type than session entries.
+    pub fn read_new_as<T: DeserializeOwned>(&mut self) -> Result<TailRead<T>> {
+        let mut file = File::open(&self.path).with_context(|| format!("Failed to open {:?}", self.path))?;
+        let len = file.metadata()?.len();
+
+        let mut read = TailRead {
+            entries: Vec::new(),
+            truncated: false,
+        };
+        if len < self.offset {
+            self.offset = 0;
+            read.truncated = true;
+        }
+        if len == self.offset {
+            return Ok(read);
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = Vec::with_capacity((len - self.offset) as usize);
+        file.read_to_end(&mut buf)?;
+
+        let mut consumed = 0;
+        while consumed < buf.len() {
+            let rest = &buf[consumed..];
+            let (line, advance) = match rest.iter().position(|&b| b == b'\n') {
+                Some(end) => (&rest[..end], end + 1),
+                // Trailing line: wait for the rest unless it already parses
+                None if serde_json::from_slice::<serde_json::Value>(rest).is_ok() => (rest, rest.len()),
+                None => break,
+            };
+            consumed += advance;
+
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            if let Ok(entry) = serde_json::from_slice::<T>(line) {
+                read.entries.push(entry);
+            }
+        }
+
+        self.offset += consumed as u64;
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn line(uuid: &str) -> String {
+        format!(
+            r#"{{"uuid":"{}","sessionId":"s","timestamp":"2026-01-01T00:00:00Z","type":"user","message":{{"role":"user","content":"hi"}}}}"#,
+            uuid
+        )
+    }
+
+    #[test]
+    fn test_tailer_reads_appended_and_partial_lines() {
+        let path = std::env::temp_dir().join(format!("tr-tail-{}.jsonl", uuid::Uuid::new_v4()));
+        let mut file = File::create(&path).unwrap();
+        let mut tailer = JsonlTailer::new(&path);
+        let uuids = |read: TailRead| read.entries.into_iter().map(|e| e.uuid).collect::<Vec<_>>();
+
+        // A complete line and half of the next
+        let second = line("b");
+        write!(file, "{}\nnot json\n{}", line("a"), &second[..20]).unwrap();
+        assert_eq!(uuids(tailer.read_new().unwrap()), ["a"]);
+
+        // The rest of the half-written line arrives
+        writeln!(file, "{}", &second[20..]).unwrap();
+        assert_eq!(uuids(tailer.read_new().unwrap()), ["b"]);
+        assert!(tailer.read_new().unwrap().entries.is_empty());
+
+        // Rewritten shorter: start over
+        std::fs::write(&path, format!("{}\n", line("c"))).unwrap();
+        let read = tailer.read_new().unwrap();
+        assert!(read.truncated);
+        assert_eq!(uuids(read), ["c"]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
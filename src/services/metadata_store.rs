@@ -0,0 +1,736 @@
+//! SQLite metadata store - session summary cache, full-text search index,
+//! project path mapping and user session metadata.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use crate::config::Config;
+use crate::models::{ContentBlock, MessageContent, MessageEntry, Session, SessionMeta};
+
+/// Marker inserted before a highlighted term in search snippets.
+const HIGHLIGHT_START: char = '\u{2}';
+/// Marker inserted after a highlighted term in search snippets.
+const HIGHLIGHT_END: char = '\u{3}';
+
+/// Maximum characters indexed per message block (tool results can be huge).
+const MAX_BODY_CHARS: usize = 20_000;
+
+/// Version of the derived-data schema (caches and search index).
+///
+/// Bump this whenever the cached `Session` format or the index layout
+/// changes; on mismatch the derived tables are dropped and rebuilt from
+/// the JSONL files on the next scan.
+const SCHEMA_VERSION: i64 = 5;
+
+/// Cached parse result for one JSONL file, keyed by path.
+#[derive(Debug, Clone)]
+pub struct CachedSummary {
+    /// File size when the summary was computed
+    pub file_size: i64,
+    /// File mtime (ns since epoch) when the summary was computed
+    pub mtime: i64,
+    /// Parsed summary, or None if the file held no messages
+    pub session: Option<Session>,
+}
+
+impl CachedSummary {
+    /// Whether this entry still matches the file on disk.
+    pub fn is_fresh(&self, stamp: Option<(i64, i64)>) -> bool {
+        stamp == Some((self.file_size, self.mtime))
+    }
+}
+
+/// A single ranked search result (best match within one session).
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    /// Session UUID (file stem of the JSONL)
+    pub session_id: String,
+    /// Project path the session ran in
+    pub project_path: String,
+    /// Path to the JSONL session file
+    pub file_path: String,
+    /// What matched: "user", "assistant", "tool_input" or "tool_result"
+    pub kind: String,
+    /// Snippet around the match, with highlight markers
+    pub snippet: String,
+    /// Number of matching blocks in this session
+    pub match_count: usize,
+}
+
+impl SearchHit {
+    /// Split the snippet into (text, highlighted) segments for rendering.
+    pub fn snippet_segments(&self) -> Vec<(String, bool)> {
+        let mut segments = Vec::new();
+        let mut current = String::new();
+        let mut highlighted = false;
+
+        for c in self.snippet.chars() {
+            match c {
+                HIGHLIGHT_START | HIGHLIGHT_END => {
+                    if !current.is_empty() {
+                        segments.push((std::mem::take(&mut current), highlighted));
+                    }
+                    highlighted = c == HIGHLIGHT_START;
+                }
+                _ => current.push(c),
+            }
+        }
+        if !current.is_empty() {
+            segments.push((current, highlighted));
+        }
+
+        segments
+    }
+}
+
+/// Persistent metadata database stored under `Config::data_dir()`.
+pub struct MetadataStore {
+    conn: Mutex<Connection>,
+}
+
+impl MetadataStore {
+    /// Open the metadata database at its default location.
+    pub fn open_default() -> Result<Self> {
+        Self::open(&Config::data_dir().join("metadata.db"))
+    }
+
+    /// Open (or create) the metadata database at a specific path.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open metadata database {:?}", path))?;
+        // WAL lets the background indexer write while the UI queries
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        Self::from_connection(conn)
+    }
+
+    /// Open a throwaway in-memory database.
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self> {
+        let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version != SCHEMA_VERSION {
+            tracing::info!(
+                "Metadata schema version {} != {}, rebuilding caches",
+                version,
+                SCHEMA_VERSION
+            );
+            conn.execute_batch(DROP_DERIVED)
+                .context("Failed to drop outdated metadata tables")?;
+        }
+        conn.execute_batch(SCHEMA)
+            .context("Failed to initialize metadata schema")?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Load every cached session summary, keyed by file path.
+    pub fn load_session_cache(&self) -> Result<HashMap<String, CachedSummary>> {
+        let conn = self.conn();
+        let mut stmt =
+            conn.prepare("SELECT file_path, file_size, mtime, summary FROM session_cache")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })?;
+
+        let mut cache = HashMap::new();
+        for row in rows {
+            let (path, file_size, mtime, summary) = row?;
+            let session = match summary {
+                Some(json) => match serde_json::from_str::<Session>(&json) {
+                    Ok(session) => Some(session),
+                    // Unreadable row: leave it out so the file gets re-parsed
+                    Err(_) => continue,
+                },
+                None => None,
+            };
+            cache.insert(path, CachedSummary { file_size, mtime, session });
+        }
+
+        Ok(cache)
+    }
+
+    /// Write changed summaries and drop entries for files that disappeared.
+    pub fn save_session_cache(
+        &self,
+        updated: &[(String, CachedSummary)],
+        removed: &[String],
+    ) -> Result<()> {
+        if updated.is_empty() && removed.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        {
+            let mut upsert = tx.prepare(
+                "INSERT OR REPLACE INTO session_cache (file_path, file_size, mtime, summary)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (path, entry) in updated {
+                let summary = entry
+                    .session
+                    .as_ref()
+                    .map(serde_json::to_string)
+                    .transpose()?;
+                upsert.execute(params![path, entry.file_size, entry.mtime, summary])?;
+            }
+
+            let mut delete = tx.prepare("DELETE FROM session_cache WHERE file_path = ?1")?;
+            for path in removed {
+                delete.execute(params![path])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Bring the search index in line with the given sessions.
+    ///
+    /// Only files whose size or mtime changed since they were last indexed
+    /// are re-read; files that no longer exist are dropped from the index.
+    /// Returns the number of sessions that were (re)indexed.
+    pub fn sync_search_index<'a>(
+        &self,
+        sessions: impl IntoIterator<Item = &'a Session>,
+    ) -> Result<usize> {
+        let mut indexed = 0;
+        let mut seen = std::collections::HashSet::new();
+
+        for session in sessions {
+            let key = session.file_path.to_string_lossy().to_string();
+            seen.insert(key.clone());
+
+            let Some((size, mtime)) = file_stamp(&session.file_path) else {
+                continue;
+            };
+            let stored: Option<(i64, i64)> = self
+                .conn()
+                .query_row(
+                    "SELECT file_size, mtime FROM search_files WHERE file_path = ?1",
+                    params![key],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            if stored == Some((size, mtime)) {
+                continue;
+            }
+
+            if let Err(e) = self.index_session(session) {
+                tracing::warn!("Failed to index {:?}: {}", session.file_path, e);
+                continue;
+            }
+            indexed += 1;
+        }
+
+        // Drop files that disappeared since the last sync
+        let stale: Vec<String> = {
+            let conn = self.conn();
+            let mut stmt = conn.prepare("SELECT file_path FROM search_files")?;
+            let paths = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .filter_map(|r| r.ok())
+                .filter(|p| !seen.contains(p))
+                .collect();
+            paths
+        };
+        for path in stale {
+            self.remove_from_index(&path)?;
+        }
+
+        Ok(indexed)
+    }
+
+    /// (Re)index a single session file.
+    pub fn index_session(&self, session: &Session) -> Result<()> {
+        let key = session.file_path.to_string_lossy().to_string();
+        let (size, mtime) = file_stamp(&session.file_path)
+            .with_context(|| format!("Failed to stat {:?}", session.file_path))?;
+        let docs = extract_documents(&session.file_path)?;
+
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM search_docs WHERE file_path = ?1", params![key])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO search_docs (file_path, kind, body) VALUES (?1, ?2, ?3)",
+            )?;
+            for (kind, body) in &docs {
+                insert.execute(params![key, kind, body])?;
+            }
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO search_files (file_path, session_id, project_path, file_size, mtime)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![key, session.id, session.project_path, size, mtime],
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Remove a session file from the search index.
+    pub fn remove_from_index(&self, file_path: &str) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM search_docs WHERE file_path = ?1", params![file_path])?;
+        tx.execute("DELETE FROM search_files WHERE file_path = ?1", params![file_path])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Search all indexed transcripts, returning the best hit per session.
+    ///
+    /// Results are ranked by bm25 of each session's best-matching block.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        let Some(fts_query) = build_fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT f.file_path, f.session_id, f.project_path, d.kind,
+                    snippet(search_fts, 0, char(2), char(3), '…', 16)
+             FROM search_fts
+             JOIN search_docs d ON d.id = search_fts.rowid
+             JOIN search_files f ON f.file_path = d.file_path
+             WHERE search_fts MATCH ?1
+             ORDER BY bm25(search_fts)
+             LIMIT ?2",
+        )?;
+
+        // Over-fetch blocks so that grouping by session still fills `limit`
+        let rows = stmt.query_map(params![fts_query, (limit * 20) as i64], |row| {
+            Ok(SearchHit {
+                file_path: row.get(0)?,
+                session_id: row.get(1)?,
+                project_path: row.get(2)?,
+                kind: row.get(3)?,
+                snippet: row.get(4)?,
+                match_count: 1,
+            })
+        })?;
+
+        let mut hits: Vec<SearchHit> = Vec::new();
+        let mut by_file: HashMap<String, usize> = HashMap::new();
+        for row in rows {
+            let hit = row?;
+            if let Some(&idx) = by_file.get(&hit.file_path) {
+                hits[idx].match_count += 1;
+            } else if hits.len() < limit {
+                by_file.insert(hit.file_path.clone(), hits.len());
+                hits.push(hit);
+            }
+        }
+
+        Ok(hits)
+    }
+
+    /// Load the persisted mapping of encoded project directory -> real path.
+    pub fn load_project_paths(&self) -> Result<HashMap<String, String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT encoded_path, decoded_path FROM project_paths")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Remember the real path of a project directory.
+    pub fn save_project_path(&self, encoded_path: &str, decoded_path: &str) -> Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO project_paths (encoded_path, decoded_path) VALUES (?1, ?2)",
+            params![encoded_path, decoded_path],
+        )?;
+        Ok(())
+    }
+
+    /// Load user metadata (titles, tags, notes, flags) by session ID.
+    pub fn load_session_meta(&self) -> Result<HashMap<String, SessionMeta>> {
+        let conn = self.conn();
+        let mut stmt =
+            conn.prepare("SELECT session_id, title, tags, note, pinned, archived FROM session_meta")?;
+        let rows = stmt.query_map([], |row| {
+            let tags: String = row.get(2)?;
+            Ok((
+                row.get(0)?,
+                SessionMeta {
+                    title: row.get(1)?,
+                    tags: serde_json::from_str(&tags).unwrap_or_default(),
+                    note: row.get(3)?,
+                    pinned: row.get(4)?,
+                    archived: row.get(5)?,
+                },
+            ))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Save a session's user metadata, deleting the row once nothing is set.
+    pub fn save_session_meta(&self, session_id: &str, meta: &SessionMeta) -> Result<()> {
+        let conn = self.conn();
+        if meta.is_empty() {
+            conn.execute("DELETE FROM session_meta WHERE session_id = ?1", params![session_id])?;
+        } else {
+            conn.execute(
+                "INSERT OR REPLACE INTO session_meta (session_id, title, tags, note, pinned, archived)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    session_id,
+                    meta.title,
+                    serde_json::to_string(&meta.tags)?,
+                    meta.note,
+                    meta.pinned,
+                    meta.archived
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Tables holding data derived from the JSONL files (safe to rebuild).
+const DROP_DERIVED: &str = "
+DROP TABLE IF EXISTS session_cache;
+DROP TRIGGER IF EXISTS search_docs_ai;
+DROP TRIGGER IF EXISTS search_docs_ad;
+DROP TABLE IF EXISTS search_fts;
+DROP TABLE IF EXISTS search_docs;
+DROP TABLE IF EXISTS search_files;
+";
+
+/// Schema for the metadata database.
+///
+/// `project_paths` and `session_meta` are not derived from the JSONL files,
+/// so they are kept across schema version bumps.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS project_paths (
+    encoded_path TEXT PRIMARY KEY,
+    decoded_path TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS session_meta (
+    session_id TEXT PRIMARY KEY,
+    title      TEXT,
+    tags       TEXT NOT NULL DEFAULT '[]',
+    note       TEXT,
+    pinned     INTEGER NOT NULL DEFAULT 0,
+    archived   INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS session_cache (
+    file_path TEXT PRIMARY KEY,
+    file_size INTEGER NOT NULL,
+    mtime     INTEGER NOT NULL,
+    summary   TEXT
+);
+CREATE TABLE IF NOT EXISTS search_files (
+    file_path    TEXT PRIMARY KEY,
+    session_id   TEXT NOT NULL,
+    project_path TEXT NOT NULL,
+    file_size    INTEGER NOT NULL,
+    mtime        INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS search_docs (
+    id        INTEGER PRIMARY KEY,
+    file_path TEXT NOT NULL,
+    kind      TEXT NOT NULL,
+    body      TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS search_docs_file ON search_docs(file_path);
+CREATE VIRTUAL TABLE IF NOT EXISTS search_fts USING fts5(
+    body,
+    content = 'search_docs',
+    content_rowid = 'id',
+    tokenize = 'porter unicode61'
+);
+CREATE TRIGGER IF NOT EXISTS search_docs_ai AFTER INSERT ON search_docs BEGIN
+    INSERT INTO search_fts (rowid, body) VALUES (new.id, new.body);
+END;
+CREATE TRIGGER IF NOT EXISTS search_docs_ad AFTER DELETE ON search_docs BEGIN
+    INSERT INTO search_fts (search_fts, rowid, body) VALUES ('delete', old.id, old.body);
+END;
+";
+
+/// File size and mtime (ns since epoch) used to detect changed session files.
+pub fn file_stamp(path: &Path) -> Option<(i64, i64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some((meta.len() as i64, mtime as i64))
+}
+
+/// Turn user input into an FTS5 query.
+///
+/// Each whitespace-separated term is quoted (so punctuation can't break the
+/// query syntax) and all terms must match. The last term is a prefix match
+/// so results show up while the user is still typing.
+fn build_fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|t| t.replace('"', ""))
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if terms.is_empty() {
+        return None;
+    }
+
+    let last = terms.len() - 1;
+    let query = terms
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            if i == last {
+                format!("\"{}\"*", t)
+            } else {
+                format!("\"{}\"", t)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(query)
+}
+
+/// Read a session JSONL and extract searchable (kind, body) documents.
+fn extract_documents(path: &Path) -> Result<Vec<(String, String)>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut docs = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_str::<MessageEntry>(&line) else {
+            continue;
+        };
+        let Some(message) = &entry.message else {
+            continue;
+        };
+
+        let role_kind = if message.role() == "assistant" {
+            "assistant"
+        } else {
+            "user"
+        };
+
+        match message {
+            MessageContent::Simple { content, .. } => {
+                push_document(&mut docs, role_kind, content.clone());
+            }
+            MessageContent::Structured { content, .. } => {
+                for block in content {
+                    match block {
+                        ContentBlock::Text { text } => {
+                            push_document(&mut docs, role_kind, text.clone());
+                        }
+                        ContentBlock::ToolUse { name, input, .. } => {
+                            let mut body = name.clone();
+                            collect_json_text(input, &mut body);
+                            push_document(&mut docs, "tool_input", body);
+                        }
+                        ContentBlock::ToolResult { content, .. } => {
+                            let mut body = String::new();
+                            collect_json_text(content, &mut body);
+                            push_document(&mut docs, "tool_result", body);
+                        }
+                        ContentBlock::Thinking { .. } | ContentBlock::Unknown => {}
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(docs)
+}
+
+/// Add a document, sanitizing control characters and capping its length.
+fn push_document(docs: &mut Vec<(String, String)>, kind: &str, body: String) {
+    let body: String = body
+        .chars()
+        .take(MAX_BODY_CHARS)
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    if !body.trim().is_empty() {
+        docs.push((kind.to_string(), body));
+    }
+}
+
+/// Append all string values of a JSON value, space-separated.
+fn collect_json_text(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::String(s) => {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(s);
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_json_text(item, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values() {
+                collect_json_text(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_session(dir: &Path, id: &str, lines: &[&str]) -> Session {
+        let path = dir.join(format!("{}.jsonl", id));
+        let mut file = File::create(&path).unwrap();
+        for line in lines {
+            writeln!(file, "{}", line).unwrap();
+        }
+        Session {
+            file_path: path,
+            message_count: lines.len(),
+            ..Session::test(id, None, "2026-01-01T10:00:00Z")
+        }
+    }
+
+    #[test]
+    fn test_session_cache_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("tr-cache-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let session = write_session(&dir, "abc", &["{}"]);
+
+        let db = MetadataStore::open_in_memory().unwrap();
+        let entry = CachedSummary {
+            file_size: 3,
+            mtime: 42,
+            session: Some(session),
+        };
+        db.save_session_cache(
+            &[
+                ("a.jsonl".to_string(), entry),
+                (
+                    "b.jsonl".to_string(),
+                    CachedSummary {
+                        file_size: 0,
+                        mtime: 1,
+                        session: None,
+                    },
+                ),
+            ],
+            &[],
+        )
+        .unwrap();
+
+        let cache = db.load_session_cache().unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(cache["a.jsonl"].is_fresh(Some((3, 42))));
+        assert!(!cache["a.jsonl"].is_fresh(Some((4, 42))));
+        assert_eq!(cache["a.jsonl"].session.as_ref().unwrap().id, "abc");
+        assert!(cache["b.jsonl"].session.is_none());
+
+        db.save_session_cache(&[], &["a.jsonl".to_string()])
+            .unwrap();
+        assert_eq!(db.load_session_cache().unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_user_data_survives_schema_rebuild() {
+        let dir = std::env::temp_dir().join(format!("tr-paths-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("metadata.db");
+
+        let meta = SessionMeta {
+            title: Some("OAuth refresh fix".to_string()),
+            tags: vec!["auth".to_string(), "bug".to_string()],
+            note: None,
+            pinned: true,
+            archived: false,
+        };
+        let db = MetadataStore::open(&path).unwrap();
+        db.save_project_path("-home-me-my-app", "/home/me/my-app").unwrap();
+        db.save_session_meta("abc", &meta).unwrap();
+        db.save_session_meta("def", &meta).unwrap();
+        db.save_session_meta("def", &SessionMeta::default()).unwrap();
+        db.conn().pragma_update(None, "user_version", 0).unwrap();
+        drop(db);
+
+        let db = MetadataStore::open(&path).unwrap();
+        let paths = db.load_project_paths().unwrap();
+        assert_eq!(paths["-home-me-my-app"], "/home/me/my-app");
+        let metas = db.load_session_meta().unwrap();
+        assert_eq!(metas.len(), 1);
+        assert_eq!(metas["abc"], meta);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_fts_query() {
+        assert_eq!(build_fts_query("   "), None);
+        assert_eq!(build_fts_query("oauth"), Some("\"oauth\"*".to_string()));
+        assert_eq!(
+            build_fts_query("oauth \"refresh bug"),
+            Some("\"oauth\" \"refresh\" \"bug\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn test_search_matches_text_and_tools() {
+        let dir = std::env::temp_dir().join(format!("tr-search-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let session = write_session(&dir, "abc", &[
+            r#"{"uuid":"1","sessionId":"abc","timestamp":"2025-01-01T00:00:00Z","type":"user","message":{"role":"user","content":"fix the OAuth refresh bug"}}"#,
+            r#"{"uuid":"2","sessionId":"abc","timestamp":"2025-01-01T00:00:01Z","type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"cargo test token_refresh"}}]}}"#,
+        ]);
+
+        let db = MetadataStore::open_in_memory().unwrap();
+        assert_eq!(db.sync_search_index([&session]).unwrap(), 1);
+        // Unchanged files are not re-indexed
+        assert_eq!(db.sync_search_index([&session]).unwrap(), 0);
+
+        let hits = db.search("oauth refresh", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_id, "abc");
+        assert_eq!(hits[0].kind, "user");
+        assert!(hits[0].snippet_segments().iter().any(|(t, hl)| *hl && t == "OAuth"));
+
+        let hits = db.search("token_refresh", 10).unwrap();
+        assert_eq!(hits[0].kind, "tool_input");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
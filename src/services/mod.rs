@@ -2,10 +2,24 @@
 
 pub mod agent_manager;
 pub mod ascii_art;
+pub mod cleanup;
+pub mod clipboard;
+pub mod exporter;
+pub mod file_changes;
+pub mod jsonl_tailer;
+pub mod metadata_store;
 pub mod session_store;
+pub mod session_watcher;
+pub mod terminal_launcher;
 pub mod theme;
+pub mod tool_stats;
 pub mod worktree_manager;
 
 pub use agent_manager::AgentManager;
+pub use clipboard::Clipboard;
+pub use jsonl_tailer::JsonlTailer;
+pub use metadata_store::MetadataStore;
 pub use session_store::SessionStore;
+pub use session_watcher::SessionWatcher;
+pub use terminal_launcher::TerminalCommand;
 pub use theme::Theme;
@@ -1,37 +1,73 @@
 //! Session store - discovers and parses Claude Code sessions.
 
-use anyhow::{Context, Result};
-use std::collections::HashMap;
+use anyhow::{bail, Context, Result};
+use chrono::{Local, NaiveDate};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use walkdir::WalkDir;
 
 use crate::config::Config;
-use crate::models::project::{decode_project_path, Project};
-use crate::models::{MessageEntry, Session};
+use crate::event::SessionChange;
+use crate::models::project::{decode_project_path, encode_project_path, Project};
+use crate::models::{ActivitySpan, MessageEntry, Session, SessionMeta, TokenCounts, UsageRecord, UsageTotals};
+use crate::services::jsonl_tailer::JsonlTailer;
+use crate::services::metadata_store::{file_stamp, CachedSummary, MetadataStore};
+
+/// Summary cache bookkeeping for a single scan.
+#[derive(Default)]
+struct ScanCache {
+    /// Entries loaded from the database; whatever is left after the scan is stale
+    cached: HashMap<String, CachedSummary>,
+    /// Entries that were (re)parsed during the scan
+    updated: Vec<(String, CachedSummary)>,
+}
 
 /// Service for discovering and loading Claude Code sessions.
 pub struct SessionStore {
     config: Arc<Config>,
+    /// Persistent summary cache (None if the database couldn't be opened)
+    cache: Option<MetadataStore>,
     /// Cached projects
     projects: Vec<Project>,
     /// Cached sessions by project encoded path
     sessions: HashMap<String, Vec<Session>>,
+    /// Sidechain (subagent) sessions by parent session ID
+    sidechains: HashMap<String, Vec<Session>>,
+    /// Known real paths of project directories, by encoded name (persisted)
+    project_paths: HashMap<String, String>,
+    /// User titles, tags and flags by session ID (persisted)
+    session_meta: HashMap<String, SessionMeta>,
 }
 
 impl SessionStore {
     /// Create a new session store.
     pub fn new(config: Arc<Config>) -> Result<Self> {
+        let cache = match MetadataStore::open_default() {
+            Ok(db) => Some(db),
+            Err(e) => {
+                tracing::warn!("Session cache unavailable, parsing all sessions: {}", e);
+                None
+            }
+        };
+
         Ok(Self {
             config,
+            cache,
             projects: Vec::new(),
             sessions: HashMap::new(),
+            sidechains: HashMap::new(),
+            project_paths: HashMap::new(),
+            session_meta: HashMap::new(),
         })
     }
 
     /// Scan for all projects and their sessions.
+    ///
+    /// Summaries come from the metadata cache when a file's size and mtime
+    /// are unchanged; only new or modified JSONL files are parsed.
     pub fn scan(&mut self) -> Result<()> {
         let projects_dir = self.config.claude_projects_dir();
 
@@ -39,7 +75,34 @@ impl SessionStore {
             return Ok(());
         }
 
+        let mut scan_cache = ScanCache {
+            cached: self
+                .cache
+                .as_ref()
+                .map(|db| {
+                    db.load_session_cache().unwrap_or_else(|e| {
+                        tracing::warn!("Failed to load session cache: {}", e);
+                        HashMap::new()
+                    })
+                })
+                .unwrap_or_default(),
+            updated: Vec::new(),
+        };
+
+        if let Some(db) = &self.cache {
+            match db.load_project_paths() {
+                Ok(paths) => self.project_paths = paths,
+                Err(e) => tracing::warn!("Failed to load project paths: {}", e),
+            }
+            match db.load_session_meta() {
+                Ok(meta) => self.session_meta = meta,
+                Err(e) => tracing::warn!("Failed to load session metadata: {}", e),
+            }
+        }
+
         let mut projects = Vec::new();
+        self.sessions.clear();
+        self.sidechains.clear();
 
         // Iterate over project directories
         for entry in fs::read_dir(&projects_dir)? {
@@ -61,45 +124,140 @@ impl SessionStore {
             }
 
             // Load sessions for this project
-            let sessions = self.load_project_sessions(&path)?;
+            let (mut sessions, mut sidechains) = self.load_project_sessions(&path, &mut scan_cache)?;
+            let decoded_path = self
+                .resolve_project_path(&encoded_path, &sessions)
+                .unwrap_or_else(|| decode_project_path(&encoded_path));
+            fix_session_paths(&mut sidechains, &encoded_path, &decoded_path);
+            for sidechain in sidechains {
+                self.add_sidechain(sidechain);
+            }
 
             if sessions.is_empty() {
                 continue;
             }
-
-            // Create project summary
-            let mut project = Project::new(encoded_path.clone());
-            project.session_count = sessions.len();
-            project.total_messages = sessions.iter().map(|s| s.message_count).sum();
-            project.last_activity = sessions
-                .iter()
-                .map(|s| s.last_message)
-                .max()
-                .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
-
+            fix_session_paths(&mut sessions, &encoded_path, &decoded_path);
+            let project = Project::new(encoded_path.clone(), decoded_path);
+            let project = summarize_project(project, &sessions, &self.sidechains);
             self.sessions.insert(encoded_path, sessions);
             projects.push(project);
         }
 
         // Sort projects by last activity (most recent first)
-        projects.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_activity));
         self.projects = projects;
 
+        if let Some(db) = &self.cache {
+            let removed: Vec<String> = scan_cache.cached.into_keys().collect();
+            if let Err(e) = db.save_session_cache(&scan_cache.updated, &removed) {
+                tracing::warn!("Failed to save session cache: {}", e);
+            }
+        }
+
         Ok(())
     }
 
+    /// Apply a single session file change reported by the watcher.
+    ///
+    /// Re-parses (or drops) just that file and refreshes its project summary.
+    /// Returns the encoded path of the affected project, if any.
+    pub fn apply_change(&mut self, file_path: &Path, change: SessionChange) -> Option<String> {
+        let project_dir = project_dir_of(file_path)?;
+        if project_dir.parent()? != self.config.claude_projects_dir() {
+            return None;
+        }
+        let encoded_path = project_dir.file_name()?.to_str()?.to_string();
+        let key = file_path.to_string_lossy().to_string();
+
+        let parsed = match change {
+            SessionChange::Deleted => None,
+            SessionChange::Created | SessionChange::Appended => {
+                let stamp = file_stamp(file_path);
+                let session = self.parse_session_summary(file_path.to_path_buf()).ok();
+                if let (Some(db), Some((file_size, mtime))) = (&self.cache, stamp) {
+                    let entry = CachedSummary { file_size, mtime, session: session.clone() };
+                    if let Err(e) = db.save_session_cache(&[(key.clone(), entry)], &[]) {
+                        tracing::warn!("Failed to update session cache: {}", e);
+                    }
+                }
+                session
+            }
+        };
+        if change == SessionChange::Deleted {
+            if let Some(db) = &self.cache {
+                if let Err(e) = db.save_session_cache(&[], &[key]) {
+                    tracing::warn!("Failed to update session cache: {}", e);
+                }
+            }
+        }
+
+        let parsed = parsed.map(|mut session| {
+            self.attach_meta(&mut session);
+            session
+        });
+
+        // Drop the old copy of this file, whichever list it was in
+        for sidechains in self.sidechains.values_mut() {
+            sidechains.retain(|s| s.file_path != file_path);
+        }
+        let (sidechain, parsed) = match parsed {
+            Some(session) if session.is_agent => (Some(session), None),
+            other => (None, other),
+        };
+
+        let mut sessions = self.sessions.remove(&encoded_path).unwrap_or_default();
+        sessions.retain(|s| s.file_path != file_path);
+        if let Some(session) = parsed {
+            sessions.push(session);
+        }
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_message));
+
+        // Reuse the existing project's path unless a session pins it down
+        let previous_path = self
+            .projects
+            .iter()
+            .position(|p| p.encoded_path == encoded_path)
+            .map(|idx| self.projects.remove(idx).decoded_path);
+        let decoded_path = self
+            .resolve_project_path(&encoded_path, &sessions)
+            .or(previous_path)
+            .unwrap_or_else(|| decode_project_path(&encoded_path));
+        if let Some(mut sidechain) = sidechain {
+            fix_session_paths(std::slice::from_mut(&mut sidechain), &encoded_path, &decoded_path);
+            self.add_sidechain(sidechain);
+        }
+        if !sessions.is_empty() {
+            fix_session_paths(&mut sessions, &encoded_path, &decoded_path);
+            let project = Project::new(encoded_path.clone(), decoded_path);
+            let project = summarize_project(project, &sessions, &self.sidechains);
+            self.projects.push(project);
+            self.sessions.insert(encoded_path.clone(), sessions);
+        }
+        self.projects.sort_by_key(|p| std::cmp::Reverse(p.last_activity));
+
+        Some(encoded_path)
+    }
+
     /// Load sessions for a specific project directory.
-    fn load_project_sessions(&self, project_dir: &PathBuf) -> Result<Vec<Session>> {
+    ///
+    /// Returns top-level sessions and sidechains separately. Sidechains live
+    /// either beside their parent or in `<parent-id>/subagents/`.
+    fn load_project_sessions(
+        &self,
+        project_dir: &PathBuf,
+        scan_cache: &mut ScanCache,
+    ) -> Result<(Vec<Session>, Vec<Session>)> {
         let mut sessions = Vec::new();
+        let mut sidechains = Vec::new();
 
         for entry in WalkDir::new(project_dir)
-            .max_depth(1)
+            .max_depth(3)
             .into_iter()
             .filter_map(|e| e.ok())
         {
             let path = entry.path();
 
-            if !path.is_file() {
+            if !path.is_file() || !(entry.depth() == 1 || is_subagent_file(path)) {
                 continue;
             }
 
@@ -108,18 +266,138 @@ impl SessionStore {
                 continue;
             }
 
-            if let Ok(session) = self.parse_session_summary(path.to_path_buf()) {
-                // Skip agent/sidechain sessions - they can't be resumed independently
-                if !session.is_agent {
+            if let Some(mut session) = self.cached_or_parse(path.to_path_buf(), scan_cache) {
+                self.attach_meta(&mut session);
+                if session.is_agent {
+                    sidechains.push(session);
+                } else {
                     sessions.push(session);
                 }
             }
         }
 
         // Sort sessions by last message (most recent first)
-        sessions.sort_by(|a, b| b.last_message.cmp(&a.last_message));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_message));
+
+        Ok((sessions, sidechains))
+    }
+
+    /// Point a project's sessions at the directory it was moved to.
+    ///
+    /// Renames `<projects>/<encoded>` to the new path's encoding (merging into
+    /// it if Claude already created one there) so `claude --resume` finds the
+    /// sessions from the new location, then rescans. Returns the new encoded name.
+    pub fn relink_project(&mut self, encoded_path: &str, new_path: &Path) -> Result<String> {
+        let new_path = new_path
+            .canonicalize()
+            .with_context(|| format!("{} does not exist", new_path.display()))?;
+        if !new_path.is_dir() {
+            bail!("{} is not a directory", new_path.display());
+        }
+        let new_path = new_path.to_string_lossy().to_string();
+        let new_encoded = encode_project_path(&new_path);
+
+        if new_encoded != encoded_path {
+            let projects_dir = self.config.claude_projects_dir();
+            let old_dir = projects_dir.join(encoded_path);
+            let new_dir = projects_dir.join(&new_encoded);
+            if new_dir.exists() {
+                merge_dir(&old_dir, &new_dir)?;
+            } else {
+                fs::rename(&old_dir, &new_dir)
+                    .with_context(|| format!("Failed to rename {:?} to {:?}", old_dir, new_dir))?;
+            }
+        }
+
+        if let Some(db) = &self.cache {
+            db.save_project_path(&new_encoded, &new_path)?;
+        }
+        self.project_paths.insert(new_encoded.clone(), new_path);
+        self.scan()?;
+        Ok(new_encoded)
+    }
+
+    /// Look up a project directory's real path without guessing.
+    ///
+    /// A session `cwd` that encodes back to the directory name is exact and
+    /// gets persisted; otherwise the previously persisted path is used.
+    fn resolve_project_path(&mut self, encoded_path: &str, sessions: &[Session]) -> Option<String> {
+        let from_cwd = sessions
+            .iter()
+            .map(|s| &s.project_path)
+            .find(|path| encode_project_path(path) == encoded_path);
+
+        let Some(path) = from_cwd else {
+            return self.project_paths.get(encoded_path).cloned();
+        };
+        if self.project_paths.get(encoded_path) != Some(path) {
+            if let Some(db) = &self.cache {
+                if let Err(e) = db.save_project_path(encoded_path, path) {
+                    tracing::warn!("Failed to save project path: {}", e);
+                }
+            }
+            self.project_paths.insert(encoded_path.to_string(), path.clone());
+        }
+        Some(path.clone())
+    }
+
+    /// Fill in a session's user metadata from the loaded table.
+    fn attach_meta(&self, session: &mut Session) {
+        session.meta = self.session_meta.get(&session.id).cloned().unwrap_or_default();
+    }
+
+    /// Replace a session's user metadata and persist it.
+    pub fn set_session_meta(&mut self, session_id: &str, meta: SessionMeta) -> Result<()> {
+        let Some(db) = &self.cache else {
+            bail!("metadata database is unavailable");
+        };
+        db.save_session_meta(session_id, &meta)?;
+
+        let sessions = self.sessions.values_mut().chain(self.sidechains.values_mut()).flatten();
+        for session in sessions.filter(|s| s.id == session_id) {
+            session.meta = meta.clone();
+        }
+        if meta.is_empty() {
+            self.session_meta.remove(session_id);
+        } else {
+            self.session_meta.insert(session_id.to_string(), meta);
+        }
+        Ok(())
+    }
 
-        Ok(sessions)
+    /// File a sidechain under its parent session.
+    fn add_sidechain(&mut self, session: Session) {
+        let Some(parent_id) = session.parent_session_id.clone() else {
+            return;
+        };
+        let sidechains = self.sidechains.entry(parent_id).or_default();
+        sidechains.push(session);
+        sidechains.sort_by_key(|s| s.first_message);
+    }
+
+    /// Get a session summary from the scan cache, parsing the file if it changed.
+    fn cached_or_parse(&self, file_path: PathBuf, scan_cache: &mut ScanCache) -> Option<Session> {
+        let key = file_path.to_string_lossy().to_string();
+        let stamp = file_stamp(&file_path);
+
+        if let Some(entry) = scan_cache.cached.remove(&key) {
+            if entry.is_fresh(stamp) {
+                return entry.session;
+            }
+        }
+
+        let session = self.parse_session_summary(file_path).ok();
+        if let Some((file_size, mtime)) = stamp {
+            scan_cache.updated.push((
+                key,
+                CachedSummary {
+                    file_size,
+                    mtime,
+                    session: session.clone(),
+                },
+            ));
+        }
+        session
     }
 
     /// Parse a session JSONL file and extract summary information.
@@ -144,6 +422,11 @@ impl SessionStore {
         let mut preview_text = String::new();
         let mut is_agent = false;
         let mut agent_id: Option<String> = None;
+        let mut entry_session_id: Option<String> = None;
+        // (local date, model) -> tokens; responses split across entries are counted once
+        let mut usage: BTreeMap<(NaiveDate, String), TokenCounts> = BTreeMap::new();
+        let mut seen_responses: HashSet<String> = HashSet::new();
+        let mut activity: Vec<ActivitySpan> = Vec::new();
 
         for line in reader.lines() {
             let line = line?;
@@ -169,6 +452,9 @@ impl SessionStore {
                 if agent_id.is_none() {
                     agent_id = entry.agent_id.clone();
                 }
+                if entry_session_id.is_none() {
+                    entry_session_id = Some(entry.session_id.clone());
+                }
 
                 // Track agent status
                 if entry.is_sidechain {
@@ -180,6 +466,22 @@ impl SessionStore {
                     first_message = Some(entry.timestamp);
                 }
                 last_message = Some(entry.timestamp);
+                ActivitySpan::record(&mut activity, entry.timestamp);
+
+                // Accumulate token usage, once per API response
+                if let Some(msg) = &entry.message {
+                    if let (Some(tokens), Some(model)) = (msg.usage(), msg.model()) {
+                        let tokens = TokenCounts::from(tokens);
+                        let first_seen = match msg.id() {
+                            Some(id) => seen_responses.insert(id.to_string()),
+                            None => true,
+                        };
+                        if first_seen && !tokens.is_empty() {
+                            let date = entry.timestamp.with_timezone(&Local).date_naive();
+                            *usage.entry((date, model.to_string())).or_default() += tokens;
+                        }
+                    }
+                }
 
                 // Get preview from first user message
                 if preview_text.is_empty() && entry.entry_type == "user" {
@@ -208,15 +510,18 @@ impl SessionStore {
             first_message.context("Session has no messages")?;
         let last_message = last_message.unwrap_or(first_message);
 
-        // Decode project path from directory name if not found in entries
-        if project_path.is_empty() {
-            if let Some(parent) = file_path.parent() {
-                if let Some(dir_name) = parent.file_name().and_then(|n| n.to_str()) {
-                    project_path = decode_project_path(dir_name);
-                }
-            }
-        }
-
+        // Sidechain entries carry the parent's session ID; the subagents
+        // directory layout names the parent too
+        let parent_session_id = if is_agent {
+            subagents_parent_id(&file_path)
+                .or(entry_session_id)
+                .filter(|id| *id != session_id)
+        } else {
+            None
+        };
+
+        // An empty project_path (no cwd in any entry) is filled in with the
+        // project's resolved path when the session is filed
         Ok(Session {
             id: session_id,
             project_path,
@@ -230,9 +535,21 @@ impl SessionStore {
             file_size: metadata.len(),
             is_agent,
             agent_id,
+            parent_session_id,
+            usage: usage
+                .into_iter()
+                .map(|((date, model), tokens)| UsageRecord { date, model, tokens })
+                .collect(),
+            activity,
+            meta: SessionMeta::default(),
         })
     }
 
+    /// Read every message entry from a session JSONL, skipping unparseable lines.
+    pub fn load_entries(file_path: &Path) -> Result<Vec<MessageEntry>> {
+        Ok(JsonlTailer::new(file_path).read_new()?.entries)
+    }
+
     /// Get all discovered projects.
     pub fn projects(&self) -> &[Project] {
         &self.projects
@@ -243,8 +560,146 @@ impl SessionStore {
         self.sessions.get(encoded_path)
     }
 
+    /// Iterate over every loaded session across all projects.
+    pub fn all_sessions(&self) -> impl Iterator<Item = &Session> {
+        self.sessions.values().flatten()
+    }
+
+    /// Sidechain sessions spawned by a session, oldest first.
+    pub fn sidechains_for(&self, session_id: &str) -> &[Session] {
+        self.sidechains.get(session_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Find a session by its ID.
+    pub fn find_session(&self, session_id: &str) -> Option<&Session> {
+        self.all_sessions().find(|s| s.id == session_id)
+    }
+
     /// Get total session count across all projects.
     pub fn total_session_count(&self) -> usize {
         self.sessions.values().map(|s| s.len()).sum()
     }
 }
+
+/// Give sessions their project's path when their recorded `cwd` is unknown
+/// or stale (it no longer encodes to the directory they live in, because the
+/// project was relinked).
+fn fix_session_paths(sessions: &mut [Session], encoded_path: &str, project_path: &str) {
+    for session in sessions
+        .iter_mut()
+        .filter(|s| s.project_path.is_empty() || encode_project_path(&s.project_path) != encoded_path)
+    {
+        session.project_path = project_path.to_string();
+    }
+}
+
+/// Move every entry of `from` into `to`, then remove `from`.
+///
+/// Entries whose name already exists in `to` are left where they are.
+fn merge_dir(from: &Path, to: &Path) -> Result<()> {
+    let mut clashes = 0;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if target.exists() {
+            clashes += 1;
+            continue;
+        }
+        fs::rename(entry.path(), &target)
+            .with_context(|| format!("Failed to move {:?} to {:?}", entry.path(), target))?;
+    }
+    if clashes > 0 {
+        bail!("{} entries already exist in {:?}; left them in {:?}", clashes, to, from);
+    }
+    fs::remove_dir(from).with_context(|| format!("Failed to remove {:?}", from))?;
+    Ok(())
+}
+
+/// Whether a file sits in a `<parent-id>/subagents/` directory.
+fn is_subagent_file(file_path: &Path) -> bool {
+    file_path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .is_some_and(|name| name == "subagents")
+}
+
+/// Parent session ID from the `<parent-id>/subagents/` layout.
+fn subagents_parent_id(file_path: &Path) -> Option<String> {
+    if !is_subagent_file(file_path) {
+        return None;
+    }
+    let parent_dir = file_path.parent()?.parent()?;
+    parent_dir.file_name()?.to_str().map(str::to_string)
+}
+
+/// The project directory a session file belongs to.
+fn project_dir_of(file_path: &Path) -> Option<&Path> {
+    let dir = file_path.parent()?;
+    if is_subagent_file(file_path) {
+        dir.parent()?.parent()
+    } else {
+        Some(dir)
+    }
+}
+
+/// Fill in a project's summary fields from its (non-empty) session list.
+///
+/// Usage also counts each session's sidechains, so subagent spend is included.
+fn summarize_project(
+    mut project: Project,
+    sessions: &[Session],
+    sidechains: &HashMap<String, Vec<Session>>,
+) -> Project {
+    project.session_count = sessions.len();
+    project.total_messages = sessions.iter().map(|s| s.message_count).sum();
+    project.total_size = sessions.iter().map(|s| s.file_size).sum();
+    project.last_activity = sessions
+        .iter()
+        .map(|s| s.last_message)
+        .max()
+        .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
+    project.usage = UsageTotals::default();
+    let sidechains = sessions.iter().filter_map(|s| sidechains.get(&s.id)).flatten();
+    for session in sessions.iter().chain(sidechains) {
+        project.usage.merge(&session.usage_totals());
+    }
+    project
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subagent_layout() {
+        let nested = Path::new("/c/projects/-home-me-app/abc-123/subagents/agent-1.jsonl");
+        assert_eq!(subagents_parent_id(nested).as_deref(), Some("abc-123"));
+        assert_eq!(project_dir_of(nested), Some(Path::new("/c/projects/-home-me-app")));
+
+        let flat = Path::new("/c/projects/-home-me-app/agent-1.jsonl");
+        assert_eq!(subagents_parent_id(flat), None);
+        assert_eq!(project_dir_of(flat), Some(Path::new("/c/projects/-home-me-app")));
+    }
+
+    #[test]
+    fn test_merge_dir_keeps_clashes() {
+        let root = std::env::temp_dir().join(format!("tr-merge-{}", uuid::Uuid::new_v4()));
+        let (from, to) = (root.join("old"), root.join("new"));
+        fs::create_dir_all(&from).unwrap();
+        fs::create_dir_all(&to).unwrap();
+        fs::write(from.join("a.jsonl"), "a").unwrap();
+        fs::write(from.join("b.jsonl"), "old b").unwrap();
+        fs::write(to.join("b.jsonl"), "new b").unwrap();
+
+        assert!(merge_dir(&from, &to).is_err());
+        assert!(to.join("a.jsonl").exists());
+        assert_eq!(fs::read_to_string(to.join("b.jsonl")).unwrap(), "new b");
+        assert!(from.join("b.jsonl").exists());
+
+        fs::remove_file(from.join("b.jsonl")).unwrap();
+        merge_dir(&from, &to).unwrap();
+        assert!(!from.exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
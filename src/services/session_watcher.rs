@@ -0,0 +1,94 @@
+//! Filesystem watcher for the Claude projects directory.
+//!
+//! Turns raw `notify` events into debounced per-file `SessionChanged`
+//! events on the app channel, so the browser can update incrementally.
+
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self as std_mpsc, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use crate::event::{AppEvent, SessionChange};
+
+/// How long to coalesce filesystem events before notifying the app.
+///
+/// Claude appends to a JSONL many times per turn; batching keeps us from
+/// re-parsing the same file for every write.
+const DEBOUNCE: Duration = Duration::from_millis(750);
+
+/// Watches `~/.claude/projects` for session files being created, appended or deleted.
+///
+/// Dropping the watcher stops it.
+pub struct SessionWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl SessionWatcher {
+    /// Start watching the projects directory, pushing events into `event_tx`.
+    pub fn start(projects_dir: &Path, event_tx: mpsc::UnboundedSender<AppEvent>) -> Result<Self> {
+        let (raw_tx, raw_rx) = std_mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            let _ = raw_tx.send(res);
+        })
+        .context("Failed to create filesystem watcher")?;
+
+        watcher
+            .watch(projects_dir, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {:?}", projects_dir))?;
+
+        std::thread::spawn(move || debounce_loop(raw_rx, event_tx));
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// Collect raw events and flush one `SessionChanged` per file every `DEBOUNCE`.
+fn debounce_loop(
+    raw_rx: std_mpsc::Receiver<notify::Result<notify::Event>>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+) {
+    // path -> whether the file was created within this batch
+    let mut pending: HashMap<PathBuf, bool> = HashMap::new();
+    let mut last_flush = Instant::now();
+
+    loop {
+        match raw_rx.recv_timeout(DEBOUNCE) {
+            Ok(Ok(event)) => {
+                for path in event.paths {
+                    if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                        continue;
+                    }
+                    let created = matches!(event.kind, EventKind::Create(_));
+                    *pending.entry(path).or_insert(false) |= created;
+                }
+            }
+            Ok(Err(e)) => tracing::warn!("Filesystem watcher error: {}", e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        // Flush on a fixed cadence, even while events keep streaming in
+        if pending.is_empty() || last_flush.elapsed() < DEBOUNCE {
+            continue;
+        }
+        last_flush = Instant::now();
+
+        for (path, created) in pending.drain() {
+            // Decide from the final state on disk: renames and
+            // delete-then-recreate sequences collapse naturally
+            let change = if !path.exists() {
+                SessionChange::Deleted
+            } else if created {
+                SessionChange::Created
+            } else {
+                SessionChange::Appended
+            };
+            if event_tx.send(AppEvent::SessionChanged { path, change }).is_err() {
+                return; // app is shutting down
+            }
+        }
+    }
+}
@@ -0,0 +1,465 @@
+//! Terminal emulator backends for opening new windows.
+//!
+//! Each backend knows how to ask its terminal for a working directory,
+//! a window class and a command to exec.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::LauncherConfig;
+
+/// What to run in a new terminal window.
+#[derive(Debug, Clone, Default)]
+pub struct TerminalCommand {
+    /// Program and arguments to exec (empty = the terminal's default shell)
+    pub argv: Vec<String>,
+    /// Directory the window starts in
+    pub working_dir: Option<PathBuf>,
+    /// Window class / app-id, for window manager rules
+    pub class: Option<String>,
+}
+
+impl TerminalCommand {
+    pub fn new<S: Into<String>>(argv: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            argv: argv.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn working_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.working_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn class(mut self, class: Option<&str>) -> Self {
+        self.class = class.map(str::to_string);
+        self
+    }
+}
+
+/// A terminal emulator that can open a window running a command.
+pub trait TerminalLauncher: Send + Sync {
+    /// Name used in config and `$TERMINAL`, e.g. "kitty".
+    fn name(&self) -> &'static str;
+
+    /// Full argv (terminal binary first) that opens a window for `cmd`.
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String>;
+
+    /// A ready-to-spawn process for `cmd`.
+    ///
+    /// The working directory is also set on the process itself, for
+    /// terminals without a flag for it.
+    fn command(&self, cmd: &TerminalCommand) -> Command {
+        let argv = self.argv(cmd);
+        let mut command = Command::new(&argv[0]);
+        command.args(&argv[1..]);
+        if let Some(dir) = &cmd.working_dir {
+            command.current_dir(dir);
+        }
+        command
+    }
+}
+
+/// Push `flag value` as two arguments.
+fn push_pair(argv: &mut Vec<String>, flag: &str, value: impl Into<String>) {
+    argv.push(flag.to_string());
+    argv.push(value.into());
+}
+
+fn dir_str(dir: &Path) -> String {
+    dir.to_string_lossy().to_string()
+}
+
+/// Ghostty. Uses a plain `ghostty` process rather than `+new-window`, whose
+/// `-e` is ignored in 1.3.0-dev.
+pub struct Ghostty;
+
+impl TerminalLauncher for Ghostty {
+    fn name(&self) -> &'static str {
+        "ghostty"
+    }
+
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String> {
+        let mut argv = vec!["ghostty".to_string()];
+        if let Some(dir) = &cmd.working_dir {
+            argv.push(format!("--working-directory={}", dir_str(dir)));
+        }
+        if let Some(class) = &cmd.class {
+            argv.push(format!("--class={}", class));
+        }
+        if !cmd.argv.is_empty() {
+            argv.push("-e".to_string());
+            argv.extend(cmd.argv.iter().cloned());
+        }
+        argv
+    }
+}
+
+/// WezTerm (`wezterm start`).
+pub struct Wezterm;
+
+impl TerminalLauncher for Wezterm {
+    fn name(&self) -> &'static str {
+        "wezterm"
+    }
+
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String> {
+        let mut argv = vec!["wezterm".to_string(), "start".to_string()];
+        if let Some(dir) = &cmd.working_dir {
+            push_pair(&mut argv, "--cwd", dir_str(dir));
+        }
+        if let Some(class) = &cmd.class {
+            push_pair(&mut argv, "--class", class);
+        }
+        if !cmd.argv.is_empty() {
+            argv.push("--".to_string());
+            argv.extend(cmd.argv.iter().cloned());
+        }
+        argv
+    }
+}
+
+/// kitty. The command is positional.
+pub struct Kitty;
+
+impl TerminalLauncher for Kitty {
+    fn name(&self) -> &'static str {
+        "kitty"
+    }
+
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String> {
+        let mut argv = vec!["kitty".to_string()];
+        if let Some(dir) = &cmd.working_dir {
+            push_pair(&mut argv, "--directory", dir_str(dir));
+        }
+        if let Some(class) = &cmd.class {
+            push_pair(&mut argv, "--class", class);
+        }
+        argv.extend(cmd.argv.iter().cloned());
+        argv
+    }
+}
+
+/// Alacritty.
+pub struct Alacritty;
+
+impl TerminalLauncher for Alacritty {
+    fn name(&self) -> &'static str {
+        "alacritty"
+    }
+
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String> {
+        let mut argv = vec!["alacritty".to_string()];
+        if let Some(dir) = &cmd.working_dir {
+            push_pair(&mut argv, "--working-directory", dir_str(dir));
+        }
+        if let Some(class) = &cmd.class {
+            push_pair(&mut argv, "--class", class);
+        }
+        if !cmd.argv.is_empty() {
+            argv.push("-e".to_string());
+            argv.extend(cmd.argv.iter().cloned());
+        }
+        argv
+    }
+}
+
+/// foot (Wayland). The class is the app-id; the command is positional.
+pub struct Foot;
+
+impl TerminalLauncher for Foot {
+    fn name(&self) -> &'static str {
+        "foot"
+    }
+
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String> {
+        let mut argv = vec!["foot".to_string()];
+        if let Some(dir) = &cmd.working_dir {
+            argv.push(format!("--working-directory={}", dir_str(dir)));
+        }
+        if let Some(class) = &cmd.class {
+            argv.push(format!("--app-id={}", class));
+        }
+        argv.extend(cmd.argv.iter().cloned());
+        argv
+    }
+}
+
+/// GNOME Terminal. The command goes after `--`.
+pub struct GnomeTerminal;
+
+impl TerminalLauncher for GnomeTerminal {
+    fn name(&self) -> &'static str {
+        "gnome-terminal"
+    }
+
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String> {
+        let mut argv = vec!["gnome-terminal".to_string()];
+        if let Some(dir) = &cmd.working_dir {
+            argv.push(format!("--working-directory={}", dir_str(dir)));
+        }
+        if let Some(class) = &cmd.class {
+            argv.push(format!("--class={}", class));
+        }
+        if !cmd.argv.is_empty() {
+            argv.push("--".to_string());
+            argv.extend(cmd.argv.iter().cloned());
+        }
+        argv
+    }
+}
+
+/// Konsole. Has no window class flag, so the class is ignored.
+pub struct Konsole;
+
+impl TerminalLauncher for Konsole {
+    fn name(&self) -> &'static str {
+        "konsole"
+    }
+
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String> {
+        let mut argv = vec!["konsole".to_string()];
+        if let Some(dir) = &cmd.working_dir {
+            push_pair(&mut argv, "--workdir", dir_str(dir));
+        }
+        if !cmd.argv.is_empty() {
+            argv.push("-e".to_string());
+            argv.extend(cmd.argv.iter().cloned());
+        }
+        argv
+    }
+}
+
+/// xterm. Has no working directory flag; relies on the process cwd.
+pub struct Xterm;
+
+impl TerminalLauncher for Xterm {
+    fn name(&self) -> &'static str {
+        "xterm"
+    }
+
+    fn argv(&self, cmd: &TerminalCommand) -> Vec<String> {
+        let mut argv = vec!["xterm".to_string()];
+        if let Some(class) = &cmd.class {
+            push_pair(&mut argv, "-class", class);
+        }
+        if !cmd.argv.is_empty() {
+            argv.push("-e".to_string());
+            argv.extend(cmd.argv.iter().cloned());
+        }
+        argv
+    }
+}
+
+/// Every supported backend, in auto-detection preference order.
+fn backends() -> Vec<Box<dyn TerminalLauncher>> {
+    vec![
+        Box::new(Ghostty),
+        Box::new(Wezterm),
+        Box::new(Kitty),
+        Box::new(Alacritty),
+        Box::new(Foot),
+        Box::new(GnomeTerminal),
+        Box::new(Konsole),
+        Box::new(Xterm),
+    ]
+}
+
+/// Find a backend by name, case-insensitively.
+///
+/// Accepts a path or `$TERM_PROGRAM` spelling too, e.g. "/usr/bin/kitty" or "WezTerm".
+pub fn by_name(name: &str) -> Option<Box<dyn TerminalLauncher>> {
+    let name = Path::new(name.trim())
+        .file_name()
+        .and_then(|n| n.to_str())?
+        .to_lowercase();
+    backends().into_iter().find(|b| b.name() == name)
+}
+
+/// Pick the terminal the user is most likely running.
+///
+/// Checks `$TERMINAL`, then `$TERM_PROGRAM`, then the first backend found
+/// on `$PATH`, falling back to ghostty.
+pub fn detect() -> Box<dyn TerminalLauncher> {
+    ["TERMINAL", "TERM_PROGRAM"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find_map(|value| by_name(&value))
+        .or_else(|| backends().into_iter().find(|b| on_path(b.name())))
+        .unwrap_or_else(|| Box::new(Ghostty))
+}
+
+/// The launcher selected by the `[launcher]` config section.
+pub fn from_config(config: &LauncherConfig) -> Box<dyn TerminalLauncher> {
+    if config.terminal.eq_ignore_ascii_case("auto") {
+        return detect();
+    }
+    by_name(&config.terminal).unwrap_or_else(|| {
+        tracing::warn!("Unknown terminal {:?} in config, auto-detecting", config.terminal);
+        detect()
+    })
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full() -> TerminalCommand {
+        TerminalCommand::new(["tmux", "attach", "-t", "tr-x"])
+            .working_dir("/home/me/proj")
+            .class(Some("total-recall"))
+    }
+
+    fn bare() -> TerminalCommand {
+        TerminalCommand::default()
+    }
+
+    #[test]
+    fn test_ghostty_argv() {
+        assert_eq!(
+            Ghostty.argv(&full()),
+            [
+                "ghostty",
+                "--working-directory=/home/me/proj",
+                "--class=total-recall",
+                "-e",
+                "tmux",
+                "attach",
+                "-t",
+                "tr-x"
+            ]
+        );
+        assert_eq!(Ghostty.argv(&bare()), ["ghostty"]);
+    }
+
+    #[test]
+    fn test_wezterm_argv() {
+        assert_eq!(
+            Wezterm.argv(&full()),
+            [
+                "wezterm",
+                "start",
+                "--cwd",
+                "/home/me/proj",
+                "--class",
+                "total-recall",
+                "--",
+                "tmux",
+                "attach",
+                "-t",
+                "tr-x"
+            ]
+        );
+        assert_eq!(Wezterm.argv(&bare()), ["wezterm", "start"]);
+    }
+
+    #[test]
+    fn test_kitty_argv() {
+        assert_eq!(
+            Kitty.argv(&full()),
+            [
+                "kitty",
+                "--directory",
+                "/home/me/proj",
+                "--class",
+                "total-recall",
+                "tmux",
+                "attach",
+                "-t",
+                "tr-x"
+            ]
+        );
+        assert_eq!(Kitty.argv(&bare()), ["kitty"]);
+    }
+
+    #[test]
+    fn test_alacritty_argv() {
+        assert_eq!(
+            Alacritty.argv(&full()),
+            [
+                "alacritty",
+                "--working-directory",
+                "/home/me/proj",
+                "--class",
+                "total-recall",
+                "-e",
+                "tmux",
+                "attach",
+                "-t",
+                "tr-x"
+            ]
+        );
+        assert_eq!(Alacritty.argv(&bare()), ["alacritty"]);
+    }
+
+    #[test]
+    fn test_foot_argv() {
+        assert_eq!(
+            Foot.argv(&full()),
+            [
+                "foot",
+                "--working-directory=/home/me/proj",
+                "--app-id=total-recall",
+                "tmux",
+                "attach",
+                "-t",
+                "tr-x"
+            ]
+        );
+        assert_eq!(Foot.argv(&bare()), ["foot"]);
+    }
+
+    #[test]
+    fn test_gnome_terminal_argv() {
+        assert_eq!(
+            GnomeTerminal.argv(&full()),
+            [
+                "gnome-terminal",
+                "--working-directory=/home/me/proj",
+                "--class=total-recall",
+                "--",
+                "tmux",
+                "attach",
+                "-t",
+                "tr-x"
+            ]
+        );
+        assert_eq!(GnomeTerminal.argv(&bare()), ["gnome-terminal"]);
+    }
+
+    #[test]
+    fn test_konsole_argv() {
+        assert_eq!(
+            Konsole.argv(&full()),
+            ["konsole", "--workdir", "/home/me/proj", "-e", "tmux", "attach", "-t", "tr-x"]
+        );
+        assert_eq!(Konsole.argv(&bare()), ["konsole"]);
+    }
+
+    #[test]
+    fn test_xterm_argv() {
+        assert_eq!(
+            Xterm.argv(&full()),
+            ["xterm", "-class", "total-recall", "-e", "tmux", "attach", "-t", "tr-x"]
+        );
+        assert_eq!(Xterm.argv(&bare()), ["xterm"]);
+        // No cwd flag, so the process itself starts there
+        let command = Xterm.command(&full());
+        assert_eq!(command.get_current_dir(), Some(Path::new("/home/me/proj")));
+    }
+
+    #[test]
+    fn test_by_name() {
+        assert_eq!(by_name("WezTerm").unwrap().name(), "wezterm");
+        assert_eq!(by_name("/usr/bin/kitty").unwrap().name(), "kitty");
+        assert_eq!(by_name("gnome-terminal").unwrap().name(), "gnome-terminal");
+        assert!(by_name("Apple_Terminal").is_none());
+    }
+}
@@ -0,0 +1,273 @@
+//! Tool call analytics: call counts, error rates, latency and the Bash
+//! commands run, over one session or many.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::models::{ContentBlock, MessageContent, MessageEntry, Session};
+use crate::services::SessionStore;
+
+/// Programs whose first argument is a subcommand worth keeping (`cargo test`).
+const SUBCOMMAND_PROGRAMS: &[&str] = &[
+    "cargo", "git", "jj", "npm", "pnpm", "yarn", "bun", "go", "docker", "kubectl", "make", "uv", "pip", "gh",
+    "nix", "dotnet", "mvn", "gradle", "poetry",
+];
+
+/// Calls, failures and latency for one tool or command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ToolCounter {
+    pub calls: usize,
+    pub errors: usize,
+    /// Sum of tool-to-result latencies, over calls that got a result
+    pub latency_ms_total: u64,
+    pub latency_samples: usize,
+    pub max_latency_ms: u64,
+}
+
+impl ToolCounter {
+    /// Failed calls as a fraction of all calls.
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+
+    pub fn mean_latency_ms(&self) -> Option<u64> {
+        (self.latency_samples > 0).then(|| self.latency_ms_total / self.latency_samples as u64)
+    }
+
+    fn add(&mut self, other: &ToolCounter) {
+        self.calls += other.calls;
+        self.errors += other.errors;
+        self.latency_ms_total += other.latency_ms_total;
+        self.latency_samples += other.latency_samples;
+        self.max_latency_ms = self.max_latency_ms.max(other.max_latency_ms);
+    }
+
+    fn record_latency(&mut self, ms: u64) {
+        self.latency_ms_total += ms;
+        self.latency_samples += 1;
+        self.max_latency_ms = self.max_latency_ms.max(ms);
+    }
+}
+
+/// Tool calls made by one session.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionToolSummary {
+    pub session_id: String,
+    pub name: String,
+    pub project_path: String,
+    pub calls: usize,
+    pub errors: usize,
+}
+
+/// Tool analytics over one or more sessions.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ToolStats {
+    /// Tool name -> counts
+    pub tools: BTreeMap<String, ToolCounter>,
+    /// Bash command (program and subcommand) -> counts
+    pub bash_commands: BTreeMap<String, ToolCounter>,
+    /// Local day -> calls made that day
+    pub by_day: BTreeMap<NaiveDate, ToolCounter>,
+    /// Per-session totals, when built from sessions
+    pub sessions: Vec<SessionToolSummary>,
+}
+
+impl ToolStats {
+    /// Analyze one session's entries.
+    pub fn from_entries(entries: &[MessageEntry]) -> Self {
+        let mut stats = Self::default();
+        // tool_use id -> (tool, bash command, call time)
+        let mut pending: HashMap<&str, (&str, Option<String>, DateTime<Utc>)> = HashMap::new();
+
+        for entry in entries {
+            let Some(MessageContent::Structured { content, .. }) = &entry.message else {
+                continue;
+            };
+            for block in content {
+                match block {
+                    ContentBlock::ToolUse { id, name, input } => {
+                        let command = (name == "Bash")
+                            .then(|| input.get("command").and_then(Value::as_str).map(command_head))
+                            .flatten();
+                        let day = entry.timestamp.with_timezone(&Local).date_naive();
+
+                        stats.tools.entry(name.clone()).or_default().calls += 1;
+                        stats.by_day.entry(day).or_default().calls += 1;
+                        if let Some(command) = &command {
+                            stats.bash_commands.entry(command.clone()).or_default().calls += 1;
+                        }
+                        pending.insert(id, (name, command, entry.timestamp));
+                    }
+                    ContentBlock::ToolResult { tool_use_id, is_error, .. } => {
+                        let Some((name, command, called_at)) = pending.remove(tool_use_id.as_str()) else {
+                            continue;
+                        };
+                        let latency = (entry.timestamp - called_at).num_milliseconds().max(0) as u64;
+                        let day = called_at.with_timezone(&Local).date_naive();
+                        let error = usize::from(*is_error);
+
+                        if let Some(tool) = stats.tools.get_mut(name) {
+                            tool.errors += error;
+                            tool.record_latency(latency);
+                        }
+                        if let Some(day) = stats.by_day.get_mut(&day) {
+                            day.errors += error;
+                        }
+                        if let Some(counter) = command.and_then(|c| stats.bash_commands.get_mut(&c)) {
+                            counter.errors += error;
+                            counter.record_latency(latency);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        stats
+    }
+
+    /// Analyze several sessions, reading each JSONL.
+    ///
+    /// Sessions that fail to load are skipped with a warning.
+    pub fn for_sessions<'a>(sessions: impl IntoIterator<Item = &'a Session>) -> Self {
+        let mut stats = Self::default();
+        for session in sessions {
+            let session_stats = match SessionStore::load_entries(&session.file_path) {
+                Ok(entries) => Self::from_entries(&entries),
+                Err(e) => {
+                    tracing::warn!("Skipping {} in tool stats: {}", session.id, e);
+                    continue;
+                }
+            };
+            let totals = session_stats.totals();
+            stats.merge(session_stats);
+            stats.sessions.push(SessionToolSummary {
+                session_id: session.id.clone(),
+                name: session.display_name(),
+                project_path: session.project_path.clone(),
+                calls: totals.calls,
+                errors: totals.errors,
+            });
+        }
+        stats
+    }
+
+    pub fn merge(&mut self, other: ToolStats) {
+        for (map, other) in [(&mut self.tools, other.tools), (&mut self.bash_commands, other.bash_commands)] {
+            for (key, counter) in other {
+                map.entry(key).or_default().add(&counter);
+            }
+        }
+        for (day, counter) in other.by_day {
+            self.by_day.entry(day).or_default().add(&counter);
+        }
+        self.sessions.extend(other.sessions);
+    }
+
+    /// All tools combined.
+    pub fn totals(&self) -> ToolCounter {
+        let mut total = ToolCounter::default();
+        for counter in self.tools.values() {
+            total.add(counter);
+        }
+        total
+    }
+
+    /// Tools, most called first.
+    pub fn tools_by_calls(&self) -> Vec<(&str, &ToolCounter)> {
+        by_calls(&self.tools)
+    }
+
+    /// Bash commands, most run first.
+    pub fn top_bash_commands(&self) -> Vec<(&str, &ToolCounter)> {
+        by_calls(&self.bash_commands)
+    }
+
+    /// Sessions with failed tool calls, most failures first.
+    pub fn thrashing_sessions(&self) -> Vec<&SessionToolSummary> {
+        let mut sessions: Vec<_> = self.sessions.iter().filter(|s| s.errors > 0).collect();
+        sessions.sort_by(|a, b| b.errors.cmp(&a.errors).then(b.calls.cmp(&a.calls)));
+        sessions
+    }
+}
+
+fn by_calls(map: &BTreeMap<String, ToolCounter>) -> Vec<(&str, &ToolCounter)> {
+    let mut rows: Vec<_> = map.iter().map(|(k, v)| (k.as_str(), v)).collect();
+    rows.sort_by_key(|(_, counter)| Reverse(counter.calls));
+    rows
+}
+
+/// Group key for a shell command: its program, plus the subcommand for
+/// tools like `cargo` and `git`.
+fn command_head(command: &str) -> String {
+    let first = command.split(['&', ';', '|', '\n']).next().unwrap_or_default();
+    let mut words = first
+        .split_whitespace()
+        .skip_while(|w| *w == "sudo" || (w.contains('=') && !w.starts_with('-')));
+    let Some(program) = words.next() else {
+        return command.trim().to_string();
+    };
+    let program = Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program);
+
+    match words.next() {
+        Some(sub) if SUBCOMMAND_PROGRAMS.contains(&program) && !sub.starts_with('-') => {
+            format!("{} {}", program, sub)
+        }
+        _ => program.to_string(),
+    }
+}
+
+/// "850ms", "12.3s" or "4m 05s".
+pub fn format_latency(ms: u64) -> String {
+    match ms {
+        0..=999 => format!("{}ms", ms),
+        1_000..=59_999 => format!("{:.1}s", ms as f64 / 1000.0),
+        _ => format!("{}m {:02}s", ms / 60_000, ms % 60_000 / 1000),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_stats_counts_errors_and_latency() {
+        let entries = vec![
+            MessageEntry::test(
+                "assistant",
+                "2026-01-01T10:00:00Z",
+                serde_json::json!([
+                    { "type": "tool_use", "id": "1", "name": "Bash", "input": { "command": "RUST_LOG=debug cargo test -p core" } },
+                    { "type": "tool_use", "id": "2", "name": "Read", "input": { "file_path": "/a" } },
+                ]),
+            ),
+            MessageEntry::test(
+                "user",
+                "2026-01-01T10:00:02Z",
+                serde_json::json!([
+                    { "type": "tool_result", "tool_use_id": "1", "content": "failed", "is_error": true },
+                    { "type": "tool_result", "tool_use_id": "2", "content": "ok" },
+                ]),
+            ),
+        ];
+
+        let stats = ToolStats::from_entries(&entries);
+        let bash = &stats.tools["Bash"];
+        assert_eq!((bash.calls, bash.errors, bash.mean_latency_ms()), (1, 1, Some(2000)));
+        assert_eq!(stats.tools["Read"].errors, 0);
+        assert_eq!(stats.top_bash_commands()[0].0, "cargo test");
+        let totals = stats.totals();
+        assert_eq!((totals.calls, totals.errors), (2, 1));
+        assert_eq!(command_head("/usr/bin/ls -la && echo hi"), "ls");
+    }
+}
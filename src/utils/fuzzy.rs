@@ -0,0 +1,161 @@
+//! Fuzzy subsequence matching for list filters.
+
+/// Score for each matched character.
+const SCORE_MATCH: i64 = 16;
+/// Bonus when a match directly follows the previous one.
+const BONUS_CONSECUTIVE: i64 = 16;
+/// Bonus for matching at the start of a word (after `/`, `-`, `_`, space, or a case change).
+const BONUS_BOUNDARY: i64 = 12;
+/// Largest penalty charged for a single gap between matches.
+const MAX_GAP_PENALTY: i64 = 10;
+
+/// Result of matching a query against several fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldsMatch {
+    /// Higher is better
+    pub score: i64,
+    /// Matched char indices, one list per field
+    pub positions: Vec<Vec<usize>>,
+}
+
+/// Match every whitespace-separated term of `query` against any of `fields`.
+///
+/// Each term may match a different field; a term that matches no field fails
+/// the whole query. An empty query matches everything with score 0.
+pub fn match_fields(query: &str, fields: &[&str]) -> Option<FieldsMatch> {
+    let mut result = FieldsMatch {
+        score: 0,
+        positions: vec![Vec::new(); fields.len()],
+    };
+
+    for term in query.split_whitespace() {
+        let (field, score, positions) = fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, text)| fuzzy_match(term, text).map(|(score, pos)| (i, score, pos)))
+            .max_by_key(|(i, score, _)| (*score, std::cmp::Reverse(*i)))?;
+        result.score += score;
+        result.positions[field].extend(positions);
+    }
+
+    for positions in &mut result.positions {
+        positions.sort_unstable();
+        positions.dedup();
+    }
+    Some(result)
+}
+
+/// Match `pattern` as a subsequence of `text`, returning the score and the
+/// char indices of the matched characters.
+///
+/// Case-insensitive unless the pattern contains an uppercase letter.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let case_sensitive = pattern.chars().any(char::is_uppercase);
+    // One char per char keeps the folded indices in line with `original`
+    let fold = |c: char| if case_sensitive { c } else { c.to_lowercase().next().unwrap_or(c) };
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    let original: Vec<char> = text.chars().collect();
+    let text: Vec<char> = original.iter().copied().map(fold).collect();
+
+    let first = *pattern.first()?;
+    let mut best: Option<(i64, Vec<usize>)> = None;
+
+    // Greedy match from every occurrence of the first character; keep the best
+    for start in (0..text.len()).filter(|&i| text[i] == first) {
+        let mut positions = vec![start];
+        let mut next = start + 1;
+        for &c in &pattern[1..] {
+            match text[next..].iter().position(|&t| t == c) {
+                Some(offset) => {
+                    positions.push(next + offset);
+                    next += offset + 1;
+                }
+                None => break,
+            }
+        }
+        if positions.len() < pattern.len() {
+            // Later starts can't do better than running out of text here
+            break;
+        }
+
+        let score = score_positions(&original, &positions);
+        let better = match &best {
+            Some((best_score, _)) => score > *best_score,
+            None => true,
+        };
+        if better {
+            best = Some((score, positions));
+        }
+    }
+
+    best
+}
+
+fn score_positions(text: &[char], positions: &[usize]) -> i64 {
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+
+    for &pos in positions {
+        score += SCORE_MATCH;
+        if is_word_start(text, pos) {
+            score += BONUS_BOUNDARY;
+        }
+        match previous {
+            Some(prev) if pos == prev + 1 => score += BONUS_CONSECUTIVE,
+            Some(prev) => score -= ((pos - prev - 1) as i64).min(MAX_GAP_PENALTY),
+            None => score -= (pos as i64).min(MAX_GAP_PENALTY),
+        }
+        previous = Some(pos);
+    }
+
+    score
+}
+
+fn is_word_start(text: &[char], pos: usize) -> bool {
+    if pos == 0 {
+        return true;
+    }
+    let (prev, current) = (text[pos - 1], text[pos]);
+    !prev.is_alphanumeric() || (prev.is_lowercase() && current.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match_subsequence() {
+        assert_eq!(fuzzy_match("trc", "total-recall").map(|(_, p)| p), Some(vec![0, 6, 8]));
+        assert!(fuzzy_match("xyz", "total-recall").is_none());
+        assert!(fuzzy_match("", "anything").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_match_prefers_word_starts_and_runs() {
+        let (boundary, _) = fuzzy_match("rc", "my-rcfile").unwrap();
+        let (mid_word, _) = fuzzy_match("rc", "myarcfile").unwrap();
+        assert!(boundary > mid_word);
+
+        let (run, _) = fuzzy_match("cos", "jwst-cosmos").unwrap();
+        let (gappy, _) = fuzzy_match("cos", "c-o-s").unwrap();
+        assert!(run > gappy);
+    }
+
+    #[test]
+    fn test_fuzzy_match_smart_case() {
+        assert!(fuzzy_match("api", "MyAPI").is_some());
+        assert!(fuzzy_match("API", "my-api").is_none());
+        assert_eq!(fuzzy_match("été", "/home/me/Été-2026").map(|(_, p)| p), Some(vec![9, 10, 11]));
+        assert!(fuzzy_match("Été", "/home/me/été-2026").is_none());
+    }
+
+    #[test]
+    fn test_match_fields_terms_across_fields() {
+        let fields = ["jwst-cosmos", "/home/me/Projects/jwst-cosmos", "feature/oauth"];
+        let m = match_fields("cos oauth", &fields).unwrap();
+        assert_eq!(m.positions[0], vec![5, 6, 7]);
+        assert_eq!(m.positions[2], vec![8, 9, 10, 11, 12]);
+        assert!(match_fields("cos nope", &fields).is_none());
+        assert_eq!(match_fields("  ", &fields).map(|m| m.score), Some(0));
+    }
+}
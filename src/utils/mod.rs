@@ -1,4 +1,12 @@
 //! Utility modules.
 
+pub mod fuzzy;
+pub mod ordering;
+pub mod shell;
+pub mod text;
+
+pub use shell::shell_quote;
+pub use text::{local_time, truncate};
+
 // Re-export path utilities from models
 pub use crate::models::project::{decode_project_path, encode_project_path};
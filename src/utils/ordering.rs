@@ -0,0 +1,190 @@
+//! Sort orders and project grouping for the browser lists.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{ProjectGrouping, SortMode};
+use crate::models::{Project, Session};
+
+/// Sort projects in place. Ties keep their existing (recency) order.
+pub fn sort_projects(projects: &mut [Project], mode: SortMode) {
+    match mode {
+        SortMode::Recency => projects.sort_by_key(|p| Reverse(p.last_activity)),
+        SortMode::Name => projects.sort_by_cached_key(|p| p.display_name.to_lowercase()),
+        SortMode::SessionCount => projects.sort_by_key(|p| Reverse(p.session_count)),
+        SortMode::MessageCount => projects.sort_by_key(|p| Reverse(p.total_messages)),
+        SortMode::FileSize => projects.sort_by_key(|p| Reverse(p.total_size)),
+        SortMode::TokenUsage => projects.sort_by_key(|p| Reverse(p.usage.tokens().total())),
+    }
+}
+
+/// Sort sessions in place. Ties keep their existing (recency) order.
+pub fn sort_sessions(sessions: &mut [&Session], mode: SortMode) {
+    match mode {
+        SortMode::Recency | SortMode::SessionCount => sessions.sort_by_key(|s| Reverse(s.last_message)),
+        SortMode::Name => sessions.sort_by_cached_key(|s| session_label(s).to_lowercase()),
+        SortMode::MessageCount => sessions.sort_by_key(|s| Reverse(s.message_count)),
+        SortMode::FileSize => sessions.sort_by_key(|s| Reverse(s.file_size)),
+        SortMode::TokenUsage => sessions.sort_by_key(|s| Reverse(s.usage_totals().tokens().total())),
+    }
+}
+
+/// What the session list shows for a session: its title, else its first
+/// prompt, else its name.
+fn session_label(session: &Session) -> String {
+    if session.meta.title.is_some() || session.preview_text.is_empty() {
+        session.display_name()
+    } else {
+        session.preview_text.clone()
+    }
+}
+
+/// Reorder `projects` so each group is contiguous, returning the group
+/// headers and sizes in display order.
+///
+/// Groups appear in the order of their first project, so the sort order
+/// still decides which group comes first. Returns no groups for
+/// `ProjectGrouping::None`. Repository roots are looked up once per project
+/// path and kept in `repo_roots`.
+pub fn group_projects(
+    projects: &mut Vec<Project>,
+    grouping: ProjectGrouping,
+    repo_roots: &mut HashMap<String, Option<PathBuf>>,
+) -> Vec<(String, usize)> {
+    if grouping == ProjectGrouping::None {
+        return Vec::new();
+    }
+
+    let mut groups: Vec<(String, Vec<Project>)> = Vec::new();
+    for project in projects.drain(..) {
+        let key = group_key(&project.decoded_path, grouping, repo_roots);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, members)) => members.push(project),
+            None => groups.push((key, vec![project])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, members)| {
+            let count = members.len();
+            projects.extend(members);
+            (key, count)
+        })
+        .collect()
+}
+
+/// Header label of the group a project path belongs to.
+fn group_key(path: &str, grouping: ProjectGrouping, repo_roots: &mut HashMap<String, Option<PathBuf>>) -> String {
+    let key = match grouping {
+        ProjectGrouping::None => None,
+        ProjectGrouping::Parent => Path::new(path).parent().map(Path::to_path_buf),
+        ProjectGrouping::Repo => repo_roots
+            .entry(path.to_string())
+            .or_insert_with(|| repo_root(Path::new(path)))
+            .clone(),
+    };
+    match key {
+        Some(dir) => shorten_home(&dir),
+        None if grouping == ProjectGrouping::Repo => "(no repository)".to_string(),
+        None => "/".to_string(),
+    }
+}
+
+/// Root of the main repository containing `path`.
+///
+/// Linked git worktrees and jj workspaces resolve to the repository they
+/// were created from.
+pub fn repo_root(path: &Path) -> Option<PathBuf> {
+    for dir in path.ancestors() {
+        let git = dir.join(".git");
+        if git.is_dir() {
+            return Some(dir.to_path_buf());
+        }
+        if git.is_file() {
+            return Some(git_worktree_main(dir, &git).unwrap_or_else(|| dir.to_path_buf()));
+        }
+
+        let jj = dir.join(".jj");
+        if jj.is_dir() {
+            return Some(jj_workspace_main(&jj).unwrap_or_else(|| dir.to_path_buf()));
+        }
+    }
+    None
+}
+
+/// Main checkout of a linked git worktree, from its `.git` file.
+///
+/// The file points at `<main>/.git/worktrees/<name>`, whose `commondir`
+/// leads back to `<main>/.git`. Submodules have no `commondir` and stay
+/// their own root.
+fn git_worktree_main(dir: &Path, git_file: &Path) -> Option<PathBuf> {
+    let contents = fs::read_to_string(git_file).ok()?;
+    let gitdir = dir.join(contents.strip_prefix("gitdir:")?.trim());
+    let commondir = fs::read_to_string(gitdir.join("commondir")).ok()?;
+    let common = gitdir.join(commondir.trim()).canonicalize().ok()?;
+    common.parent().map(Path::to_path_buf)
+}
+
+/// Main workspace of a jj repository, from a secondary workspace's `.jj/repo` file.
+fn jj_workspace_main(jj_dir: &Path) -> Option<PathBuf> {
+    let repo = jj_dir.join("repo");
+    if !repo.is_file() {
+        return None;
+    }
+    let target = fs::read_to_string(&repo).ok()?;
+    let store = jj_dir.join(target.trim()).canonicalize().ok()?;
+    // <main>/.jj/repo -> <main>
+    store.parent()?.parent().map(Path::to_path_buf)
+}
+
+/// Replace a leading home directory with `~`.
+fn shorten_home(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_root_follows_worktrees() {
+        let tmp = std::env::temp_dir().join(format!("tr-repo-root-{}", uuid::Uuid::new_v4()));
+        let main = tmp.join("app");
+        let worktree = tmp.join("app-feature");
+        fs::create_dir_all(main.join(".git/worktrees/feature")).unwrap();
+        fs::create_dir_all(main.join("crates/core")).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(main.join(".git/worktrees/feature/commondir"), "../..\n").unwrap();
+        fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", main.join(".git/worktrees/feature").display()),
+        )
+        .unwrap();
+
+        let main = main.canonicalize().unwrap();
+        assert_eq!(repo_root(&main.join("crates/core")), Some(main.clone()));
+        assert_eq!(repo_root(&worktree), Some(main));
+
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+
+    #[test]
+    fn test_group_projects_keeps_sort_order() {
+        let mut projects: Vec<Project> = ["/work/b/one", "/work/a/two", "/work/b/three"]
+            .iter()
+            .map(|path| Project::new(String::new(), path.to_string()))
+            .collect();
+
+        let groups = group_projects(&mut projects, ProjectGrouping::Parent, &mut HashMap::new());
+        assert_eq!(groups, vec![("/work/b".to_string(), 2), ("/work/a".to_string(), 1)]);
+        let names: Vec<&str> = projects.iter().map(|p| p.display_name.as_str()).collect();
+        assert_eq!(names, ["one", "three", "two"]);
+    }
+}
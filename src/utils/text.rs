@@ -0,0 +1,19 @@
+//! Text helpers for fixed-width columns.
+
+use chrono::{DateTime, Local, Utc};
+
+/// Shorten `text` to at most `max_chars` characters, ending in `…` when cut.
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let mut out: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
+
+/// A timestamp in local time, to the minute.
+pub fn local_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}